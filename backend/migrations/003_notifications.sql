@@ -0,0 +1,18 @@
+-- 创建站内通知表
+CREATE TABLE notifications (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    actor_id TEXT,
+    kind TEXT NOT NULL CHECK (kind IN ('task_assigned', 'status_changed', 'commented', 'mentioned', 'due_soon')),
+    invite_id TEXT,
+    task_id TEXT,
+    message TEXT NOT NULL,
+    read_at DATETIME,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id),
+    FOREIGN KEY (actor_id) REFERENCES users(id),
+    FOREIGN KEY (invite_id) REFERENCES invites(id),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_notifications_user ON notifications(user_id, read_at);
@@ -0,0 +1,36 @@
+-- 添加任务截止时间
+ALTER TABLE tasks ADD COLUMN due_date DATETIME;
+ALTER TABLE tasks ADD COLUMN overdue_notified_at DATETIME;
+
+-- 创建用户通知偏好表
+CREATE TABLE notification_preferences (
+    user_id TEXT PRIMARY KEY,
+    email_invite_redeemed BOOLEAN NOT NULL DEFAULT 1,
+    email_task_completed BOOLEAN NOT NULL DEFAULT 1,
+    email_task_overdue BOOLEAN NOT NULL DEFAULT 1,
+    daily_digest BOOLEAN NOT NULL DEFAULT 0,
+    digest_hour INTEGER NOT NULL DEFAULT 8 CHECK (digest_hour BETWEEN 0 AND 23),
+    last_digest_at DATETIME,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id)
+);
+
+-- 创建邮件发件箱表
+CREATE TABLE email_outbox (
+    id TEXT PRIMARY KEY,
+    user_id TEXT,
+    to_address TEXT NOT NULL,
+    template TEXT NOT NULL,
+    subject TEXT NOT NULL,
+    body TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'sent', 'failed')),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at DATETIME,
+    last_error TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    sent_at DATETIME,
+    FOREIGN KEY (user_id) REFERENCES users(id)
+);
+
+CREATE INDEX idx_tasks_due_date ON tasks(due_date);
+CREATE INDEX idx_email_outbox_pending ON email_outbox(status, next_attempt_at);
@@ -0,0 +1,44 @@
+-- 添加临期提醒标记
+ALTER TABLE tasks ADD COLUMN due_soon_notified_at DATETIME;
+
+-- 创建定时任务表
+CREATE TABLE jobs (
+    id TEXT PRIMARY KEY,
+    name TEXT UNIQUE NOT NULL,
+    interval_secs INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'scheduled' CHECK (status IN ('scheduled', 'running', 'failed')),
+    run_at DATETIME NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_run_at DATETIME,
+    last_success_at DATETIME,
+    last_error TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_jobs_run_at ON jobs(status, run_at);
+
+-- 重建通知表，增加overdue类型
+CREATE TABLE notifications_new (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    actor_id TEXT,
+    kind TEXT NOT NULL CHECK (kind IN ('task_assigned', 'status_changed', 'commented', 'mentioned', 'due_soon', 'overdue')),
+    invite_id TEXT,
+    task_id TEXT,
+    message TEXT NOT NULL,
+    read_at DATETIME,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id),
+    FOREIGN KEY (actor_id) REFERENCES users(id),
+    FOREIGN KEY (invite_id) REFERENCES invites(id),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+INSERT INTO notifications_new (id, user_id, actor_id, kind, invite_id, task_id, message, read_at, created_at)
+SELECT id, user_id, actor_id, kind, invite_id, task_id, message, read_at, created_at FROM notifications;
+
+DROP TABLE notifications;
+ALTER TABLE notifications_new RENAME TO notifications;
+
+CREATE INDEX idx_notifications_user ON notifications(user_id, read_at);
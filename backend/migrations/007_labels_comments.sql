@@ -0,0 +1,35 @@
+-- 看板标签
+CREATE TABLE labels (
+    id TEXT PRIMARY KEY,
+    invite_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    color TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (invite_id, name),
+    FOREIGN KEY (invite_id) REFERENCES invites(id) ON DELETE CASCADE
+);
+
+-- 任务与标签的关联
+CREATE TABLE task_labels (
+    task_id TEXT NOT NULL,
+    label_id TEXT NOT NULL,
+    PRIMARY KEY (task_id, label_id),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (label_id) REFERENCES labels(id) ON DELETE CASCADE
+);
+
+-- 任务评论，author_name保存导入数据中的外部作者名
+CREATE TABLE comments (
+    id TEXT PRIMARY KEY,
+    task_id TEXT NOT NULL,
+    author_id TEXT,
+    author_name TEXT,
+    body TEXT NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (author_id) REFERENCES users(id)
+);
+
+CREATE INDEX idx_labels_invite ON labels(invite_id);
+CREATE INDEX idx_task_labels_label ON task_labels(label_id);
+CREATE INDEX idx_comments_task ON comments(task_id, created_at);
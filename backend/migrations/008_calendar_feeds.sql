@@ -0,0 +1,7 @@
+-- 用户日历订阅令牌，订阅地址不需要登录，泄露后可重新生成
+CREATE TABLE calendar_feeds (
+    user_id TEXT PRIMARY KEY,
+    token TEXT UNIQUE NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
@@ -0,0 +1,22 @@
+-- 任务状态变更历史，用于统计周期时间和累积流图
+CREATE TABLE task_status_history (
+    id TEXT PRIMARY KEY,
+    task_id TEXT NOT NULL,
+    from_status TEXT,
+    to_status TEXT NOT NULL,
+    actor_id TEXT,
+    changed_at DATETIME NOT NULL,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_status_history_task ON task_status_history(task_id, changed_at);
+
+-- 根据已有时间补全历史：创建时为todo，进行中的任务按最后更新时间，已完成的任务按完成时间
+INSERT INTO task_status_history (id, task_id, from_status, to_status, actor_id, changed_at)
+SELECT lower(hex(randomblob(16))), id, NULL, 'todo', NULL, created_at FROM tasks;
+
+INSERT INTO task_status_history (id, task_id, from_status, to_status, actor_id, changed_at)
+SELECT lower(hex(randomblob(16))), id, 'todo', 'in_progress', NULL, updated_at FROM tasks WHERE status = 'in_progress';
+
+INSERT INTO task_status_history (id, task_id, from_status, to_status, actor_id, changed_at)
+SELECT lower(hex(randomblob(16))), id, 'todo', 'done', NULL, COALESCE(completed_at, updated_at) FROM tasks WHERE status = 'done';
@@ -0,0 +1,25 @@
+-- 里程碑/迭代
+CREATE TABLE milestones (
+    id TEXT PRIMARY KEY,
+    invite_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    start_date DATE NOT NULL,
+    end_date DATE NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    CHECK (end_date >= start_date),
+    FOREIGN KEY (invite_id) REFERENCES invites(id) ON DELETE CASCADE
+);
+
+-- 里程碑包含的任务，added_at用于燃尽图计算范围变化
+CREATE TABLE milestone_tasks (
+    milestone_id TEXT NOT NULL,
+    task_id TEXT NOT NULL,
+    added_at DATETIME NOT NULL,
+    PRIMARY KEY (milestone_id, task_id),
+    FOREIGN KEY (milestone_id) REFERENCES milestones(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_milestones_invite ON milestones(invite_id);
+CREATE INDEX idx_milestone_tasks_task ON milestone_tasks(task_id);
@@ -0,0 +1,32 @@
+-- 周期任务模板
+CREATE TABLE recurring_tasks (
+    id TEXT PRIMARY KEY,
+    invite_id TEXT NOT NULL,
+    creator_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT,
+    rrule TEXT NOT NULL,
+    start_at DATETIME NOT NULL,
+    due_in_hours INTEGER,
+    paused BOOLEAN NOT NULL DEFAULT 0,
+    next_run_at DATETIME,
+    last_run_at DATETIME,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (invite_id) REFERENCES invites(id) ON DELETE CASCADE,
+    FOREIGN KEY (creator_id) REFERENCES users(id)
+);
+
+-- 已生成的重复实例，主键保证同一次重复只创建一个任务
+CREATE TABLE recurring_task_occurrences (
+    recurring_task_id TEXT NOT NULL,
+    occurrence_at DATETIME NOT NULL,
+    task_id TEXT,
+    created_at DATETIME NOT NULL,
+    PRIMARY KEY (recurring_task_id, occurrence_at),
+    FOREIGN KEY (recurring_task_id) REFERENCES recurring_tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_recurring_tasks_invite ON recurring_tasks(invite_id);
+CREATE INDEX idx_recurring_tasks_next_run ON recurring_tasks(paused, next_run_at);
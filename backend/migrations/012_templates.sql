@@ -0,0 +1,32 @@
+-- 任务优先级，为空表示未设置
+ALTER TABLE tasks ADD COLUMN priority TEXT CHECK (priority IN ('low', 'medium', 'high'));
+
+-- 任务模板，labels和checklist为JSON字符串数组
+CREATE TABLE task_templates (
+    id TEXT PRIMARY KEY,
+    creator_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    title_pattern TEXT NOT NULL,
+    description TEXT,
+    labels TEXT NOT NULL DEFAULT '[]',
+    checklist TEXT NOT NULL DEFAULT '[]',
+    priority TEXT CHECK (priority IN ('low', 'medium', 'high')),
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (creator_id) REFERENCES users(id)
+);
+
+-- 看板模板，tasks为任务定义的JSON数组，格式与任务模板相同
+CREATE TABLE board_templates (
+    id TEXT PRIMARY KEY,
+    creator_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    tasks TEXT NOT NULL DEFAULT '[]',
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (creator_id) REFERENCES users(id)
+);
+
+CREATE INDEX idx_task_templates_creator ON task_templates(creator_id);
+CREATE INDEX idx_board_templates_creator ON board_templates(creator_id);
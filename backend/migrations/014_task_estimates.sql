@@ -0,0 +1,3 @@
+-- 任务预估工作量，单位为故事点或小时，为空表示未预估
+ALTER TABLE tasks ADD COLUMN estimate REAL CHECK (estimate >= 0);
+ALTER TABLE tasks ADD COLUMN estimate_unit TEXT CHECK (estimate_unit IN ('points', 'hours'));
@@ -0,0 +1,62 @@
+-- 新增待验收状态 in_review，SQLite 无法修改 CHECK 约束，需要重建任务表
+-- 重建期间关闭外键检查，避免删除旧表时级联删除关联数据
+PRAGMA foreign_keys = OFF;
+
+CREATE TABLE tasks_new (
+    id TEXT PRIMARY KEY,
+    title TEXT NOT NULL,
+    description TEXT,
+    status TEXT DEFAULT 'todo' CHECK (status IN ('todo', 'in_progress', 'in_review', 'done')),
+    creator_id TEXT NOT NULL,
+    executor_id TEXT,
+    invite_id TEXT NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    completion_details TEXT,
+    completed_at DATETIME,
+    due_date DATETIME,
+    overdue_notified_at DATETIME,
+    due_soon_notified_at DATETIME,
+    priority TEXT CHECK (priority IN ('low', 'medium', 'high')),
+    estimate REAL CHECK (estimate >= 0),
+    estimate_unit TEXT CHECK (estimate_unit IN ('points', 'hours')),
+    FOREIGN KEY (creator_id) REFERENCES users(id),
+    FOREIGN KEY (executor_id) REFERENCES users(id),
+    FOREIGN KEY (invite_id) REFERENCES invites(id)
+);
+
+INSERT INTO tasks_new (
+    id, title, description, status, creator_id, executor_id, invite_id, created_at, updated_at,
+    completion_details, completed_at, due_date, overdue_notified_at, due_soon_notified_at,
+    priority, estimate, estimate_unit
+)
+SELECT
+    id, title, description, status, creator_id, executor_id, invite_id, created_at, updated_at,
+    completion_details, completed_at, due_date, overdue_notified_at, due_soon_notified_at,
+    priority, estimate, estimate_unit
+FROM tasks;
+
+DROP TABLE tasks;
+ALTER TABLE tasks_new RENAME TO tasks;
+
+CREATE INDEX idx_tasks_creator ON tasks(creator_id);
+CREATE INDEX idx_tasks_executor ON tasks(executor_id);
+CREATE INDEX idx_tasks_invite ON tasks(invite_id);
+CREATE INDEX idx_tasks_due_date ON tasks(due_date);
+
+PRAGMA foreign_keys = ON;
+
+-- 验收记录，completion_details 保存验收时提交的完成详情
+CREATE TABLE task_reviews (
+    id TEXT PRIMARY KEY,
+    task_id TEXT NOT NULL,
+    reviewer_id TEXT NOT NULL,
+    decision TEXT NOT NULL CHECK (decision IN ('accepted', 'changes_requested')),
+    comment TEXT,
+    completion_details TEXT,
+    created_at DATETIME NOT NULL,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (reviewer_id) REFERENCES users(id)
+);
+
+CREATE INDEX idx_task_reviews_task ON task_reviews(task_id, created_at);
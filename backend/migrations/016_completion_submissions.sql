@@ -0,0 +1,17 @@
+-- 完成详情的每次提交，按任务内的版本号排序
+CREATE TABLE completion_submissions (
+    id TEXT PRIMARY KEY,
+    task_id TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    author_id TEXT,
+    body TEXT NOT NULL,
+    created_at DATETIME NOT NULL,
+    UNIQUE (task_id, version),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (author_id) REFERENCES users(id)
+);
+
+-- 已有的完成详情作为第一版，作者按任务执行者
+INSERT INTO completion_submissions (id, task_id, version, author_id, body, created_at)
+SELECT lower(hex(randomblob(16))), id, 1, executor_id, completion_details, COALESCE(completed_at, updated_at)
+FROM tasks WHERE completion_details IS NOT NULL;
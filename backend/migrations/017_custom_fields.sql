@@ -0,0 +1,18 @@
+-- 看板自定义字段，options为单选和多选的JSON字符串数组
+CREATE TABLE custom_fields (
+    id TEXT PRIMARY KEY,
+    invite_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    field_type TEXT NOT NULL CHECK (field_type IN ('text', 'number', 'date', 'single_select', 'multi_select', 'checkbox')),
+    options TEXT,
+    position INTEGER NOT NULL DEFAULT 0,
+    created_at DATETIME NOT NULL,
+    updated_at DATETIME NOT NULL,
+    UNIQUE (invite_id, name),
+    FOREIGN KEY (invite_id) REFERENCES invites(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_custom_fields_invite ON custom_fields(invite_id, position);
+
+-- 任务的自定义字段值，以字段ID为键的JSON对象
+ALTER TABLE tasks ADD COLUMN custom_fields TEXT;
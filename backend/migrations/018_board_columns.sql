@@ -0,0 +1,78 @@
+-- 看板工作流列，每列属于一个分类：not_started(未开始)、active(进行中)、finished(已完成)
+-- 看板没有配置列时使用默认的 todo、in_progress、in_review、done 四列
+CREATE TABLE board_columns (
+    id TEXT PRIMARY KEY,
+    invite_id TEXT NOT NULL,
+    key TEXT NOT NULL,
+    name TEXT NOT NULL,
+    category TEXT NOT NULL CHECK (category IN ('not_started', 'active', 'finished')),
+    position INTEGER NOT NULL DEFAULT 0,
+    created_at DATETIME NOT NULL,
+    updated_at DATETIME NOT NULL,
+    UNIQUE (invite_id, key),
+    FOREIGN KEY (invite_id) REFERENCES invites(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_board_columns_invite ON board_columns(invite_id, position);
+
+-- 任务状态改为看板列的键，去掉 CHECK 约束，并冗余保存所在列的分类
+-- 重建期间关闭外键检查，避免删除旧表时级联删除关联数据
+PRAGMA foreign_keys = OFF;
+
+CREATE TABLE tasks_new (
+    id TEXT PRIMARY KEY,
+    title TEXT NOT NULL,
+    description TEXT,
+    status TEXT NOT NULL DEFAULT 'todo',
+    status_category TEXT NOT NULL DEFAULT 'not_started' CHECK (status_category IN ('not_started', 'active', 'finished')),
+    creator_id TEXT NOT NULL,
+    executor_id TEXT,
+    invite_id TEXT NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    completion_details TEXT,
+    completed_at DATETIME,
+    due_date DATETIME,
+    overdue_notified_at DATETIME,
+    due_soon_notified_at DATETIME,
+    priority TEXT CHECK (priority IN ('low', 'medium', 'high')),
+    estimate REAL CHECK (estimate >= 0),
+    estimate_unit TEXT CHECK (estimate_unit IN ('points', 'hours')),
+    custom_fields TEXT,
+    FOREIGN KEY (creator_id) REFERENCES users(id),
+    FOREIGN KEY (executor_id) REFERENCES users(id),
+    FOREIGN KEY (invite_id) REFERENCES invites(id)
+);
+
+INSERT INTO tasks_new (
+    id, title, description, status, status_category, creator_id, executor_id, invite_id, created_at, updated_at,
+    completion_details, completed_at, due_date, overdue_notified_at, due_soon_notified_at,
+    priority, estimate, estimate_unit, custom_fields
+)
+SELECT
+    id, title, description, COALESCE(status, 'todo'),
+    CASE status WHEN 'done' THEN 'finished' WHEN 'in_progress' THEN 'active' WHEN 'in_review' THEN 'active' ELSE 'not_started' END,
+    creator_id, executor_id, invite_id, created_at, updated_at,
+    completion_details, completed_at, due_date, overdue_notified_at, due_soon_notified_at,
+    priority, estimate, estimate_unit, custom_fields
+FROM tasks;
+
+DROP TABLE tasks;
+ALTER TABLE tasks_new RENAME TO tasks;
+
+CREATE INDEX idx_tasks_creator ON tasks(creator_id);
+CREATE INDEX idx_tasks_executor ON tasks(executor_id);
+CREATE INDEX idx_tasks_invite ON tasks(invite_id, status);
+CREATE INDEX idx_tasks_due_date ON tasks(due_date);
+
+PRAGMA foreign_keys = ON;
+
+-- 状态历史记录变更时目标列的分类，统计不再依赖具体的列名
+ALTER TABLE task_status_history ADD COLUMN to_category TEXT NOT NULL DEFAULT 'not_started';
+
+UPDATE task_status_history SET to_category = CASE to_status
+    WHEN 'done' THEN 'finished'
+    WHEN 'in_progress' THEN 'active'
+    WHEN 'in_review' THEN 'active'
+    ELSE 'not_started'
+END;
@@ -0,0 +1,2 @@
+-- 看板列的在制品(WIP)上限，为空表示不限制
+ALTER TABLE board_columns ADD COLUMN wip_limit INTEGER CHECK (wip_limit IS NULL OR wip_limit > 0);
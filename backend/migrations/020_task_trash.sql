@@ -0,0 +1,4 @@
+-- 任务软删除，deleted_at 不为空的任务在回收站中，超过保留期后由定时任务彻底删除
+ALTER TABLE tasks ADD COLUMN deleted_at DATETIME;
+
+CREATE INDEX idx_tasks_deleted_at ON tasks(deleted_at);
@@ -0,0 +1,6 @@
+-- 任务归档，归档的任务不显示在看板上，仍计入统计和报表
+ALTER TABLE tasks ADD COLUMN archived_at DATETIME;
+
+-- 看板归档后只读；auto_archive_days 为完成多少天后自动归档任务，为空表示不自动归档
+ALTER TABLE invites ADD COLUMN archived_at DATETIME;
+ALTER TABLE invites ADD COLUMN auto_archive_days INTEGER CHECK (auto_archive_days IS NULL OR auto_archive_days > 0);
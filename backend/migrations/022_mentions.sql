@@ -0,0 +1,20 @@
+-- 描述、完成详情和评论中@提到的看板成员
+-- source: description（任务描述）、completion_details（完成详情）、comment（评论，comment_id不为空）
+CREATE TABLE mentions (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    actor_id TEXT,
+    invite_id TEXT NOT NULL,
+    task_id TEXT NOT NULL,
+    comment_id TEXT,
+    source TEXT NOT NULL CHECK (source IN ('description', 'completion_details', 'comment')),
+    created_at DATETIME NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id),
+    FOREIGN KEY (actor_id) REFERENCES users(id),
+    FOREIGN KEY (invite_id) REFERENCES invites(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (comment_id) REFERENCES comments(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_mentions_user ON mentions(user_id, created_at);
+CREATE INDEX idx_mentions_task ON mentions(task_id, source);
@@ -0,0 +1,28 @@
+-- 客户端和外部导入的时间以前按原时区保存，统一转换为UTC，使按时间比较的查询结果正确
+UPDATE tasks SET due_date = strftime('%Y-%m-%dT%H:%M:%f', due_date) || '+00:00'
+WHERE due_date NOT LIKE '%+00:00' AND strftime('%Y-%m-%dT%H:%M:%f', due_date) IS NOT NULL;
+
+UPDATE tasks SET completed_at = strftime('%Y-%m-%dT%H:%M:%f', completed_at) || '+00:00'
+WHERE completed_at NOT LIKE '%+00:00' AND strftime('%Y-%m-%dT%H:%M:%f', completed_at) IS NOT NULL;
+
+UPDATE tasks SET created_at = strftime('%Y-%m-%dT%H:%M:%f', created_at) || '+00:00'
+WHERE created_at LIKE '%T%' AND created_at NOT LIKE '%+00:00' AND strftime('%Y-%m-%dT%H:%M:%f', created_at) IS NOT NULL;
+
+UPDATE tasks SET updated_at = strftime('%Y-%m-%dT%H:%M:%f', updated_at) || '+00:00'
+WHERE updated_at LIKE '%T%' AND updated_at NOT LIKE '%+00:00' AND strftime('%Y-%m-%dT%H:%M:%f', updated_at) IS NOT NULL;
+
+UPDATE comments SET created_at = strftime('%Y-%m-%dT%H:%M:%f', created_at) || '+00:00'
+WHERE created_at LIKE '%T%' AND created_at NOT LIKE '%+00:00' AND strftime('%Y-%m-%dT%H:%M:%f', created_at) IS NOT NULL;
+
+UPDATE task_status_history SET changed_at = strftime('%Y-%m-%dT%H:%M:%f', changed_at) || '+00:00'
+WHERE changed_at NOT LIKE '%+00:00' AND strftime('%Y-%m-%dT%H:%M:%f', changed_at) IS NOT NULL;
+
+-- 开始和结束时间同时转换，避免违反结束时间不早于开始时间的约束
+UPDATE time_entries SET
+    started_at = CASE WHEN started_at NOT LIKE '%+00:00' THEN strftime('%Y-%m-%dT%H:%M:%f', started_at) || '+00:00' ELSE started_at END,
+    ended_at = CASE WHEN ended_at NOT LIKE '%+00:00' THEN strftime('%Y-%m-%dT%H:%M:%f', ended_at) || '+00:00' ELSE ended_at END
+WHERE (started_at NOT LIKE '%+00:00' AND strftime('%Y-%m-%dT%H:%M:%f', started_at) IS NOT NULL)
+   OR (ended_at NOT LIKE '%+00:00' AND strftime('%Y-%m-%dT%H:%M:%f', ended_at) IS NOT NULL);
+
+UPDATE recurring_tasks SET start_at = strftime('%Y-%m-%dT%H:%M:%f', start_at) || '+00:00'
+WHERE start_at NOT LIKE '%+00:00' AND strftime('%Y-%m-%dT%H:%M:%f', start_at) IS NOT NULL;
@@ -0,0 +1,24 @@
+-- 重建通知表，增加task_reviewed类型
+CREATE TABLE notifications_new (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    actor_id TEXT,
+    kind TEXT NOT NULL CHECK (kind IN ('task_assigned', 'status_changed', 'task_reviewed', 'commented', 'mentioned', 'due_soon', 'overdue')),
+    invite_id TEXT,
+    task_id TEXT,
+    message TEXT NOT NULL,
+    read_at DATETIME,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id),
+    FOREIGN KEY (actor_id) REFERENCES users(id),
+    FOREIGN KEY (invite_id) REFERENCES invites(id),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+INSERT INTO notifications_new (id, user_id, actor_id, kind, invite_id, task_id, message, read_at, created_at)
+SELECT id, user_id, actor_id, kind, invite_id, task_id, message, read_at, created_at FROM notifications;
+
+DROP TABLE notifications;
+ALTER TABLE notifications_new RENAME TO notifications;
+
+CREATE INDEX idx_notifications_user ON notifications(user_id, read_at);
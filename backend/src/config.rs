@@ -1,9 +1,43 @@
 use dotenvy::dotenv;
 use std::env;
 
+#[derive(Clone)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: String,
+    pub tls: String, // "none", "starttls" or "tls"
+}
+
+#[derive(Clone)]
 pub struct Config {
     pub database_url: String,
     pub port: u16,
+    // 前端访问地址，用于拼接邀请链接
+    pub public_base_url: String,
+    // 邀请链接签名密钥
+    // 邀请链接签名密钥，未设置时邀请链接和二维码不可用
+    pub invite_link_secret: Option<String>,
+    // 邀请链接默认有效期（小时）
+    pub invite_link_ttl_hours: i64,
+    // Webhook最大投递次数（含首次）
+    pub webhook_max_attempts: i32,
+    // Webhook请求超时（秒）
+    pub webhook_timeout_secs: u64,
+    // SMTP配置，未设置SMTP_HOST时不发送邮件
+    pub smtp: Option<SmtpConfig>,
+    // 邮件最大发送次数（含首次）
+    pub email_max_attempts: i32,
+    // 临期提醒提前量（小时）
+    pub due_soon_hours: i64,
+    // 未使用邀请码的有效期（天），0表示永不过期
+    pub invite_expiry_days: i64,
+    // 回收站中任务的保留期（天），0表示不自动清理
+    pub trash_retention_days: i64,
+    // 管理接口令牌，未设置时管理接口不可用
+    pub admin_token: Option<String>,
 }
 
 impl Config {
@@ -16,6 +50,63 @@ impl Config {
                 .unwrap_or_else(|_| "20000".to_string())
                 .parse()
                 .unwrap_or(20000),
+            public_base_url: env::var("PUBLIC_BASE_URL")
+                .unwrap_or_else(|_| "http://127.0.0.1:20001".to_string())
+                .trim_end_matches('/')
+                .to_string(),
+            // 没有默认值，避免使用公开的默认密钥签名
+            invite_link_secret: env::var("INVITE_LINK_SECRET")
+                .ok()
+                .filter(|secret| !secret.trim().is_empty()),
+            invite_link_ttl_hours: env::var("INVITE_LINK_TTL_HOURS")
+                .unwrap_or_else(|_| "72".to_string())
+                .parse()
+                .unwrap_or(72),
+            webhook_max_attempts: env::var("WEBHOOK_MAX_ATTEMPTS")
+                .unwrap_or_else(|_| "6".to_string())
+                .parse()
+                .unwrap_or(6),
+            webhook_timeout_secs: env::var("WEBHOOK_TIMEOUT_SECS")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .unwrap_or(10),
+            smtp: SmtpConfig::from_env(),
+            email_max_attempts: env::var("EMAIL_MAX_ATTEMPTS")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .unwrap_or(5),
+            due_soon_hours: env::var("DUE_SOON_HOURS")
+                .unwrap_or_else(|_| "24".to_string())
+                .parse()
+                .unwrap_or(24),
+            // 默认不过期，与升级前的行为一致
+            invite_expiry_days: env::var("INVITE_EXPIRY_DAYS")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .unwrap_or(0),
+            trash_retention_days: env::var("TRASH_RETENTION_DAYS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .unwrap_or(30),
+            admin_token: env::var("ADMIN_TOKEN").ok(),
+        })
+    }
+}
+
+impl SmtpConfig {
+    fn from_env() -> Option<Self> {
+        let host = env::var("SMTP_HOST").ok()?;
+
+        Some(SmtpConfig {
+            host,
+            port: env::var("SMTP_PORT")
+                .unwrap_or_else(|_| "587".to_string())
+                .parse()
+                .unwrap_or(587),
+            username: env::var("SMTP_USERNAME").ok(),
+            password: env::var("SMTP_PASSWORD").ok(),
+            from: env::var("SMTP_FROM").unwrap_or_else(|_| "TaskView <noreply@taskview.local>".to_string()),
+            tls: env::var("SMTP_TLS").unwrap_or_else(|_| "starttls".to_string()),
         })
     }
-}
\ No newline at end of file
+}
@@ -1,6 +1,37 @@
 use sea_orm::*;
 use std::fs;
 
+// 按顺序执行的迁移文件，新增迁移追加到末尾
+const MIGRATIONS: &[&str] = &[
+    "001_initial.sql",
+    "002_add_completion_details.sql",
+    "003_notifications.sql",
+    "004_webhooks.sql",
+    "005_email_notifications.sql",
+    "006_scheduled_jobs.sql",
+    "007_labels_comments.sql",
+    "008_calendar_feeds.sql",
+    "009_task_status_history.sql",
+    "010_milestones.sql",
+    "011_recurring_tasks.sql",
+    "012_templates.sql",
+    "013_time_entries.sql",
+    "014_task_estimates.sql",
+    "015_task_reviews.sql",
+    "016_completion_submissions.sql",
+    "017_custom_fields.sql",
+    "018_board_columns.sql",
+    "019_wip_limits.sql",
+    "020_task_trash.sql",
+    "021_archiving.sql",
+    "022_mentions.sql",
+    "023_utc_timestamps.sql",
+    "024_review_notifications.sql",
+];
+
+// 迁移文件所在目录
+const MIGRATIONS_DIR: &str = "/app/migrations";
+
 pub struct Database {
     pub connection: DatabaseConnection,
 }
@@ -10,41 +41,86 @@ impl Database {
         let connection = sea_orm::Database::connect(database_url).await?;
         
         // 运行迁移
-        Self::run_migrations(&connection).await?;
+        Self::run_migrations(&connection, MIGRATIONS_DIR).await?;
         
         Ok(Database { connection })
     }
+
+    // 测试使用的内存数据库，迁移文件从源码目录读取
+    #[cfg(test)]
+    pub async fn in_memory() -> Result<DatabaseConnection, DbErr> {
+        let connection = sea_orm::Database::connect("sqlite::memory:").await?;
+        Self::run_migrations(&connection, concat!(env!("CARGO_MANIFEST_DIR"), "/migrations")).await?;
+        Ok(connection)
+    }
     
-    async fn run_migrations(db: &DatabaseConnection) -> Result<(), DbErr> {
-        // 检查是否需要运行迁移
-        if Self::should_run_migrations(db).await? {
-            tracing::info!("Running database migrations...");
-            
-            // 运行001迁移
-            let migration_001 = fs::read_to_string("/app/migrations/001_initial.sql")
-                .map_err(|e| DbErr::Custom(format!("Failed to read migration file 001: {}", e)))?;
-            db.execute_unprepared(&migration_001).await?;
-            
-            // 运行002迁移  
-            let migration_002 = fs::read_to_string("/app/migrations/002_add_completion_details.sql")
-                .map_err(|e| DbErr::Custom(format!("Failed to read migration file 002: {}", e)))?;
-            db.execute_unprepared(&migration_002).await?;
-            
-            tracing::info!("Database migrations completed");
+    async fn run_migrations(db: &DatabaseConnection, dir: &str) -> Result<(), DbErr> {
+        // 迁移记录表
+        db.execute_unprepared(
+            "CREATE TABLE IF NOT EXISTS schema_migrations (name TEXT PRIMARY KEY, applied_at DATETIME DEFAULT CURRENT_TIMESTAMP);",
+        )
+        .await?;
+
+        // 旧版本数据库没有迁移记录，users表存在说明001和002已执行
+        if Self::is_legacy_database(db).await? {
+            for name in &MIGRATIONS[..2] {
+                Self::record_migration(db, name).await?;
+            }
         }
-        
+
+        for name in MIGRATIONS {
+            if Self::is_migration_applied(db, name).await? {
+                continue;
+            }
+
+            tracing::info!("Running database migration {}...", name);
+            let sql = fs::read_to_string(format!("{}/{}", dir, name))
+                .map_err(|e| DbErr::Custom(format!("Failed to read migration file {}: {}", name, e)))?;
+            db.execute_unprepared(&sql).await?;
+            Self::record_migration(db, name).await?;
+        }
+
+        tracing::info!("Database migrations completed");
         Ok(())
     }
-    
-    async fn should_run_migrations(db: &DatabaseConnection) -> Result<bool, DbErr> {
+
+    async fn is_legacy_database(db: &DatabaseConnection) -> Result<bool, DbErr> {
         // 检查users表是否存在，使用查询方式
-        let result = db
+        let users_exist = db
             .execute_unprepared("SELECT 1 FROM users LIMIT 1;")
-            .await;
-            
-        match result {
-            Ok(_) => Ok(false), // 表存在，不需要迁移
-            Err(_) => Ok(true), // 表不存在，需要迁移
+            .await
+            .is_ok();
+        if !users_exist {
+            return Ok(false);
         }
+
+        let recorded = db
+            .query_one(Statement::from_string(
+                DatabaseBackend::Sqlite,
+                "SELECT 1 FROM schema_migrations LIMIT 1",
+            ))
+            .await?;
+        Ok(recorded.is_none())
+    }
+
+    async fn is_migration_applied(db: &DatabaseConnection, name: &str) -> Result<bool, DbErr> {
+        let row = db
+            .query_one(Statement::from_sql_and_values(
+                DatabaseBackend::Sqlite,
+                "SELECT 1 FROM schema_migrations WHERE name = ?",
+                [name.into()],
+            ))
+            .await?;
+        Ok(row.is_some())
     }
-}
\ No newline at end of file
+
+    async fn record_migration(db: &DatabaseConnection, name: &str) -> Result<(), DbErr> {
+        db.execute(Statement::from_sql_and_values(
+            DatabaseBackend::Sqlite,
+            "INSERT OR IGNORE INTO schema_migrations (name) VALUES (?)",
+            [name.into()],
+        ))
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,142 @@
+// 按行比较两段文本，基于最长公共子序列
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Op {
+    Equal,
+    Insert,
+    Delete,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Line {
+    pub op: Op,
+    pub text: String,
+}
+
+// 最长公共子序列表的最大格数，超过时中间部分整体按删除再插入输出，避免长文本占用过多内存
+const MAX_LCS_CELLS: usize = 4_000_000;
+
+// 逐行差异，删除的行排在同位置插入的行之前
+pub fn lines(old: &str, new: &str) -> Vec<Line> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // 相同的开头和结尾直接输出，只比较中间不同的部分
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (old_middle, new_middle) = (&old[prefix..old.len() - suffix], &new[prefix..new.len() - suffix]);
+
+    let mut result = Vec::with_capacity(old.len().max(new.len()));
+    result.extend(old[..prefix].iter().map(|text| Line { op: Op::Equal, text: text.to_string() }));
+    if (old_middle.len() + 1).saturating_mul(new_middle.len() + 1) > MAX_LCS_CELLS {
+        result.extend(old_middle.iter().map(|text| Line { op: Op::Delete, text: text.to_string() }));
+        result.extend(new_middle.iter().map(|text| Line { op: Op::Insert, text: text.to_string() }));
+    } else {
+        lcs_lines(old_middle, new_middle, &mut result);
+    }
+    result.extend(old[old.len() - suffix..].iter().map(|text| Line { op: Op::Equal, text: text.to_string() }));
+    result
+}
+
+fn lcs_lines(old: &[&str], new: &[&str], result: &mut Vec<Line>) {
+    // lcs[i][j]：old[i..]和new[j..]的最长公共子序列长度
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        let (op, text) = if i < old.len() && j < new.len() && old[i] == new[j] {
+            i += 1;
+            j += 1;
+            (Op::Equal, old[i - 1])
+        } else if j == new.len() || (i < old.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            i += 1;
+            (Op::Delete, old[i - 1])
+        } else {
+            j += 1;
+            (Op::Insert, new[j - 1])
+        };
+        result.push(Line { op, text: text.to_string() });
+    }
+}
+
+// 输出统一格式的差异文本，每行以" "、"+"或"-"开头
+pub fn unified(lines: &[Line]) -> String {
+    lines
+        .iter()
+        .map(|line| {
+            let prefix = match line.op {
+                Op::Equal => ' ',
+                Op::Insert => '+',
+                Op::Delete => '-',
+            };
+            format!("{}{}\n", prefix, line.text)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ops(old: &str, new: &str) -> Vec<(Op, String)> {
+        lines(old, new).into_iter().map(|line| (line.op, line.text)).collect()
+    }
+
+    fn line(op: Op, text: &str) -> (Op, String) {
+        (op, text.to_string())
+    }
+
+    #[test]
+    fn empty_inputs() {
+        assert!(lines("", "").is_empty());
+        assert_eq!(ops("", "a\nb"), vec![line(Op::Insert, "a"), line(Op::Insert, "b")]);
+        assert_eq!(ops("a\nb", ""), vec![line(Op::Delete, "a"), line(Op::Delete, "b")]);
+    }
+
+    #[test]
+    fn insert_delete_and_replace() {
+        assert_eq!(
+            ops("a\nc", "a\nb\nc"),
+            vec![line(Op::Equal, "a"), line(Op::Insert, "b"), line(Op::Equal, "c")],
+        );
+        assert_eq!(
+            ops("a\nb\nc", "a\nc"),
+            vec![line(Op::Equal, "a"), line(Op::Delete, "b"), line(Op::Equal, "c")],
+        );
+        assert_eq!(
+            ops("a\nb\nc", "a\nx\nc"),
+            vec![line(Op::Equal, "a"), line(Op::Delete, "b"), line(Op::Insert, "x"), line(Op::Equal, "c")],
+        );
+        assert_eq!(unified(&lines("a\nb", "a\nx")), " a\n-b\n+x\n");
+    }
+
+    // 中间部分超过上限时不建表，整体按删除再插入输出，相同的开头和结尾仍然保留
+    #[test]
+    fn large_inputs_skip_the_lcs_table() {
+        let old: String = (0..3000).map(|n| format!("old {}\n", n)).collect();
+        let new: String = (0..3000).map(|n| format!("new {}\n", n)).collect();
+        let result = lines(&format!("head\n{}tail", old), &format!("head\n{}tail", new));
+
+        assert_eq!(result.len(), 6002);
+        assert_eq!(result[0], Line { op: Op::Equal, text: "head".to_string() });
+        assert!(result[1..3001].iter().all(|line| line.op == Op::Delete));
+        assert!(result[3001..6001].iter().all(|line| line.op == Op::Insert));
+        assert_eq!(result[6001], Line { op: Op::Equal, text: "tail".to_string() });
+    }
+}
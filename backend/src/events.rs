@@ -0,0 +1,356 @@
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbErr, EntityTrait};
+use serde_json::{json, Value};
+
+use crate::config::Config;
+use crate::models::{comment, invite, invite::Entity as Invite, mention, task, task_review};
+use crate::{mailer, webhooks};
+
+// 领域事件，处理函数在数据变更成功后发布
+pub enum Event {
+    // 新建任务
+    TaskCreated {
+        task: task::Model,
+        actor_id: Option<String>,
+    },
+    // 任务分配给执行者
+    TaskAssigned {
+        task: task::Model,
+        actor_id: Option<String>,
+    },
+    // 任务状态变更
+    TaskStatusChanged {
+        task: task::Model,
+        old_status: String,
+        actor_id: Option<String>,
+    },
+    // 创建者验收任务：通过或要求修改
+    TaskReviewed {
+        task: task::Model,
+        review: task_review::Model,
+        actor_id: Option<String>,
+    },
+    // 任务内容更新
+    TaskUpdated {
+        task: task::Model,
+        actor_id: Option<String>,
+    },
+    // 任务被删除（移到回收站）
+    TaskDeleted {
+        task: task::Model,
+        actor_id: Option<String>,
+    },
+    // 任务从回收站恢复
+    TaskRestored {
+        task: task::Model,
+        actor_id: Option<String>,
+    },
+    // 看板成员发表评论
+    CommentCreated {
+        task: task::Model,
+        comment: comment::Model,
+    },
+    // 在描述、完成详情或评论中@提到看板成员
+    TaskMentioned {
+        task: task::Model,
+        mention: mention::Model,
+    },
+    // 执行者使用邀请码加入看板
+    InviteUsed {
+        invite: invite::Model,
+    },
+    // 任务即将到期，由定时任务发布
+    TaskDueSoon {
+        task: task::Model,
+    },
+    // 任务已逾期，由定时任务发布
+    TaskOverdue {
+        task: task::Model,
+    },
+}
+
+// 可订阅的事件类型
+pub const EVENT_TYPES: &[&str] = &[
+    "task.created",
+    "task.assigned",
+    "task.status_changed",
+    "task.reviewed",
+    "task.updated",
+    "task.deleted",
+    "task.restored",
+    "task.mentioned",
+    "comment.created",
+    "task.due_soon",
+    "task.overdue",
+    "invite.used",
+];
+
+impl Event {
+    // 事件类型，用于Webhook订阅过滤
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            Event::TaskCreated { .. } => "task.created",
+            Event::TaskAssigned { .. } => "task.assigned",
+            Event::TaskStatusChanged { .. } => "task.status_changed",
+            Event::TaskReviewed { .. } => "task.reviewed",
+            Event::TaskUpdated { .. } => "task.updated",
+            Event::TaskDeleted { .. } => "task.deleted",
+            Event::TaskRestored { .. } => "task.restored",
+            Event::TaskMentioned { .. } => "task.mentioned",
+            Event::CommentCreated { .. } => "comment.created",
+            Event::TaskDueSoon { .. } => "task.due_soon",
+            Event::TaskOverdue { .. } => "task.overdue",
+            Event::InviteUsed { .. } => "invite.used",
+        }
+    }
+
+    // 事件所属看板
+    pub fn invite_id(&self) -> &str {
+        match self {
+            Event::TaskCreated { task, .. }
+            | Event::TaskAssigned { task, .. }
+            | Event::TaskStatusChanged { task, .. }
+            | Event::TaskReviewed { task, .. }
+            | Event::TaskUpdated { task, .. }
+            | Event::TaskDeleted { task, .. }
+            | Event::TaskRestored { task, .. }
+            | Event::TaskMentioned { task, .. }
+            | Event::CommentCreated { task, .. }
+            | Event::TaskDueSoon { task }
+            | Event::TaskOverdue { task } => &task.invite_id,
+            Event::InviteUsed { invite } => &invite.id,
+        }
+    }
+
+    fn actor_id(&self) -> Option<&str> {
+        match self {
+            Event::TaskCreated { actor_id, .. }
+            | Event::TaskAssigned { actor_id, .. }
+            | Event::TaskStatusChanged { actor_id, .. }
+            | Event::TaskReviewed { actor_id, .. }
+            | Event::TaskUpdated { actor_id, .. }
+            | Event::TaskDeleted { actor_id, .. }
+            | Event::TaskRestored { actor_id, .. } => actor_id.as_deref(),
+            Event::TaskMentioned { mention, .. } => mention.actor_id.as_deref(),
+            Event::CommentCreated { comment, .. } => comment.author_id.as_deref(),
+            Event::TaskDueSoon { .. } | Event::TaskOverdue { .. } => None,
+            Event::InviteUsed { invite } => invite.executor_id.as_deref(),
+        }
+    }
+
+    // 对外推送的事件数据
+    pub fn payload(&self) -> Value {
+        let data = match self {
+            Event::TaskStatusChanged { task, old_status, .. } => json!({
+                "task": task_json(task),
+                "old_status": old_status,
+            }),
+            Event::TaskReviewed { task, review, .. } => json!({
+                "task": task_json(task),
+                "review": {
+                    "id": review.id,
+                    "reviewer_id": review.reviewer_id,
+                    "decision": review.decision,
+                    "comment": review.comment,
+                    "created_at": review.created_at.to_rfc3339(),
+                },
+            }),
+            Event::TaskMentioned { task, mention } => json!({
+                "task": task_json(task),
+                "mention": {
+                    "id": mention.id,
+                    "user_id": mention.user_id,
+                    "source": mention.source,
+                    "comment_id": mention.comment_id,
+                    "created_at": mention.created_at.to_rfc3339(),
+                },
+            }),
+            Event::CommentCreated { task, comment } => json!({
+                "task": task_json(task),
+                "comment": {
+                    "id": comment.id,
+                    "author_id": comment.author_id,
+                    "body": comment.body,
+                    "created_at": comment.created_at.to_rfc3339(),
+                },
+            }),
+            Event::TaskCreated { task, .. }
+            | Event::TaskAssigned { task, .. }
+            | Event::TaskUpdated { task, .. }
+            | Event::TaskDeleted { task, .. }
+            | Event::TaskRestored { task, .. }
+            | Event::TaskDueSoon { task }
+            | Event::TaskOverdue { task } => json!({ "task": task_json(task) }),
+            Event::InviteUsed { invite } => json!({
+                "invite": {
+                    "id": invite.id,
+                    "code": invite.code,
+                    "creator_id": invite.creator_id,
+                    "executor_id": invite.executor_id,
+                    "status": invite.status,
+                    "created_at": invite.created_at.to_rfc3339(),
+                    "used_at": invite.used_at.map(|dt| dt.to_rfc3339()),
+                }
+            }),
+        };
+
+        json!({
+            "event": self.event_type(),
+            "invite_id": self.invite_id(),
+            "actor_id": self.actor_id(),
+            "occurred_at": chrono::Utc::now().to_rfc3339(),
+            "data": data,
+        })
+    }
+
+    // 站内通知类型和内容，不需要通知的事件返回None
+    fn notification(&self) -> Option<(&'static str, &task::Model, String)> {
+        match self {
+            Event::TaskCreated { task, .. } | Event::TaskAssigned { task, .. } => Some((
+                "task_assigned",
+                task,
+                format!("任务「{}」已分配给你", task.title),
+            )),
+            Event::TaskStatusChanged { task, old_status, .. } => Some((
+                "status_changed",
+                task,
+                format!("任务「{}」状态由 {} 变更为 {}", task.title, old_status, task.status),
+            )),
+            Event::TaskReviewed { task, review, .. } => Some((
+                "task_reviewed",
+                task,
+                match (review.decision.as_str(), &review.comment) {
+                    ("accepted", _) => format!("任务「{}」已通过验收", task.title),
+                    (_, Some(comment)) => format!("任务「{}」需要修改：{}", task.title, comment),
+                    _ => format!("任务「{}」需要修改", task.title),
+                },
+            )),
+            Event::TaskMentioned { task, mention } => Some((
+                "mentioned",
+                task,
+                format!("有人在任务「{}」的{}中提到了你", task.title, match mention.source.as_str() {
+                    "completion_details" => "完成详情",
+                    "comment" => "评论",
+                    _ => "描述",
+                }),
+            )),
+            Event::CommentCreated { task, .. } => Some((
+                "commented",
+                task,
+                format!("任务「{}」有新评论", task.title),
+            )),
+            Event::TaskDueSoon { task } => Some((
+                "due_soon",
+                task,
+                format!("任务「{}」即将到期", task.title),
+            )),
+            Event::TaskOverdue { task } => Some((
+                "overdue",
+                task,
+                format!("任务「{}」已逾期", task.title),
+            )),
+            _ => None,
+        }
+    }
+}
+
+fn task_json(task: &task::Model) -> Value {
+    json!({
+        "id": task.id,
+        "title": task.title,
+        "description": task.description,
+        "status": task.status,
+        "status_category": task.status_category,
+        "creator_id": task.creator_id,
+        "executor_id": task.executor_id,
+        "invite_id": task.invite_id,
+        "completion_details": task.completion_details,
+        "completed_at": task.completed_at.map(|dt| dt.to_rfc3339()),
+        "due_date": task.due_date.map(|dt| dt.to_rfc3339()),
+        "deleted_at": task.deleted_at.map(|dt| dt.to_rfc3339()),
+        "archived_at": task.archived_at.map(|dt| dt.to_rfc3339()),
+        "created_at": task.created_at.to_rfc3339(),
+        "updated_at": task.updated_at.to_rfc3339(),
+    })
+}
+
+// 发布事件，失败只记录日志，不影响主流程
+pub async fn publish(db: &DatabaseConnection, config: &Config, event: Event) {
+    if let Err(e) = notify(db, &event).await {
+        tracing::error!("写入通知失败: {:?}", e);
+    }
+    if let Err(e) = webhooks::enqueue(db, &event).await {
+        tracing::error!("写入Webhook投递队列失败: {:?}", e);
+    }
+    if let Err(e) = mailer::enqueue_for_event(db, config, &event).await {
+        tracing::error!("写入邮件发件箱失败: {:?}", e);
+    }
+}
+
+// 根据事件生成站内通知，不通知操作者本人
+async fn notify(db: &DatabaseConnection, event: &Event) -> Result<(), DbErr> {
+    let Some((kind, task, message)) = event.notification() else {
+        return Ok(());
+    };
+
+    let recipients = match event {
+        Event::TaskStatusChanged { .. }
+        | Event::CommentCreated { .. }
+        | Event::TaskDueSoon { .. }
+        | Event::TaskOverdue { .. } => {
+            board_members(db, &task.invite_id).await?
+        }
+        Event::TaskMentioned { mention, .. } => vec![mention.user_id.clone()],
+        _ => match &task.executor_id {
+            Some(executor_id) => vec![executor_id.clone()],
+            None => board_executor(db, &task.invite_id).await?.into_iter().collect(),
+        },
+    };
+
+    let now = chrono::Utc::now();
+    for user_id in recipients {
+        if Some(user_id.as_str()) == event.actor_id() {
+            continue;
+        }
+
+        db.execute(sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            r#"
+            INSERT INTO notifications (id, user_id, actor_id, kind, invite_id, task_id, message, read_at, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            [
+                uuid::Uuid::new_v4().to_string().into(),
+                user_id.into(),
+                event.actor_id().map(str::to_string).into(),
+                kind.into(),
+                task.invite_id.clone().into(),
+                task.id.clone().into(),
+                message.clone().into(),
+                sea_orm::Value::ChronoDateTimeWithTimeZone(None),
+                now.into(),
+            ],
+        ))
+        .await?;
+    }
+
+    Ok(())
+}
+
+// 看板的执行者
+async fn board_executor(db: &DatabaseConnection, invite_id: &str) -> Result<Option<String>, DbErr> {
+    Ok(Invite::find_by_id(invite_id)
+        .one(db)
+        .await?
+        .and_then(|invite| invite.executor_id))
+}
+
+// 看板成员：创建者和执行者
+async fn board_members(db: &DatabaseConnection, invite_id: &str) -> Result<Vec<String>, DbErr> {
+    let Some(invite) = Invite::find_by_id(invite_id).one(db).await? else {
+        return Ok(Vec::new());
+    };
+
+    let mut members = vec![invite.creator_id];
+    members.extend(invite.executor_id);
+    Ok(members)
+}
@@ -0,0 +1,91 @@
+use axum::{extract::{Query, State}, http::{HeaderMap, StatusCode}, Json};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::config::Config;
+use crate::models::{job, job::Entity as Job};
+use crate::signing;
+
+#[derive(Deserialize)]
+pub struct JobListQuery {
+    pub status: Option<String>, // "scheduled", "running", "failed"
+}
+
+#[derive(Serialize)]
+pub struct JobResponse {
+    pub id: String,
+    pub name: String,
+    pub interval_secs: i64,
+    pub status: String,
+    pub run_at: String,
+    pub attempts: i32,
+    pub last_run_at: Option<String>,
+    pub last_success_at: Option<String>,
+    pub last_error: Option<String>,
+    pub updated_at: String,
+}
+
+#[derive(Serialize)]
+pub struct JobListResponse {
+    pub jobs: Vec<JobResponse>,
+}
+
+impl From<job::Model> for JobResponse {
+    fn from(job: job::Model) -> Self {
+        JobResponse {
+            id: job.id,
+            name: job.name,
+            interval_secs: job.interval_secs,
+            status: job.status,
+            run_at: job.run_at.to_rfc3339(),
+            attempts: job.attempts,
+            last_run_at: job.last_run_at.map(|dt| dt.to_rfc3339()),
+            last_success_at: job.last_success_at.map(|dt| dt.to_rfc3339()),
+            last_error: job.last_error,
+            updated_at: job.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+// 校验管理令牌，未配置ADMIN_TOKEN时一律拒绝
+fn check_admin(config: &Config, headers: &HeaderMap) -> Result<(), StatusCode> {
+    let expected = config.admin_token.as_deref().ok_or(StatusCode::FORBIDDEN)?;
+    let provided = headers
+        .get("X-Admin-Token")
+        .and_then(|value| value.to_str().ok())
+        .ok_or(StatusCode::FORBIDDEN)?;
+
+    if !signing::secure_eq(expected, provided) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    Ok(())
+}
+
+// 查看定时任务状态，可按状态过滤
+pub async fn get_jobs(
+    State(db): State<DatabaseConnection>,
+    State(config): State<Arc<Config>>,
+    headers: HeaderMap,
+    Query(query): Query<JobListQuery>,
+) -> Result<Json<JobListResponse>, StatusCode> {
+    check_admin(&config, &headers)?;
+
+    let mut select = Job::find();
+    if let Some(status) = &query.status {
+        if !["scheduled", "running", "failed"].contains(&status.as_str()) {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        select = select.filter(job::Column::Status.eq(status));
+    }
+
+    let jobs = select
+        .order_by_asc(job::Column::RunAt)
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(JobListResponse {
+        jobs: jobs.into_iter().map(Into::into).collect(),
+    }))
+}
@@ -0,0 +1,243 @@
+use axum::{extract::{Path, State}, http::StatusCode, Json};
+use chrono::{DateTime, Utc};
+use sea_orm::{ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, Set, TransactionTrait};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::config::Config;
+use crate::events::{self, Event};
+use crate::handlers::invite::InviteResponse;
+use crate::handlers::task::TaskResponse;
+use crate::handlers::time_entry::stop_running_timers;
+use crate::models::{invite, invite::Entity as Invite, task, task::Entity as Task};
+
+#[derive(Deserialize)]
+pub struct ArchiveBoardRequest {
+    pub creator_id: String,
+}
+
+#[derive(Deserialize)]
+pub struct AutoArchiveRequest {
+    pub creator_id: String,
+    pub auto_archive_days: Option<i32>, // 为空表示不自动归档
+}
+
+// 归档的看板只读，写操作返回409；看板不存在时返回404
+pub async fn ensure_board_writable<C: ConnectionTrait>(conn: &C, invite_id: &str) -> Result<invite::Model, StatusCode> {
+    let invite = Invite::find_by_id(invite_id)
+        .one(conn)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    if invite.archived_at.is_some() {
+        return Err(StatusCode::CONFLICT);
+    }
+    Ok(invite)
+}
+
+// 任务所在的看板可写
+pub async fn ensure_task_board_writable<C: ConnectionTrait>(conn: &C, task_id: &str) -> Result<(), StatusCode> {
+    let task = Task::find_by_id(task_id)
+        .one(conn)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    ensure_board_writable(conn, &task.invite_id).await?;
+    Ok(())
+}
+
+async fn find_board_task(db: &DatabaseConnection, task_id: &str) -> Result<task::Model, StatusCode> {
+    let task = Task::find_by_id(task_id)
+        .filter(task::Column::DeletedAt.is_null())
+        .one(db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    ensure_board_writable(db, &task.invite_id).await?;
+    Ok(task)
+}
+
+// 归档任务：从看板上隐藏，停止计时，仍计入统计和报表
+pub async fn archive_task(
+    State(db): State<DatabaseConnection>,
+    State(config): State<Arc<Config>>,
+    Path(task_id): Path<String>,
+) -> Result<Json<TaskResponse>, StatusCode> {
+    let task = find_board_task(&db, &task_id).await?;
+    if task.archived_at.is_some() {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    let txn = db.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let archived_task = archive(&txn, task, Utc::now())
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    txn.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    events::publish(&db, &config, Event::TaskUpdated {
+        task: archived_task.clone(),
+        actor_id: None,
+    }).await;
+
+    Ok(Json(archived_task.into()))
+}
+
+// 归档任务并停止任务上的计时
+pub async fn archive<C: ConnectionTrait>(conn: &C, task: task::Model, now: DateTime<Utc>) -> Result<task::Model, DbErr> {
+    let task_id = task.id.clone();
+    let mut task_active: task::ActiveModel = task.into();
+    task_active.archived_at = Set(Some(now.into()));
+    task_active.updated_at = Set(now.into());
+    let archived_task = task_active.update(conn).await?;
+    stop_running_timers(conn, &task_id, now).await?;
+    Ok(archived_task)
+}
+
+// 取消归档任务，任务回到原来所在的列
+pub async fn unarchive_task(
+    State(db): State<DatabaseConnection>,
+    State(config): State<Arc<Config>>,
+    Path(task_id): Path<String>,
+) -> Result<Json<TaskResponse>, StatusCode> {
+    let task = find_board_task(&db, &task_id).await?;
+    if task.archived_at.is_none() {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    let mut task_active: task::ActiveModel = task.into();
+    task_active.archived_at = Set(None);
+    task_active.updated_at = Set(Utc::now().into());
+    let task = task_active
+        .update(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    events::publish(&db, &config, Event::TaskUpdated {
+        task: task.clone(),
+        actor_id: None,
+    }).await;
+
+    Ok(Json(task.into()))
+}
+
+// 设置看板的自动归档：完成超过指定天数的任务由定时任务归档
+pub async fn update_auto_archive(
+    State(db): State<DatabaseConnection>,
+    Path(invite_id): Path<String>,
+    Json(payload): Json<AutoArchiveRequest>,
+) -> Result<Json<InviteResponse>, StatusCode> {
+    let invite = find_creator_board(&db, &invite_id, &payload.creator_id).await?;
+    if invite.archived_at.is_some() {
+        return Err(StatusCode::CONFLICT);
+    }
+    if payload.auto_archive_days.is_some_and(|days| days < 1) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let mut invite_active: invite::ActiveModel = invite.into();
+    invite_active.auto_archive_days = Set(payload.auto_archive_days);
+    let invite = invite_active
+        .update(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(invite.into()))
+}
+
+// 归档看板：看板变为只读，不再出现在默认的看板列表中，停止看板上所有计时
+pub async fn archive_board(
+    State(db): State<DatabaseConnection>,
+    Path(invite_id): Path<String>,
+    Json(payload): Json<ArchiveBoardRequest>,
+) -> Result<Json<InviteResponse>, StatusCode> {
+    let invite = find_creator_board(&db, &invite_id, &payload.creator_id).await?;
+    if invite.archived_at.is_some() {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    let now = Utc::now();
+    let txn = db.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let mut invite_active: invite::ActiveModel = invite.into();
+    invite_active.archived_at = Set(Some(now.into()));
+    let invite = invite_active
+        .update(&txn)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    txn.execute(
+        sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            r#"
+            UPDATE time_entries SET ended_at = ?, updated_at = ?
+            WHERE ended_at IS NULL AND task_id IN (SELECT id FROM tasks WHERE invite_id = ?)
+            "#,
+            [now.into(), now.into(), invite_id.into()],
+        )
+    ).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    txn.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(invite.into()))
+}
+
+// 恢复归档的看板
+pub async fn unarchive_board(
+    State(db): State<DatabaseConnection>,
+    Path(invite_id): Path<String>,
+    Json(payload): Json<ArchiveBoardRequest>,
+) -> Result<Json<InviteResponse>, StatusCode> {
+    let invite = find_creator_board(&db, &invite_id, &payload.creator_id).await?;
+    if invite.archived_at.is_none() {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    let mut invite_active: invite::ActiveModel = invite.into();
+    invite_active.archived_at = Set(None);
+    let invite = invite_active
+        .update(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(invite.into()))
+}
+
+// 自动归档完成超过看板设置天数的任务，没有完成时间的按最后更新时间计算
+pub async fn auto_archive_tasks(db: &DatabaseConnection) -> Result<(), DbErr> {
+    let invites = Invite::find()
+        .filter(invite::Column::AutoArchiveDays.is_not_null())
+        .filter(invite::Column::ArchivedAt.is_null())
+        .all(db)
+        .await?;
+
+    let now = Utc::now();
+    for invite in invites {
+        let Some(days) = invite.auto_archive_days else { continue };
+        let cutoff = now - chrono::Duration::days(days as i64);
+        let result = db
+            .execute(sea_orm::Statement::from_sql_and_values(
+                sea_orm::DatabaseBackend::Sqlite,
+                r#"
+                UPDATE tasks SET archived_at = ?
+                WHERE invite_id = ? AND status_category = 'finished'
+                  AND archived_at IS NULL AND deleted_at IS NULL
+                  AND COALESCE(completed_at, updated_at) < ?
+                "#,
+                [now.into(), invite.id.clone().into(), cutoff.into()],
+            ))
+            .await?;
+
+        if result.rows_affected() > 0 {
+            tracing::info!("看板 {} 自动归档任务 {} 个", invite.id, result.rows_affected());
+        }
+    }
+
+    Ok(())
+}
+
+async fn find_creator_board(db: &DatabaseConnection, invite_id: &str, creator_id: &str) -> Result<invite::Model, StatusCode> {
+    Invite::find()
+        .filter(invite::Column::Id.eq(invite_id))
+        .filter(invite::Column::CreatorId.eq(creator_id))
+        .one(db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)
+}
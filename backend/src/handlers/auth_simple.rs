@@ -1,5 +1,5 @@
 use axum::{extract::{State, Path}, http::StatusCode, Json};
-use sea_orm::{ConnectionTrait, DatabaseConnection, Set};
+use sea_orm::{ConnectionTrait, DatabaseConnection};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use bcrypt;
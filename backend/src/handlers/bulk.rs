@@ -0,0 +1,300 @@
+use axum::{extract::State, http::StatusCode, Json};
+use chrono::Utc;
+use sea_orm::{ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DatabaseTransaction, EntityTrait, QueryFilter, Set, TransactionTrait};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::config::Config;
+use crate::events::{self, Event};
+use crate::handlers::archive::{archive, ensure_board_writable};
+use crate::handlers::column::{wip_limit_message, MoveError};
+use crate::handlers::label::attach_labels;
+use crate::handlers::task::{move_task, trash_task, TaskResponse, UpdateTaskStatusRequest};
+use crate::models::{task, task::Entity as Task};
+
+// 一次最多操作的任务数
+const MAX_BULK_TASKS: usize = 200;
+
+#[derive(Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum BulkAction {
+    Status {
+        status: String, // 看板列的键
+        #[serde(default)]
+        override_wip_limit: bool,
+    },
+    Assign {
+        executor_id: String,
+    },
+    AddLabel {
+        label: String, // 标签名，看板上还没有时自动创建
+    },
+    RemoveLabel {
+        label: String,
+    },
+    Archive,
+    Delete,
+}
+
+#[derive(Deserialize)]
+pub struct BulkTaskRequest {
+    pub user_id: String, // 操作者ID，必须是任务所在看板的创建者或执行者
+    pub task_ids: Vec<String>,
+    #[serde(flatten)]
+    pub action: BulkAction,
+}
+
+#[derive(Serialize)]
+pub struct BulkItemResult {
+    pub task_id: String,
+    pub success: bool,
+    pub status: u16, // 与单个任务接口一致的状态码
+    pub error: Option<String>,
+    pub task: Option<TaskResponse>,
+}
+
+// 批量操作允许部分成功：成功的任务在请求结束时一起提交，失败的任务保持原样，
+// 每个任务的结果见results；只有数据库错误会让整个请求失败并全部回滚
+#[derive(Serialize)]
+pub struct BulkTaskResponse {
+    pub succeeded: usize,
+    pub failed: usize,
+    pub results: Vec<BulkItemResult>,
+}
+
+// 批量操作任务：在同一个事务中逐个处理，单个任务失败只回滚该任务，返回每个任务的结果
+pub async fn bulk_update_tasks(
+    State(db): State<DatabaseConnection>,
+    State(config): State<Arc<Config>>,
+    Json(payload): Json<BulkTaskRequest>,
+) -> Result<Json<BulkTaskResponse>, StatusCode> {
+    if payload.task_ids.is_empty() || payload.task_ids.len() > MAX_BULK_TASKS {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let action = match payload.action {
+        BulkAction::AddLabel { label } | BulkAction::RemoveLabel { label } if label.trim().is_empty() => {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        BulkAction::AddLabel { label } => BulkAction::AddLabel { label: label.trim().to_string() },
+        BulkAction::RemoveLabel { label } => BulkAction::RemoveLabel { label: label.trim().to_string() },
+        action => action,
+    };
+
+    // 重复的任务ID只处理一次
+    let mut seen = HashSet::new();
+    let task_ids: Vec<String> = payload.task_ids.into_iter().filter(|id| seen.insert(id.clone())).collect();
+
+    let txn = db.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let mut results = Vec::new();
+    let mut pending_events = Vec::new();
+    for task_id in task_ids {
+        // 每个任务使用一个保存点，失败时不影响已处理的任务
+        let savepoint = txn.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        match apply_action(&savepoint, &payload.user_id, &task_id, &action).await {
+            Ok((task, task_events)) => {
+                savepoint.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+                pending_events.extend(task_events);
+                results.push(BulkItemResult {
+                    task_id,
+                    success: true,
+                    status: StatusCode::OK.as_u16(),
+                    error: None,
+                    task: Some(task.into()),
+                });
+            }
+            Err(MoveError::Status(StatusCode::INTERNAL_SERVER_ERROR)) => {
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+            Err(error) => {
+                savepoint.rollback().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+                let (status, message) = describe_error(error);
+                results.push(BulkItemResult {
+                    task_id,
+                    success: false,
+                    status: status.as_u16(),
+                    error: Some(message),
+                    task: None,
+                });
+            }
+        }
+    }
+    txn.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    for event in pending_events {
+        events::publish(&db, &config, event).await;
+    }
+
+    let succeeded = results.iter().filter(|result| result.success).count();
+    Ok(Json(BulkTaskResponse {
+        succeeded,
+        failed: results.len() - succeeded,
+        results,
+    }))
+}
+
+// 对单个任务执行操作，权限和状态检查与单个任务接口相同，返回更新后的任务和提交后要发布的事件
+async fn apply_action(
+    conn: &DatabaseTransaction,
+    user_id: &str,
+    task_id: &str,
+    action: &BulkAction,
+) -> Result<(task::Model, Vec<Event>), MoveError> {
+    let task = Task::find_by_id(task_id)
+        .filter(task::Column::DeletedAt.is_null())
+        .one(conn)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let invite = ensure_board_writable(conn, &task.invite_id).await?;
+    if invite.creator_id != user_id && invite.executor_id.as_deref() != Some(user_id) {
+        return Err(StatusCode::FORBIDDEN.into());
+    }
+    // 已归档的任务只能删除
+    if task.archived_at.is_some() && !matches!(action, BulkAction::Delete) {
+        return Err(StatusCode::CONFLICT.into());
+    }
+
+    let actor_id = Some(user_id.to_string());
+    let now = Utc::now();
+    match action {
+        BulkAction::Status { status, override_wip_limit } => {
+            let request = UpdateTaskStatusRequest {
+                status: status.clone(),
+                completion_details: None,
+                user_id: actor_id.clone(),
+                override_wip_limit: *override_wip_limit,
+            };
+            let (task, old_status) = move_task(conn, task, &invite, request).await?;
+            let mut task_events = Vec::new();
+            if task.status != old_status {
+                task_events.push(Event::TaskStatusChanged { task: task.clone(), old_status, actor_id });
+            }
+            Ok((task, task_events))
+        }
+        BulkAction::Assign { executor_id } => {
+            if task.executor_id.as_deref() == Some(executor_id.as_str()) {
+                return Ok((task, Vec::new()));
+            }
+            let mut task_active: task::ActiveModel = task.into();
+            task_active.executor_id = Set(Some(executor_id.clone()));
+            task_active.updated_at = Set(now.into());
+            let task = task_active
+                .update(conn)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            Ok((task.clone(), vec![
+                Event::TaskAssigned { task: task.clone(), actor_id: actor_id.clone() },
+                Event::TaskUpdated { task, actor_id },
+            ]))
+        }
+        BulkAction::AddLabel { label } => {
+            attach_labels(conn, &task.invite_id, &task.id, std::slice::from_ref(label))
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            let task = touch(conn, task).await?;
+            Ok((task.clone(), vec![Event::TaskUpdated { task, actor_id }]))
+        }
+        BulkAction::RemoveLabel { label } => {
+            conn.execute(sea_orm::Statement::from_sql_and_values(
+                sea_orm::DatabaseBackend::Sqlite,
+                r#"
+                DELETE FROM task_labels
+                WHERE task_id = ? AND label_id IN (SELECT id FROM labels WHERE invite_id = ? AND name = ?)
+                "#,
+                [task.id.clone().into(), task.invite_id.clone().into(), label.clone().into()],
+            ))
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            let task = touch(conn, task).await?;
+            Ok((task.clone(), vec![Event::TaskUpdated { task, actor_id }]))
+        }
+        BulkAction::Archive => {
+            let task = archive(conn, task, now)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            Ok((task.clone(), vec![Event::TaskUpdated { task, actor_id }]))
+        }
+        BulkAction::Delete => {
+            let task = trash_task(conn, task, now)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            Ok((task.clone(), vec![Event::TaskDeleted { task, actor_id }]))
+        }
+    }
+}
+
+// 更新任务的修改时间
+async fn touch(conn: &DatabaseTransaction, task: task::Model) -> Result<task::Model, StatusCode> {
+    let mut task_active: task::ActiveModel = task.into();
+    task_active.updated_at = Set(Utc::now().into());
+    task_active
+        .update(conn)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+// 单个任务失败的状态码和原因
+fn describe_error(error: MoveError) -> (StatusCode, String) {
+    match error {
+        MoveError::WipLimitExceeded { name, limit, current, .. } => {
+            (StatusCode::CONFLICT, wip_limit_message(&name, limit, current))
+        }
+        MoveError::Status(code) => {
+            let message = match code {
+                StatusCode::BAD_REQUEST => "状态不是看板上的列",
+                StatusCode::FORBIDDEN => "没有权限操作该任务",
+                StatusCode::NOT_FOUND => "任务不存在或已删除",
+                StatusCode::CONFLICT => "任务或看板已归档",
+                _ => "操作失败",
+            };
+            (code, message.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    use crate::test_util::{create_board, create_task, create_user, test_config, test_db};
+
+    // 部分任务失败时其余任务照常更新，失败的任务保持原样
+    #[tokio::test]
+    async fn failed_items_do_not_block_the_rest() {
+        let db = test_db().await;
+        let config = Arc::new(test_config());
+        let creator_id = create_user(&db, "creator", "creator").await;
+        let invite_id = create_board(&db, &creator_id, None).await;
+        let first = create_task(&db, &invite_id, &creator_id, None).await;
+        let second = create_task(&db, &invite_id, &creator_id, None).await;
+        let archived = create_task(&db, &invite_id, &creator_id, None).await;
+        let mut archived_active: task::ActiveModel = archived.into();
+        archived_active.archived_at = Set(Some(Utc::now().into()));
+        let archived = archived_active.update(&db).await.unwrap();
+
+        let Json(response) = bulk_update_tasks(
+            State(db.clone()),
+            State(config),
+            Json(serde_json::from_value(json!({
+                "user_id": creator_id,
+                "task_ids": [first.id, "missing", archived.id, second.id],
+                "action": "status",
+                "status": "in_progress",
+            })).unwrap()),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!((response.succeeded, response.failed), (2, 2));
+        let statuses: Vec<u16> = response.results.iter().map(|result| result.status).collect();
+        assert_eq!(statuses, [200, 404, 409, 200]);
+        for task_id in [&first.id, &second.id] {
+            let task = Task::find_by_id(task_id).one(&db).await.unwrap().unwrap();
+            assert_eq!(task.status, "in_progress");
+        }
+        let archived = Task::find_by_id(&archived.id).one(&db).await.unwrap().unwrap();
+        assert_eq!(archived.status, "todo");
+    }
+}
@@ -0,0 +1,171 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use sea_orm::{ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait, ModelTrait, QueryFilter, QueryOrder};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::config::Config;
+use crate::ical::{self, Component};
+use crate::models::{calendar_feed, calendar_feed::Entity as CalendarFeed, invite, invite::Entity as Invite, task, task::Entity as Task, user::Entity as User};
+
+#[derive(Deserialize)]
+pub struct CalendarQuery {
+    pub component: Option<String>, // "vtodo"（默认）或 "vevent"
+}
+
+#[derive(Serialize)]
+pub struct CalendarFeedResponse {
+    pub user_id: String,
+    pub token: String,
+    pub url: String,
+    pub created_at: String,
+}
+
+fn feed_response(config: &Config, feed: calendar_feed::Model) -> CalendarFeedResponse {
+    CalendarFeedResponse {
+        url: format!("{}/api/calendar/{}.ics", config.public_base_url, feed.token),
+        user_id: feed.user_id,
+        token: feed.token,
+        created_at: feed.created_at.to_rfc3339(),
+    }
+}
+
+// 获取用户的日历订阅地址
+pub async fn get_calendar_feed(
+    State(db): State<DatabaseConnection>,
+    State(config): State<Arc<Config>>,
+    Path(user_id): Path<String>,
+) -> Result<Json<CalendarFeedResponse>, StatusCode> {
+    let feed = CalendarFeed::find_by_id(&user_id)
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(Json(feed_response(&config, feed)))
+}
+
+// 生成或重新生成日历订阅地址，旧地址立即失效
+pub async fn reset_calendar_feed(
+    State(db): State<DatabaseConnection>,
+    State(config): State<Arc<Config>>,
+    Path(user_id): Path<String>,
+) -> Result<Json<CalendarFeedResponse>, StatusCode> {
+    User::find_by_id(&user_id)
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let token = format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple());
+    db.execute(
+        sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            r#"
+            INSERT INTO calendar_feeds (user_id, token, created_at)
+            VALUES (?, ?, ?)
+            ON CONFLICT(user_id) DO UPDATE SET token = excluded.token, created_at = excluded.created_at
+            "#,
+            [user_id.clone().into(), token.into(), chrono::Utc::now().into()]
+        )
+    ).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let feed = CalendarFeed::find_by_id(&user_id)
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(feed_response(&config, feed)))
+}
+
+// 停用日历订阅地址
+pub async fn delete_calendar_feed(
+    State(db): State<DatabaseConnection>,
+    Path(user_id): Path<String>,
+) -> Result<StatusCode, StatusCode> {
+    let feed = CalendarFeed::find_by_id(&user_id)
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    feed.delete(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// 日历订阅内容，包含用户所在全部看板中有截止时间的任务
+pub async fn get_calendar(
+    State(db): State<DatabaseConnection>,
+    State(config): State<Arc<Config>>,
+    Path(file): Path<String>,
+    Query(query): Query<CalendarQuery>,
+) -> Result<Response, StatusCode> {
+    let component = match query.component.as_deref().unwrap_or("vtodo") {
+        "vtodo" => Component::Todo,
+        "vevent" => Component::Event,
+        _ => return Err(StatusCode::BAD_REQUEST),
+    };
+    let token = file.strip_suffix(".ics").unwrap_or(&file);
+
+    let feed = CalendarFeed::find()
+        .filter(calendar_feed::Column::Token.eq(token))
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let user = User::find_by_id(&feed.user_id)
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    // 用户创建或加入的看板
+    let invite_ids: Vec<String> = Invite::find()
+        .filter(
+            Condition::any()
+                .add(invite::Column::CreatorId.eq(&user.id))
+                .add(invite::Column::ExecutorId.eq(&user.id)),
+        )
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .into_iter()
+        .map(|invite| invite.id)
+        .collect();
+
+    let tasks = Task::find()
+        .filter(task::Column::DeletedAt.is_null())
+        .filter(task::Column::InviteId.is_in(invite_ids))
+        .filter(task::Column::DueDate.is_not_null())
+        .order_by_asc(task::Column::DueDate)
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // UID使用对外地址的主机名，保证不同部署之间不冲突
+    let uid_domain = config
+        .public_base_url
+        .split("://")
+        .last()
+        .and_then(|rest| rest.split(['/', ':']).next())
+        .unwrap_or("task-view");
+    let body = ical::render(&format!("{}的任务", user.username), uid_domain, component, &tasks);
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/calendar; charset=utf-8"),
+            (header::CONTENT_DISPOSITION, "inline; filename=\"tasks.ics\""),
+        ],
+        body,
+    )
+        .into_response())
+}
@@ -0,0 +1,507 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::Utc;
+use sea_orm::{ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, TransactionTrait};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+
+use crate::handlers::archive::ensure_board_writable;
+use crate::models::{board_column, board_column::Entity as BoardColumn, invite, invite::Entity as Invite, task, task::Entity as Task};
+
+pub const CATEGORIES: [&str; 3] = ["not_started", "active", "finished"];
+
+// 没有配置列的看板使用的默认列：(键, 名称, 分类)
+pub const DEFAULT_COLUMNS: [(&str, &str, &str); 4] = [
+    ("todo", "待办", "not_started"),
+    ("in_progress", "进行中", "active"),
+    ("in_review", "待验收", "active"),
+    ("done", "已完成", "finished"),
+];
+
+// 待验收列的键，看板有这一列时非创建者完成任务需要先经过验收
+pub const REVIEW_STATUS: &str = "in_review";
+
+#[derive(Clone, Debug)]
+pub struct WorkflowColumn {
+    pub key: String,
+    pub name: String,
+    pub category: String,
+    pub wip_limit: Option<i32>,
+}
+
+// 看板的工作流，列按顺序排列
+#[derive(Clone, Debug)]
+pub struct Workflow {
+    pub columns: Vec<WorkflowColumn>,
+    pub custom: bool, // 是否为看板自定义的列
+}
+
+impl Workflow {
+    pub fn default_columns() -> Self {
+        Workflow {
+            columns: DEFAULT_COLUMNS
+                .iter()
+                .map(|(key, name, category)| WorkflowColumn {
+                    key: key.to_string(),
+                    name: name.to_string(),
+                    category: category.to_string(),
+                    wip_limit: None,
+                })
+                .collect(),
+            custom: false,
+        }
+    }
+
+    pub fn contains(&self, status: &str) -> bool {
+        self.columns.iter().any(|column| column.key == status)
+    }
+
+    pub fn column(&self, status: &str) -> Option<&WorkflowColumn> {
+        self.columns.iter().find(|column| column.key == status)
+    }
+
+    // 状态所在列的分类，看板上没有该列时为None
+    pub fn category(&self, status: &str) -> Option<&str> {
+        self.column(status).map(|column| column.category.as_str())
+    }
+
+    // 某分类下的第一列
+    pub fn first_in(&self, category: &str) -> Option<&str> {
+        self.columns
+            .iter()
+            .find(|column| column.category == category && column.key != REVIEW_STATUS)
+            .map(|column| column.key.as_str())
+    }
+
+    // 新任务所在的列，保存列时保证至少有一个未开始列
+    pub fn initial(&self) -> &str {
+        self.first_in("not_started").unwrap_or(DEFAULT_COLUMNS[0].0)
+    }
+}
+
+// 读取看板的工作流，没有配置时使用默认列
+pub async fn load_workflow<C: ConnectionTrait>(conn: &C, invite_id: &str) -> Result<Workflow, DbErr> {
+    let columns = BoardColumn::find()
+        .filter(board_column::Column::InviteId.eq(invite_id))
+        .order_by_asc(board_column::Column::Position)
+        .all(conn)
+        .await?;
+
+    if columns.is_empty() {
+        return Ok(Workflow::default_columns());
+    }
+    Ok(Workflow {
+        columns: columns
+            .into_iter()
+            .map(|column| WorkflowColumn {
+                key: column.key,
+                name: column.name,
+                category: column.category,
+                wip_limit: column.wip_limit,
+            })
+            .collect(),
+        custom: true,
+    })
+}
+
+// 移动任务的错误：超过WIP上限时返回说明上限的错误信息，其余只返回状态码
+#[derive(Debug)]
+pub enum MoveError {
+    Status(StatusCode),
+    WipLimitExceeded { status: String, name: String, limit: i32, current: u64 },
+}
+
+impl From<StatusCode> for MoveError {
+    fn from(code: StatusCode) -> Self {
+        MoveError::Status(code)
+    }
+}
+
+impl IntoResponse for MoveError {
+    fn into_response(self) -> Response {
+        match self {
+            MoveError::Status(code) => code.into_response(),
+            MoveError::WipLimitExceeded { status, name, limit, current } => (
+                StatusCode::CONFLICT,
+                Json(json!({
+                    "error": "wip_limit_exceeded",
+                    "message": wip_limit_message(&name, limit, current),
+                    "status": status,
+                    "wip_limit": limit,
+                    "current": current,
+                })),
+            )
+                .into_response(),
+        }
+    }
+}
+
+// 超过WIP上限的说明
+pub fn wip_limit_message(name: &str, limit: i32, current: u64) -> String {
+    format!("列「{}」的WIP上限为 {}，当前已有 {} 个任务", name, limit, current)
+}
+
+// 只有看板创建者可以越过WIP上限
+pub fn check_override(override_wip_limit: bool, actor_id: Option<&str>, creator_id: &str) -> Result<(), StatusCode> {
+    if override_wip_limit && actor_id != Some(creator_id) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    Ok(())
+}
+
+// 检查任务移入某列后是否超过该列的WIP上限，调用方保证任务原来不在该列
+pub async fn check_wip_limit<C: ConnectionTrait>(
+    conn: &C,
+    workflow: &Workflow,
+    invite_id: &str,
+    status: &str,
+) -> Result<(), MoveError> {
+    let Some(column) = workflow.column(status) else { return Ok(()) };
+    let Some(limit) = column.wip_limit else { return Ok(()) };
+
+    let current = Task::find()
+        .filter(task::Column::DeletedAt.is_null())
+        .filter(task::Column::ArchivedAt.is_null())
+        .filter(task::Column::InviteId.eq(invite_id))
+        .filter(task::Column::Status.eq(status))
+        .count(conn)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    if current >= limit as u64 {
+        return Err(MoveError::WipLimitExceeded {
+            status: column.key.clone(),
+            name: column.name.clone(),
+            limit,
+            current,
+        });
+    }
+    Ok(())
+}
+
+#[derive(Deserialize)]
+pub struct ColumnInput {
+    pub key: String,
+    pub name: String,
+    pub category: String, // "not_started", "active", "finished"
+    pub wip_limit: Option<i32>, // 为空表示不限制
+}
+
+#[derive(Deserialize)]
+pub struct UpdateColumnsRequest {
+    pub creator_id: String,
+    pub columns: Vec<ColumnInput>, // 完整的列列表，按顺序排列
+}
+
+#[derive(Serialize)]
+pub struct BoardColumnResponse {
+    pub key: String,
+    pub name: String,
+    pub category: String,
+    pub position: i32,
+    pub wip_limit: Option<i32>,
+    pub task_count: usize,
+}
+
+#[derive(Serialize)]
+pub struct BoardColumnListResponse {
+    pub invite_id: String,
+    pub custom: bool,
+    pub columns: Vec<BoardColumnResponse>,
+}
+
+// 列的键只能包含小写字母、数字和下划线
+fn valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= 32
+        && key.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+async fn columns_response(db: &DatabaseConnection, invite_id: String) -> Result<BoardColumnListResponse, StatusCode> {
+    let workflow = load_workflow(db, &invite_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let tasks = Task::find()
+        .filter(task::Column::DeletedAt.is_null())
+        .filter(task::Column::ArchivedAt.is_null())
+        .filter(task::Column::InviteId.eq(&invite_id))
+        .all(db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for task in tasks {
+        *counts.entry(task.status).or_insert(0) += 1;
+    }
+
+    Ok(BoardColumnListResponse {
+        invite_id,
+        custom: workflow.custom,
+        columns: workflow
+            .columns
+            .into_iter()
+            .enumerate()
+            .map(|(position, column)| BoardColumnResponse {
+                task_count: counts.get(&column.key).copied().unwrap_or(0),
+                key: column.key,
+                name: column.name,
+                category: column.category,
+                wip_limit: column.wip_limit,
+                position: position as i32,
+            })
+            .collect(),
+    })
+}
+
+// 获取看板的工作流列
+pub async fn get_board_columns(
+    State(db): State<DatabaseConnection>,
+    Path(invite_id): Path<String>,
+) -> Result<Json<BoardColumnListResponse>, StatusCode> {
+    Invite::find_by_id(&invite_id)
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(Json(columns_response(&db, invite_id).await?))
+}
+
+// 设置看板的工作流列：整体替换，仍有任务的列不能删除，分类变化同步到任务和状态历史
+pub async fn update_board_columns(
+    State(db): State<DatabaseConnection>,
+    Path(invite_id): Path<String>,
+    Json(payload): Json<UpdateColumnsRequest>,
+) -> Result<Json<BoardColumnListResponse>, StatusCode> {
+    // 只有看板创建者可以配置工作流
+    Invite::find()
+        .filter(invite::Column::Id.eq(&invite_id))
+        .filter(invite::Column::CreatorId.eq(&payload.creator_id))
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    ensure_board_writable(&db, &invite_id).await?;
+
+    let mut keys = HashSet::new();
+    let mut columns = Vec::with_capacity(payload.columns.len());
+    for column in payload.columns {
+        let key = column.key.trim().to_string();
+        let name = column.name.trim().to_string();
+        if !valid_key(&key)
+            || name.is_empty()
+            || !CATEGORIES.contains(&column.category.as_str())
+            || column.wip_limit.is_some_and(|limit| limit < 1)
+            || !keys.insert(key.clone())
+        {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        columns.push(WorkflowColumn { key, name, category: column.category, wip_limit: column.wip_limit });
+    }
+    // 至少要有一个未开始列放新任务，一个完成列表示任务完成
+    if !columns.iter().any(|c| c.category == "not_started") || !columns.iter().any(|c| c.category == "finished") {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let tasks = Task::find()
+        .filter(task::Column::InviteId.eq(&invite_id))
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    if tasks.iter().any(|task| !keys.contains(&task.status)) {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    let now = Utc::now();
+    let txn = db.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    txn.execute(
+        sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            "DELETE FROM board_columns WHERE invite_id = ?",
+            [invite_id.clone().into()],
+        )
+    ).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    for (position, column) in columns.iter().enumerate() {
+        txn.execute(
+            sea_orm::Statement::from_sql_and_values(
+                sea_orm::DatabaseBackend::Sqlite,
+                r#"
+                INSERT INTO board_columns (id, invite_id, key, name, category, position, wip_limit, created_at, updated_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+                [
+                    uuid::Uuid::new_v4().to_string().into(),
+                    invite_id.clone().into(),
+                    column.key.clone().into(),
+                    column.name.clone().into(),
+                    column.category.clone().into(),
+                    (position as i32).into(),
+                    column.wip_limit.into(),
+                    now.into(),
+                    now.into(),
+                ]
+            )
+        ).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        // 列改为完成分类时任务记为现在完成，不再是完成分类时清除完成时间
+        txn.execute(
+            sea_orm::Statement::from_sql_and_values(
+                sea_orm::DatabaseBackend::Sqlite,
+                r#"
+                UPDATE tasks SET status_category = ?, completed_at = CASE WHEN ? = 'finished' THEN COALESCE(completed_at, ?) ELSE NULL END
+                WHERE invite_id = ? AND status = ? AND status_category != ?
+                "#,
+                [
+                    column.category.clone().into(),
+                    column.category.clone().into(),
+                    now.into(),
+                    invite_id.clone().into(),
+                    column.key.clone().into(),
+                    column.category.clone().into(),
+                ],
+            )
+        ).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        // 状态历史按当前的列定义统计
+        txn.execute(
+            sea_orm::Statement::from_sql_and_values(
+                sea_orm::DatabaseBackend::Sqlite,
+                r#"
+                UPDATE task_status_history SET to_category = ?
+                WHERE to_status = ? AND to_category != ? AND task_id IN (SELECT id FROM tasks WHERE invite_id = ?)
+                "#,
+                [
+                    column.category.clone().into(),
+                    column.key.clone().into(),
+                    column.category.clone().into(),
+                    invite_id.clone().into(),
+                ],
+            )
+        ).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+    txn.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(columns_response(&db, invite_id).await?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use crate::handlers::task::update_task_status;
+    use crate::test_util::{create_board, create_task, create_user, test_config, test_db};
+
+    // 进行中列的WIP上限为1
+    async fn limit_in_progress(db: &DatabaseConnection, invite_id: &str, creator_id: &str) {
+        let Json(_) = update_board_columns(
+            State(db.clone()),
+            Path(invite_id.to_string()),
+            Json(serde_json::from_value(json!({
+                "creator_id": creator_id,
+                "columns": [
+                    { "key": "todo", "name": "待办", "category": "not_started" },
+                    { "key": "in_progress", "name": "进行中", "category": "active", "wip_limit": 1 },
+                    { "key": "done", "name": "已完成", "category": "finished" },
+                ],
+            })).unwrap()),
+        )
+        .await
+        .unwrap();
+    }
+
+    async fn move_to(db: &DatabaseConnection, task_id: &str, request: serde_json::Value) -> Result<String, MoveError> {
+        let Json(task) = update_task_status(
+            State(db.clone()),
+            State(Arc::new(test_config())),
+            Path(task_id.to_string()),
+            Json(serde_json::from_value(request).unwrap()),
+        )
+        .await?;
+        Ok(task.status)
+    }
+
+    #[test]
+    fn only_the_creator_can_override() {
+        assert!(check_override(false, None, "creator").is_ok());
+        assert!(check_override(false, Some("executor"), "creator").is_ok());
+        assert!(check_override(true, Some("creator"), "creator").is_ok());
+        assert_eq!(check_override(true, Some("executor"), "creator"), Err(StatusCode::FORBIDDEN));
+        assert_eq!(check_override(true, None, "creator"), Err(StatusCode::FORBIDDEN));
+    }
+
+    #[tokio::test]
+    async fn enforces_wip_limits() {
+        let db = test_db().await;
+        let creator_id = create_user(&db, "creator", "creator").await;
+        let executor_id = create_user(&db, "executor", "executor").await;
+        let invite_id = create_board(&db, &creator_id, Some(&executor_id)).await;
+        limit_in_progress(&db, &invite_id, &creator_id).await;
+        let first = create_task(&db, &invite_id, &creator_id, Some(&executor_id)).await;
+        let second = create_task(&db, &invite_id, &creator_id, Some(&executor_id)).await;
+        let third = create_task(&db, &invite_id, &creator_id, Some(&executor_id)).await;
+
+        let status = move_to(&db, &first.id, json!({ "status": "in_progress", "user_id": executor_id })).await.unwrap();
+        assert_eq!(status, "in_progress");
+
+        // 达到上限后不能再移入
+        let result = move_to(&db, &second.id, json!({ "status": "in_progress", "user_id": executor_id })).await;
+        match result {
+            Err(MoveError::WipLimitExceeded { status, limit, current, .. }) => {
+                assert_eq!((status.as_str(), limit, current), ("in_progress", 1, 1));
+            }
+            _ => panic!("应当超过WIP上限"),
+        }
+
+        // 已在列中的任务再次移到同一列不占用额外名额
+        let status = move_to(&db, &first.id, json!({ "status": "in_progress", "user_id": executor_id })).await.unwrap();
+        assert_eq!(status, "in_progress");
+
+        // 执行者不能越过上限，看板创建者可以
+        let result = move_to(&db, &second.id, json!({ "status": "in_progress", "user_id": executor_id, "override_wip_limit": true })).await;
+        assert!(matches!(result, Err(MoveError::Status(StatusCode::FORBIDDEN))));
+        let status = move_to(&db, &second.id, json!({ "status": "in_progress", "user_id": creator_id, "override_wip_limit": true }))
+            .await
+            .unwrap();
+        assert_eq!(status, "in_progress");
+
+        // 移出后腾出名额
+        move_to(&db, &first.id, json!({ "status": "done", "user_id": creator_id })).await.unwrap();
+        move_to(&db, &second.id, json!({ "status": "done", "user_id": creator_id })).await.unwrap();
+        let status = move_to(&db, &third.id, json!({ "status": "in_progress", "user_id": executor_id })).await.unwrap();
+        assert_eq!(status, "in_progress");
+    }
+
+    // 列的分类改为完成或不再是完成时，同步列中任务的完成时间
+    #[tokio::test]
+    async fn category_changes_update_completed_at() {
+        let db = test_db().await;
+        let creator_id = create_user(&db, "creator", "creator").await;
+        let invite_id = create_board(&db, &creator_id, None).await;
+        let columns = |category: &str| serde_json::from_value(json!({
+            "creator_id": creator_id,
+            "columns": [
+                { "key": "todo", "name": "待办", "category": "not_started" },
+                { "key": "shipped", "name": "已发布", "category": category },
+                { "key": "done", "name": "已完成", "category": "finished" },
+            ],
+        })).unwrap();
+        let Json(_) = update_board_columns(State(db.clone()), Path(invite_id.clone()), Json(columns("active"))).await.unwrap();
+        let task = create_task(&db, &invite_id, &creator_id, None).await;
+        move_to(&db, &task.id, json!({ "status": "shipped", "user_id": creator_id })).await.unwrap();
+
+        let Json(_) = update_board_columns(State(db.clone()), Path(invite_id.clone()), Json(columns("finished"))).await.unwrap();
+        let finished = Task::find_by_id(&task.id).one(&db).await.unwrap().unwrap();
+        assert_eq!(finished.status_category, "finished");
+        assert!(finished.completed_at.is_some());
+
+        let Json(_) = update_board_columns(State(db.clone()), Path(invite_id.clone()), Json(columns("active"))).await.unwrap();
+        let reopened = Task::find_by_id(&task.id).one(&db).await.unwrap().unwrap();
+        assert_eq!(reopened.status_category, "active");
+        assert!(reopened.completed_at.is_none());
+    }
+}
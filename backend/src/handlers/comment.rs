@@ -0,0 +1,132 @@
+use axum::{extract::{Path, State}, http::StatusCode, Json};
+use sea_orm::{ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::config::Config;
+use crate::events::{self, Event};
+use crate::handlers::mention::{sync_mentions, MentionSource};
+use crate::models::{comment, comment::Entity as Comment, invite::Entity as Invite, task, task::Entity as Task};
+
+#[derive(Deserialize)]
+pub struct CreateCommentRequest {
+    pub author_id: String,
+    pub body: String,
+}
+
+#[derive(Serialize)]
+pub struct CommentResponse {
+    pub id: String,
+    pub task_id: String,
+    pub author_id: Option<String>,
+    pub author_name: Option<String>,
+    pub body: String,
+    pub created_at: String,
+}
+
+#[derive(Serialize)]
+pub struct CommentListResponse {
+    pub comments: Vec<CommentResponse>,
+}
+
+impl From<comment::Model> for CommentResponse {
+    fn from(comment: comment::Model) -> Self {
+        CommentResponse {
+            id: comment.id,
+            task_id: comment.task_id,
+            author_id: comment.author_id,
+            author_name: comment.author_name,
+            body: comment.body,
+            created_at: comment.created_at.to_rfc3339(),
+        }
+    }
+}
+
+// 获取任务评论，按时间先后排序
+pub async fn get_comments(
+    State(db): State<DatabaseConnection>,
+    Path(task_id): Path<String>,
+) -> Result<Json<CommentListResponse>, StatusCode> {
+    Task::find_by_id(&task_id)
+        .filter(task::Column::DeletedAt.is_null())
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let comments = Comment::find()
+        .filter(comment::Column::TaskId.eq(&task_id))
+        .order_by_asc(comment::Column::CreatedAt)
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(CommentListResponse {
+        comments: comments.into_iter().map(Into::into).collect(),
+    }))
+}
+
+// 发表评论，只有看板的创建者和执行者可以评论
+pub async fn create_comment(
+    State(db): State<DatabaseConnection>,
+    State(config): State<Arc<Config>>,
+    Path(task_id): Path<String>,
+    Json(payload): Json<CreateCommentRequest>,
+) -> Result<Json<CommentResponse>, StatusCode> {
+    if payload.body.trim().is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let task = Task::find_by_id(&task_id)
+        .filter(task::Column::DeletedAt.is_null())
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let invite = Invite::find_by_id(&task.invite_id)
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    if invite.creator_id != payload.author_id && invite.executor_id.as_deref() != Some(payload.author_id.as_str()) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    if invite.archived_at.is_some() {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    let comment_id = uuid::Uuid::new_v4().to_string();
+    db.execute(
+        sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            r#"
+            INSERT INTO comments (id, task_id, author_id, author_name, body, created_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+            [
+                comment_id.clone().into(),
+                task_id.into(),
+                payload.author_id.into(),
+                sea_orm::Value::String(None),
+                payload.body.into(),
+                chrono::Utc::now().into(),
+            ]
+        )
+    ).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let comment = Comment::find_by_id(&comment_id)
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    sync_mentions(&db, &config, &task, MentionSource::Comment(&comment.id), Some(&comment.body), comment.author_id.as_deref()).await;
+
+    events::publish(&db, &config, Event::CommentCreated {
+        task,
+        comment: comment.clone(),
+    }).await;
+
+    Ok(Json(comment.into()))
+}
@@ -0,0 +1,133 @@
+use axum::{extract::{Path, State}, http::StatusCode, Json};
+use chrono::Utc;
+use sea_orm::{ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use serde::Serialize;
+
+use crate::diff;
+use crate::models::{completion_submission, completion_submission::Entity as CompletionSubmission, task, task::Entity as Task};
+
+#[derive(Serialize)]
+pub struct CompletionSubmissionResponse {
+    pub id: String,
+    pub task_id: String,
+    pub version: i32,
+    pub author_id: Option<String>,
+    pub body: String,
+    pub created_at: String,
+}
+
+#[derive(Serialize)]
+pub struct CompletionSubmissionListResponse {
+    pub task_id: String,
+    pub submissions: Vec<CompletionSubmissionResponse>,
+}
+
+#[derive(Serialize)]
+pub struct CompletionDiffResponse {
+    pub task_id: String,
+    pub from_version: Option<i32>, // 第一版与空文本比较
+    pub to_version: i32,
+    pub added: usize,
+    pub removed: usize,
+    pub lines: Vec<diff::Line>,
+    pub unified: String,
+}
+
+impl From<completion_submission::Model> for CompletionSubmissionResponse {
+    fn from(submission: completion_submission::Model) -> Self {
+        CompletionSubmissionResponse {
+            id: submission.id,
+            task_id: submission.task_id,
+            version: submission.version,
+            author_id: submission.author_id,
+            body: submission.body,
+            created_at: submission.created_at.to_rfc3339(),
+        }
+    }
+}
+
+// 记录一次完成详情提交，版本号在任务内递增
+pub async fn record_submission<C: ConnectionTrait>(
+    conn: &C,
+    task_id: &str,
+    author_id: Option<&str>,
+    body: &str,
+    at: chrono::DateTime<Utc>,
+) -> Result<(), DbErr> {
+    conn.execute(
+        sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            r#"
+            INSERT INTO completion_submissions (id, task_id, version, author_id, body, created_at)
+            SELECT ?, ?, COALESCE(MAX(version), 0) + 1, ?, ?, ? FROM completion_submissions WHERE task_id = ?
+            "#,
+            [
+                uuid::Uuid::new_v4().to_string().into(),
+                task_id.into(),
+                author_id.map(str::to_string).into(),
+                body.into(),
+                at.into(),
+                task_id.into(),
+            ],
+        )
+    ).await?;
+    Ok(())
+}
+
+// 获取任务的完成详情提交记录，按版本排序
+pub async fn get_completion_submissions(
+    State(db): State<DatabaseConnection>,
+    Path(task_id): Path<String>,
+) -> Result<Json<CompletionSubmissionListResponse>, StatusCode> {
+    Task::find_by_id(&task_id)
+        .filter(task::Column::DeletedAt.is_null())
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let submissions = CompletionSubmission::find()
+        .filter(completion_submission::Column::TaskId.eq(&task_id))
+        .order_by_asc(completion_submission::Column::Version)
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(CompletionSubmissionListResponse {
+        task_id,
+        submissions: submissions.into_iter().map(Into::into).collect(),
+    }))
+}
+
+// 某一版与上一版完成详情的逐行差异
+pub async fn get_completion_diff(
+    State(db): State<DatabaseConnection>,
+    Path((task_id, version)): Path<(String, i32)>,
+) -> Result<Json<CompletionDiffResponse>, StatusCode> {
+    let mut submissions = CompletionSubmission::find()
+        .filter(completion_submission::Column::TaskId.eq(&task_id))
+        .filter(completion_submission::Column::Version.lte(version))
+        .order_by_desc(completion_submission::Column::Version)
+        .limit(2)
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .into_iter();
+
+    let current = submissions
+        .next()
+        .filter(|submission| submission.version == version)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let previous = submissions.next();
+
+    let lines = diff::lines(previous.as_ref().map_or("", |p| p.body.as_str()), &current.body);
+    Ok(Json(CompletionDiffResponse {
+        task_id,
+        from_version: previous.map(|p| p.version),
+        to_version: current.version,
+        added: lines.iter().filter(|line| line.op == diff::Op::Insert).count(),
+        removed: lines.iter().filter(|line| line.op == diff::Op::Delete).count(),
+        unified: diff::unified(&lines),
+        lines,
+    }))
+}
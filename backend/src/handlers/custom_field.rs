@@ -0,0 +1,479 @@
+use axum::{extract::{Path, Query, State}, http::StatusCode, Json};
+use chrono::{NaiveDate, Utc};
+use sea_orm::{ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, ModelTrait, QueryFilter, QueryOrder, Set, TransactionTrait};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+use crate::handlers::archive::ensure_board_writable;
+use crate::models::{custom_field, custom_field::Entity as CustomField, invite, invite::Entity as Invite};
+
+pub const FIELD_TYPES: [&str; 6] = ["text", "number", "date", "single_select", "multi_select", "checkbox"];
+
+#[derive(Deserialize)]
+pub struct CreateCustomFieldRequest {
+    pub creator_id: String,
+    pub name: String,
+    pub field_type: String,
+    pub options: Option<Vec<String>>, // 单选和多选的可选值
+    pub position: Option<i32>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateCustomFieldRequest {
+    pub creator_id: String,
+    pub name: Option<String>,
+    pub options: Option<Vec<String>>,
+    pub position: Option<i32>,
+}
+
+#[derive(Deserialize)]
+pub struct CreatorQuery {
+    pub creator_id: String,
+}
+
+#[derive(Serialize)]
+pub struct CustomFieldResponse {
+    pub id: String,
+    pub invite_id: String,
+    pub name: String,
+    pub field_type: String,
+    pub options: Vec<String>,
+    pub position: i32,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Serialize)]
+pub struct CustomFieldListResponse {
+    pub custom_fields: Vec<CustomFieldResponse>,
+}
+
+impl From<custom_field::Model> for CustomFieldResponse {
+    fn from(field: custom_field::Model) -> Self {
+        CustomFieldResponse {
+            options: parse_options(&field),
+            id: field.id,
+            invite_id: field.invite_id,
+            name: field.name,
+            field_type: field.field_type,
+            position: field.position,
+            created_at: field.created_at.to_rfc3339(),
+            updated_at: field.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+fn parse_options(field: &custom_field::Model) -> Vec<String> {
+    field
+        .options
+        .as_deref()
+        .and_then(|options| serde_json::from_str(options).ok())
+        .unwrap_or_default()
+}
+
+fn is_select(field_type: &str) -> bool {
+    field_type == "single_select" || field_type == "multi_select"
+}
+
+// 去掉空白和重复的选项，保持原有顺序
+fn normalize_options(options: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for option in options {
+        let option = option.trim().to_string();
+        if !option.is_empty() && !normalized.contains(&option) {
+            normalized.push(option);
+        }
+    }
+    normalized
+}
+
+// 任务上保存的自定义字段值
+pub fn parse_values(raw: Option<&str>) -> Map<String, Value> {
+    raw.and_then(|raw| serde_json::from_str(raw).ok()).unwrap_or_default()
+}
+
+// 校验一个字段值，返回规范化后的值
+fn validate_value(field: &custom_field::Model, value: Value) -> Result<Value, String> {
+    let invalid = || format!("字段「{}」的值无效", field.name);
+    match field.field_type.as_str() {
+        "text" => value.as_str().map(|text| Value::String(text.to_string())).ok_or_else(invalid),
+        "number" => value.as_f64().filter(|n| n.is_finite()).map(|_| value.clone()).ok_or_else(invalid),
+        "date" => value
+            .as_str()
+            .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+            .map(|date| Value::String(date.to_string()))
+            .ok_or_else(invalid),
+        "checkbox" => value.as_bool().map(Value::Bool).ok_or_else(invalid),
+        "single_select" => {
+            let options = parse_options(field);
+            value
+                .as_str()
+                .filter(|option| options.iter().any(|o| o == option))
+                .map(|option| Value::String(option.to_string()))
+                .ok_or_else(invalid)
+        }
+        "multi_select" => {
+            let options = parse_options(field);
+            let selected = value.as_array().ok_or_else(invalid)?;
+            // 按定义中的顺序保存，忽略重复
+            if selected.iter().any(|s| !s.as_str().is_some_and(|s| options.iter().any(|o| o == s))) {
+                return Err(invalid());
+            }
+            Ok(Value::Array(
+                options
+                    .into_iter()
+                    .filter(|option| selected.iter().any(|s| s.as_str() == Some(option)))
+                    .map(Value::String)
+                    .collect(),
+            ))
+        }
+        _ => Err(invalid()),
+    }
+}
+
+// 看板的自定义字段，以字段ID为键
+pub async fn board_fields<C: ConnectionTrait>(conn: &C, invite_id: &str) -> Result<HashMap<String, custom_field::Model>, DbErr> {
+    Ok(CustomField::find()
+        .filter(custom_field::Column::InviteId.eq(invite_id))
+        .all(conn)
+        .await?
+        .into_iter()
+        .map(|field| (field.id.clone(), field))
+        .collect())
+}
+
+// 校验并合并自定义字段值：键为字段ID，值为null表示清除，返回合并后的JSON，没有值时为None
+pub fn merge_values(
+    fields: &HashMap<String, custom_field::Model>,
+    current: Option<&str>,
+    changes: Map<String, Value>,
+) -> Result<Option<String>, String> {
+    let mut values = parse_values(current);
+    for (field_id, value) in changes {
+        let field = fields.get(&field_id).ok_or_else(|| format!("看板上没有字段 {}", field_id))?;
+        if value.is_null() {
+            values.remove(&field_id);
+        } else {
+            values.insert(field_id, validate_value(field, value)?);
+        }
+    }
+
+    Ok((!values.is_empty()).then(|| Value::Object(values).to_string()))
+}
+
+// 列表过滤：多选包含该选项，复选框按 true/false（未填写视为未勾选），其余类型按值相等
+pub fn matches(field: &custom_field::Model, values: &Map<String, Value>, expected: &str) -> bool {
+    let value = values.get(&field.id);
+    match field.field_type.as_str() {
+        "multi_select" => value
+            .and_then(Value::as_array)
+            .is_some_and(|selected| selected.iter().any(|s| s.as_str() == Some(expected))),
+        "checkbox" => expected
+            .parse::<bool>()
+            .is_ok_and(|expected| value.and_then(Value::as_bool).unwrap_or(false) == expected),
+        "number" => expected
+            .parse::<f64>()
+            .is_ok_and(|expected| value.and_then(Value::as_f64) == Some(expected)),
+        _ => value.and_then(Value::as_str) == Some(expected),
+    }
+}
+
+// 创建自定义字段接口
+pub async fn create_custom_field(
+    State(db): State<DatabaseConnection>,
+    Path(invite_id): Path<String>,
+    Json(payload): Json<CreateCustomFieldRequest>,
+) -> Result<Json<CustomFieldResponse>, StatusCode> {
+    // 只有看板创建者可以管理自定义字段
+    Invite::find()
+        .filter(invite::Column::Id.eq(&invite_id))
+        .filter(invite::Column::CreatorId.eq(&payload.creator_id))
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    ensure_board_writable(&db, &invite_id).await?;
+
+    let name = payload.name.trim().to_string();
+    let options = normalize_options(payload.options.unwrap_or_default());
+    if name.is_empty() || !FIELD_TYPES.contains(&payload.field_type.as_str()) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    // 单选和多选必须有可选值，其他类型不能设置
+    if is_select(&payload.field_type) == options.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let field_id = uuid::Uuid::new_v4().to_string();
+    let now = Utc::now();
+    db.execute(
+        sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            r#"
+            INSERT INTO custom_fields (id, invite_id, name, field_type, options, position, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            [
+                field_id.clone().into(),
+                invite_id.into(),
+                name.into(),
+                payload.field_type.clone().into(),
+                is_select(&payload.field_type).then(|| serde_json::to_string(&options).unwrap_or_default()).into(),
+                payload.position.unwrap_or(0).into(),
+                now.into(),
+                now.into(),
+            ]
+        )
+    ).await.map_err(|e| match e.sql_err() {
+        // 同一看板内字段名唯一
+        Some(sea_orm::SqlErr::UniqueConstraintViolation(_)) => StatusCode::CONFLICT,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    })?;
+
+    let field = CustomField::find_by_id(&field_id)
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(field.into()))
+}
+
+// 获取看板的自定义字段
+pub async fn get_custom_fields(
+    State(db): State<DatabaseConnection>,
+    Path(invite_id): Path<String>,
+) -> Result<Json<CustomFieldListResponse>, StatusCode> {
+    let fields = CustomField::find()
+        .filter(custom_field::Column::InviteId.eq(&invite_id))
+        .order_by_asc(custom_field::Column::Position)
+        .order_by_asc(custom_field::Column::CreatedAt)
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(CustomFieldListResponse {
+        custom_fields: fields.into_iter().map(Into::into).collect(),
+    }))
+}
+
+// 更新自定义字段接口，字段类型不能修改；删掉的选项不影响任务上已保存的值
+pub async fn update_custom_field(
+    State(db): State<DatabaseConnection>,
+    Path(field_id): Path<String>,
+    Json(payload): Json<UpdateCustomFieldRequest>,
+) -> Result<Json<CustomFieldResponse>, StatusCode> {
+    let field = find_creator_field(&db, &field_id, &payload.creator_id).await?;
+    let select = is_select(&field.field_type);
+
+    let mut field_active: custom_field::ActiveModel = field.into();
+    if let Some(name) = payload.name {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        field_active.name = Set(name);
+    }
+    if let Some(options) = payload.options {
+        let options = normalize_options(options);
+        if !select || options.is_empty() {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        field_active.options = Set(Some(serde_json::to_string(&options).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?));
+    }
+    if let Some(position) = payload.position {
+        field_active.position = Set(position);
+    }
+    field_active.updated_at = Set(Utc::now().into());
+
+    let updated = field_active
+        .update(&db)
+        .await
+        .map_err(|e| match e.sql_err() {
+            Some(sea_orm::SqlErr::UniqueConstraintViolation(_)) => StatusCode::CONFLICT,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        })?;
+
+    Ok(Json(updated.into()))
+}
+
+// 删除自定义字段接口，同时清除任务上该字段的值
+pub async fn delete_custom_field(
+    State(db): State<DatabaseConnection>,
+    Path(field_id): Path<String>,
+    Query(query): Query<CreatorQuery>,
+) -> Result<StatusCode, StatusCode> {
+    let field = find_creator_field(&db, &field_id, &query.creator_id).await?;
+
+    let txn = db.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    txn.execute(
+        sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            r#"
+            UPDATE tasks SET custom_fields = NULLIF(json_remove(custom_fields, '$."' || ? || '"'), '{}')
+            WHERE invite_id = ? AND custom_fields IS NOT NULL
+            "#,
+            [field.id.clone().into(), field.invite_id.clone().into()],
+        )
+    ).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    field.delete(&txn)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    txn.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// 查找创建者看板上的自定义字段
+async fn find_creator_field(
+    db: &DatabaseConnection,
+    field_id: &str,
+    creator_id: &str,
+) -> Result<custom_field::Model, StatusCode> {
+    let field = CustomField::find_by_id(field_id)
+        .one(db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    Invite::find()
+        .filter(invite::Column::Id.eq(&field.invite_id))
+        .filter(invite::Column::CreatorId.eq(creator_id))
+        .one(db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    ensure_board_writable(db, &field.invite_id).await?;
+
+    Ok(field)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn field(id: &str, field_type: &str, options: &[&str]) -> custom_field::Model {
+        let now = Utc::now().into();
+        custom_field::Model {
+            id: id.to_string(),
+            invite_id: "board".to_string(),
+            name: id.to_string(),
+            field_type: field_type.to_string(),
+            options: is_select(field_type).then(|| json!(options).to_string()),
+            position: 0,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn board() -> HashMap<String, custom_field::Model> {
+        [
+            field("text", "text", &[]),
+            field("number", "number", &[]),
+            field("date", "date", &[]),
+            field("checkbox", "checkbox", &[]),
+            field("single", "single_select", &["低", "高"]),
+            field("multi", "multi_select", &["前端", "后端", "测试"]),
+        ]
+        .into_iter()
+        .map(|field| (field.id.clone(), field))
+        .collect()
+    }
+
+    #[test]
+    fn validates_each_field_type() {
+        let fields = board();
+        let valid = |id: &str, value: Value| validate_value(&fields[id], value);
+
+        assert_eq!(valid("text", json!("说明")), Ok(json!("说明")));
+        assert!(valid("text", json!(1)).is_err());
+
+        assert_eq!(valid("number", json!(2.5)), Ok(json!(2.5)));
+        assert!(valid("number", json!("2.5")).is_err());
+
+        assert_eq!(valid("date", json!("2024-03-01")), Ok(json!("2024-03-01")));
+        assert!(valid("date", json!("2024-02-30")).is_err());
+        assert!(valid("date", json!("03/01/2024")).is_err());
+
+        assert_eq!(valid("checkbox", json!(true)), Ok(json!(true)));
+        assert!(valid("checkbox", json!("true")).is_err());
+
+        assert_eq!(valid("single", json!("高")), Ok(json!("高")));
+        // 多选按定义顺序保存并去重
+        assert_eq!(valid("multi", json!(["测试", "前端", "测试"])), Ok(json!(["前端", "测试"])));
+        assert!(valid("multi", json!("前端")).is_err());
+    }
+
+    #[test]
+    fn rejects_options_not_in_the_definition() {
+        let fields = board();
+        assert_eq!(validate_value(&fields["single"], json!("中")), Err("字段「single」的值无效".to_string()));
+        assert!(validate_value(&fields["multi"], json!(["前端", "运维"])).is_err());
+        assert!(merge_values(&fields, None, json!({ "single": "中" }).as_object().cloned().unwrap()).is_err());
+    }
+
+    #[test]
+    fn merges_and_clears_values() {
+        let fields = board();
+        let changes = |value: Value| value.as_object().cloned().unwrap();
+
+        let merged = merge_values(&fields, None, changes(json!({ "text": "说明", "number": 3 }))).unwrap();
+        let current = merged.as_deref();
+        assert_eq!(parse_values(current), changes(json!({ "text": "说明", "number": 3 })));
+
+        // null清除单个字段，其他字段保留
+        let merged = merge_values(&fields, current, changes(json!({ "text": null, "checkbox": false }))).unwrap();
+        assert_eq!(parse_values(merged.as_deref()), changes(json!({ "number": 3, "checkbox": false })));
+
+        // 全部清除后没有值
+        let cleared = merge_values(&fields, merged.as_deref(), changes(json!({ "number": null, "checkbox": null }))).unwrap();
+        assert_eq!(cleared, None);
+
+        // 未知字段和无效值都不会改动已有值
+        assert_eq!(
+            merge_values(&fields, current, changes(json!({ "missing": 1 }))),
+            Err("看板上没有字段 missing".to_string())
+        );
+        assert!(merge_values(&fields, current, changes(json!({ "number": "三" }))).is_err());
+    }
+
+    #[test]
+    fn matches_values_by_field_type() {
+        let fields = board();
+        let values = json!({
+            "text": "说明",
+            "number": 3,
+            "date": "2024-03-01",
+            "checkbox": true,
+            "single": "高",
+            "multi": ["前端", "测试"],
+        })
+        .as_object()
+        .cloned()
+        .unwrap();
+        let empty = Map::new();
+
+        assert!(matches(&fields["text"], &values, "说明"));
+        assert!(!matches(&fields["text"], &empty, "说明"));
+
+        assert!(matches(&fields["number"], &values, "3"));
+        assert!(matches(&fields["number"], &values, "3.0"));
+        assert!(!matches(&fields["number"], &values, "abc"));
+
+        assert!(matches(&fields["date"], &values, "2024-03-01"));
+
+        assert!(matches(&fields["checkbox"], &values, "true"));
+        assert!(!matches(&fields["checkbox"], &values, "false"));
+        // 未填写的复选框视为未勾选
+        assert!(matches(&fields["checkbox"], &empty, "false"));
+        assert!(!matches(&fields["checkbox"], &empty, "yes"));
+
+        assert!(matches(&fields["single"], &values, "高"));
+        assert!(!matches(&fields["single"], &values, "低"));
+
+        assert!(matches(&fields["multi"], &values, "测试"));
+        assert!(!matches(&fields["multi"], &values, "后端"));
+    }
+}
@@ -0,0 +1,426 @@
+use axum::{
+    body::Body,
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use futures::TryStreamExt;
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::handlers::column::load_workflow;
+use crate::models::{invite, invite::Entity as Invite, task, task::Entity as Task};
+
+#[derive(Deserialize)]
+pub struct ExportQuery {
+    pub creator_id: String,
+    pub format: Option<String>,     // "csv", "json", "ndjson"，默认csv
+    pub status: Option<String>,     // 逗号分隔，如 "in_progress,done"
+    pub date_field: Option<String>, // 日期范围作用的字段："created_at"（默认）、"updated_at"、"completed_at"、"due_date"
+    pub from: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub to: Option<chrono::DateTime<chrono::FixedOffset>>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ExportFormat {
+    Csv,
+    Json,
+    Ndjson,
+}
+
+impl ExportFormat {
+    fn parse(format: Option<&str>) -> Option<Self> {
+        match format.unwrap_or("csv") {
+            "csv" => Some(ExportFormat::Csv),
+            "json" => Some(ExportFormat::Json),
+            "ndjson" => Some(ExportFormat::Ndjson),
+            _ => None,
+        }
+    }
+
+    fn content_type(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Json => "application/json",
+            ExportFormat::Ndjson => "application/x-ndjson",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Ndjson => "ndjson",
+        }
+    }
+}
+
+// 导出的字段，与task::Model一一对应（回收站中的任务不导出，没有deleted_at），CSV表头和JSON键名都使用这里的顺序
+const COLUMNS: [&str; 20] = [
+    "id",
+    "title",
+    "description",
+    "status",
+    "status_category",
+    "creator_id",
+    "executor_id",
+    "invite_id",
+    "completion_details",
+    "completed_at",
+    "due_date",
+    "overdue_notified_at",
+    "due_soon_notified_at",
+    "priority",
+    "estimate",
+    "estimate_unit",
+    "custom_fields",
+    "archived_at",
+    "created_at",
+    "updated_at",
+];
+
+// 每次发送给客户端的缓冲行数
+const CHANNEL_CAPACITY: usize = 64;
+
+// 字段值，JSON中保留数字类型和自定义字段对象，CSV中转换为文本
+fn task_fields(task: task::Model) -> [Value; 20] {
+    [
+        task.id.into(),
+        task.title.into(),
+        task.description.into(),
+        task.status.into(),
+        task.status_category.into(),
+        task.creator_id.into(),
+        task.executor_id.into(),
+        task.invite_id.into(),
+        task.completion_details.into(),
+        task.completed_at.map(|dt| dt.to_rfc3339()).into(),
+        task.due_date.map(|dt| dt.to_rfc3339()).into(),
+        task.overdue_notified_at.map(|dt| dt.to_rfc3339()).into(),
+        task.due_soon_notified_at.map(|dt| dt.to_rfc3339()).into(),
+        task.priority.into(),
+        task.estimate.into(),
+        task.estimate_unit.into(),
+        task.custom_fields.and_then(|fields| serde_json::from_str(&fields).ok()).unwrap_or(Value::Null),
+        task.archived_at.map(|dt| dt.to_rfc3339()).into(),
+        task.created_at.to_rfc3339().into(),
+        task.updated_at.to_rfc3339().into(),
+    ]
+}
+
+fn csv_cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
+
+fn csv_line<T: AsRef<[u8]>>(fields: &[T]) -> Vec<u8> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    // 写入内存缓冲区不会失败
+    writer.write_record(fields).expect("写入CSV失败");
+    writer.into_inner().expect("写入CSV失败")
+}
+
+fn json_object(fields: [Value; 20]) -> Value {
+    let mut object = Map::new();
+    for (column, value) in COLUMNS.iter().zip(fields) {
+        object.insert(column.to_string(), value);
+    }
+    Value::Object(object)
+}
+
+// 按格式编码一行任务，first表示是否为第一行（JSON数组需要逗号分隔）
+fn encode_task(format: ExportFormat, task: task::Model, first: bool) -> Vec<u8> {
+    let fields = task_fields(task);
+    match format {
+        ExportFormat::Csv => {
+            let values: Vec<String> = fields.iter().map(csv_cell).collect();
+            csv_line(&values)
+        }
+        ExportFormat::Json => {
+            let mut line = if first { Vec::new() } else { b",\n".to_vec() };
+            line.extend(json_object(fields).to_string().into_bytes());
+            line
+        }
+        ExportFormat::Ndjson => {
+            let mut line = json_object(fields).to_string().into_bytes();
+            line.push(b'\n');
+            line
+        }
+    }
+}
+
+// 导出看板任务，逐行流式输出，不把全部任务加载到内存
+pub async fn export_tasks(
+    State(db): State<DatabaseConnection>,
+    Path(invite_id): Path<String>,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, StatusCode> {
+    let format = ExportFormat::parse(query.format.as_deref()).ok_or(StatusCode::BAD_REQUEST)?;
+
+    // 只有看板创建者可以导出
+    Invite::find()
+        .filter(invite::Column::Id.eq(&invite_id))
+        .filter(invite::Column::CreatorId.eq(&query.creator_id))
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let mut select = Task::find()
+        .filter(task::Column::InviteId.eq(&invite_id))
+        .filter(task::Column::DeletedAt.is_null());
+
+    if let Some(status) = &query.status {
+        let statuses: Vec<&str> = status.split(',').map(str::trim).filter(|s| !s.is_empty()).collect();
+        let workflow = load_workflow(&db, &invite_id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        if statuses.iter().any(|s| !workflow.contains(s)) {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        select = select.filter(task::Column::Status.is_in(statuses));
+    }
+
+    let date_column = match query.date_field.as_deref().unwrap_or("created_at") {
+        "created_at" => task::Column::CreatedAt,
+        "updated_at" => task::Column::UpdatedAt,
+        "completed_at" => task::Column::CompletedAt,
+        "due_date" => task::Column::DueDate,
+        _ => return Err(StatusCode::BAD_REQUEST),
+    };
+    // 时间统一按UTC保存，范围也转换为UTC后比较
+    if let Some(from) = query.from {
+        select = select.filter(date_column.gte(from.with_timezone(&chrono::Utc)));
+    }
+    if let Some(to) = query.to {
+        select = select.filter(date_column.lt(to.with_timezone(&chrono::Utc)));
+    }
+
+    let select = select.order_by_asc(task::Column::CreatedAt);
+
+    let (sender, receiver) = mpsc::channel::<Result<Vec<u8>, DbErr>>(CHANNEL_CAPACITY);
+    tokio::spawn(async move {
+        let header = match format {
+            ExportFormat::Csv => {
+                // 带BOM，Excel打开时能正确识别UTF-8中文
+                let mut header = b"\xEF\xBB\xBF".to_vec();
+                header.extend(csv_line(&COLUMNS));
+                header
+            }
+            ExportFormat::Json => b"[\n".to_vec(),
+            ExportFormat::Ndjson => Vec::new(),
+        };
+        if sender.send(Ok(header)).await.is_err() {
+            return;
+        }
+
+        let mut stream = match select.stream(&db).await {
+            Ok(stream) => stream,
+            Err(e) => {
+                tracing::error!("导出任务失败: {:?}", e);
+                let _ = sender.send(Err(e)).await;
+                return;
+            }
+        };
+
+        let mut first = true;
+        loop {
+            match stream.try_next().await {
+                Ok(Some(task)) => {
+                    // 客户端断开后停止查询
+                    if sender.send(Ok(encode_task(format, task, first))).await.is_err() {
+                        return;
+                    }
+                    first = false;
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    // 中途出错时中断响应，客户端会收到不完整的内容而不是看似完整的文件
+                    tracing::error!("导出任务失败: {:?}", e);
+                    let _ = sender.send(Err(e)).await;
+                    return;
+                }
+            }
+        }
+
+        if format == ExportFormat::Json {
+            let _ = sender.send(Ok(b"\n]\n".to_vec())).await;
+        }
+    });
+
+    let filename = format!("tasks-{}.{}", invite_id, format.extension());
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", filename)),
+        ],
+        Body::from_stream(ReceiverStream::new(receiver)),
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, Utc};
+    use sea_orm::{ActiveModelTrait, Set};
+    use std::sync::Arc;
+
+    use crate::handlers::import::import_tasks;
+    use crate::handlers::task::trash_task;
+    use crate::test_util::{create_board, create_task, create_user, test_config, test_db};
+
+    async fn export(db: &DatabaseConnection, invite_id: &str, query: ExportQuery) -> String {
+        let response = export_tasks(State(db.clone()), Path(invite_id.to_string()), Query(query)).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    // 日期范围带非UTC时区时按实际时间过滤
+    #[tokio::test]
+    async fn filters_date_range_across_offsets() {
+        let db = test_db().await;
+        let creator_id = create_user(&db, "creator", "creator").await;
+        let invite_id = create_board(&db, &creator_id, None).await;
+        let task = create_task(&db, &invite_id, &creator_id, None).await;
+        let due_date = Utc::now();
+        let mut task_active: task::ActiveModel = task.into();
+        task_active.due_date = Set(Some(due_date.into()));
+        task_active.update(&db).await.unwrap();
+
+        let east = FixedOffset::east_opt(8 * 3600).unwrap();
+        let west = FixedOffset::west_opt(5 * 3600).unwrap();
+        let range = |from: chrono::DateTime<Utc>, to: chrono::DateTime<Utc>| ExportQuery {
+            creator_id: creator_id.clone(),
+            format: Some("ndjson".to_string()),
+            status: None,
+            date_field: Some("due_date".to_string()),
+            from: Some(from.with_timezone(&east)),
+            to: Some(to.with_timezone(&west)),
+        };
+
+        let hour = chrono::Duration::hours(1);
+        let included = export(&db, &invite_id, range(due_date - hour, due_date + hour)).await;
+        assert_eq!(included.lines().count(), 1);
+        let excluded = export(&db, &invite_id, range(due_date + hour, due_date + hour * 2)).await;
+        assert_eq!(excluded.lines().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn exports_every_task_field() {
+        let db = test_db().await;
+        let creator_id = create_user(&db, "creator", "creator").await;
+        let invite_id = create_board(&db, &creator_id, None).await;
+        let task = create_task(&db, &invite_id, &creator_id, None).await;
+        let mut task_active: task::ActiveModel = task.into();
+        task_active.estimate = Set(Some(3.5));
+        task_active.estimate_unit = Set(Some("hours".to_string()));
+        task_active.custom_fields = Set(Some(r#"{"字段ID":"值"}"#.to_string()));
+        task_active.archived_at = Set(Some(Utc::now().into()));
+        task_active.update(&db).await.unwrap();
+
+        let query = |format: &str| ExportQuery {
+            creator_id: creator_id.clone(),
+            format: Some(format.to_string()),
+            status: None,
+            date_field: None,
+            from: None,
+            to: None,
+        };
+
+        let csv = export(&db, &invite_id, query("csv")).await;
+        let mut reader = csv::Reader::from_reader(csv.trim_start_matches('\u{feff}').as_bytes());
+        assert_eq!(reader.headers().unwrap().iter().collect::<Vec<_>>(), COLUMNS);
+        let row: Vec<String> = reader.records().next().unwrap().unwrap().iter().map(str::to_string).collect();
+        let cell = |column: &str| row[COLUMNS.iter().position(|c| *c == column).unwrap()].clone();
+        assert_eq!(cell("status_category"), "not_started");
+        assert_eq!(cell("estimate"), "3.5");
+        assert_eq!(cell("estimate_unit"), "hours");
+        assert_eq!(cell("due_date"), "");
+        assert!(!cell("archived_at").is_empty());
+        assert_eq!(serde_json::from_str::<Value>(&cell("custom_fields")).unwrap(), serde_json::json!({ "字段ID": "值" }));
+
+        let json: Value = serde_json::from_str(&export(&db, &invite_id, query("json")).await).unwrap();
+        let object = json[0].as_object().unwrap();
+        assert_eq!(object.keys().count(), COLUMNS.len());
+        assert_eq!(object["status_category"], "not_started");
+        assert_eq!(object["estimate"], 3.5);
+        assert_eq!(object["estimate_unit"], "hours");
+        assert_eq!(object["due_date"], Value::Null);
+        assert_eq!(object["custom_fields"]["字段ID"], "值");
+    }
+
+    // 导出的CSV重新导入时已归档的任务保持归档
+    #[tokio::test]
+    async fn archived_tasks_stay_archived_after_reimport() {
+        let db = test_db().await;
+        let config = Arc::new(test_config());
+        let creator_id = create_user(&db, "creator", "creator").await;
+        let invite_id = create_board(&db, &creator_id, None).await;
+        let archived_at = Utc::now();
+        let task = create_task(&db, &invite_id, &creator_id, None).await;
+        let mut task_active: task::ActiveModel = task.into();
+        task_active.archived_at = Set(Some(archived_at.into()));
+        task_active.update(&db).await.unwrap();
+        create_task(&db, &invite_id, &creator_id, None).await;
+
+        let csv = export(&db, &invite_id, ExportQuery {
+            creator_id: creator_id.clone(),
+            format: None,
+            status: None,
+            date_field: None,
+            from: None,
+            to: None,
+        })
+        .await;
+
+        let target_id = create_board(&db, &creator_id, None).await;
+        let axum::Json(response) = import_tasks(
+            State(db.clone()),
+            State(config),
+            Path(target_id.clone()),
+            Query(serde_json::from_value(serde_json::json!({ "creator_id": creator_id })).unwrap()),
+            csv,
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.imported, 2);
+
+        let imported = Task::find().filter(task::Column::InviteId.eq(&target_id)).all(&db).await.unwrap();
+        let archived: Vec<_> = imported.iter().filter_map(|task| task.archived_at).collect();
+        assert_eq!(archived.len(), 1);
+        assert_eq!(archived[0].timestamp(), archived_at.timestamp());
+    }
+
+    // 回收站中的任务不导出
+    #[tokio::test]
+    async fn skips_deleted_tasks() {
+        let db = test_db().await;
+        let creator_id = create_user(&db, "creator", "creator").await;
+        let invite_id = create_board(&db, &creator_id, None).await;
+        let kept = create_task(&db, &invite_id, &creator_id, None).await;
+        let deleted = create_task(&db, &invite_id, &creator_id, None).await;
+        trash_task(&db, deleted, Utc::now()).await.unwrap();
+
+        let ndjson = export(&db, &invite_id, ExportQuery {
+            creator_id: creator_id.clone(),
+            format: Some("ndjson".to_string()),
+            status: None,
+            date_field: None,
+            from: None,
+            to: None,
+        })
+        .await;
+        let ids: Vec<String> = ndjson
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap()["id"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(ids, [kept.id]);
+    }
+}
@@ -0,0 +1,645 @@
+use axum::{extract::{Path, Query, State}, http::StatusCode, Json};
+use sea_orm::{ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, TransactionTrait};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::config::Config;
+use crate::events::{self, Event};
+use crate::handlers::archive::ensure_board_writable;
+use crate::handlers::invite::{generate_invite_code, InviteResponse};
+use crate::handlers::mention::{sync_mentions, MentionSource};
+use crate::handlers::column::Workflow;
+use crate::handlers::task::{insert_task, record_status_change, NewTask, TaskResponse, PRIORITIES};
+use crate::importers::{self, map_status, SkippedItem};
+use crate::models::{invite, invite::Entity as Invite, task, task::Entity as Task, user, user::Entity as User};
+
+// 单次导入的最大行数
+const MAX_IMPORT_ROWS: usize = 1000;
+
+#[derive(Deserialize)]
+pub struct ImportQuery {
+    pub creator_id: String,
+    pub format: Option<String>, // "csv", "json"，默认csv
+    pub dry_run: Option<bool>,
+}
+
+#[derive(Serialize)]
+pub struct ImportRowError {
+    pub row: usize, // 数据行号，从1开始，不含CSV表头
+    pub field: Option<String>,
+    pub message: String,
+}
+
+#[derive(Serialize)]
+pub struct ImportPreviewRow {
+    pub row: usize,
+    pub title: String,
+    pub description: Option<String>,
+    pub due_date: Option<String>,
+    pub priority: Option<String>,
+    pub archived_at: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct ImportResponse {
+    pub dry_run: bool,
+    pub total: usize,
+    pub valid: usize,
+    pub imported: usize,
+    pub errors: Vec<ImportRowError>,
+    pub preview: Vec<ImportPreviewRow>,
+    pub tasks: Vec<TaskResponse>,
+}
+
+// 导入文件中的一行，字段均为原始字符串
+#[derive(Default)]
+struct RawRow {
+    title: Option<String>,
+    description: Option<String>,
+    due_date: Option<String>,
+    priority: Option<String>,
+    archived_at: Option<String>,
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|v| !v.trim().is_empty())
+}
+
+// 解析CSV，要求表头包含title列，description、due_date、priority、archived_at列可选，其他列忽略
+fn parse_csv(body: &str) -> Result<Vec<Result<RawRow, ImportRowError>>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(body.trim_start_matches('\u{feff}').as_bytes());
+
+    let headers = reader.headers().map_err(|e| format!("无法读取CSV表头: {}", e))?.clone();
+    let position = |name: &str| headers.iter().position(|h| h.trim().eq_ignore_ascii_case(name));
+    let title_index = position("title").ok_or("CSV缺少title列")?;
+    let description_index = position("description");
+    let due_date_index = position("due_date");
+    let priority_index = position("priority");
+    let archived_at_index = position("archived_at");
+
+    Ok(reader
+        .records()
+        .enumerate()
+        .map(|(index, record)| {
+            let record = record.map_err(|e| ImportRowError {
+                row: index + 1,
+                field: None,
+                message: format!("CSV格式错误: {}", e),
+            })?;
+            let field = |i: Option<usize>| i.and_then(|i| record.get(i)).map(str::to_string);
+            Ok(RawRow {
+                title: field(Some(title_index)),
+                description: non_empty(field(description_index)),
+                due_date: non_empty(field(due_date_index)),
+                priority: non_empty(field(priority_index)),
+                archived_at: non_empty(field(archived_at_index)),
+            })
+        })
+        .collect())
+}
+
+// 解析JSON，接受任务对象数组或 {"tasks": [...]}
+fn parse_json(body: &str) -> Result<Vec<Result<RawRow, ImportRowError>>, String> {
+    let value: Value = serde_json::from_str(body).map_err(|e| format!("JSON格式错误: {}", e))?;
+    let items = match value {
+        Value::Array(items) => items,
+        Value::Object(mut object) => match object.remove("tasks") {
+            Some(Value::Array(items)) => items,
+            _ => return Err("JSON应为任务数组或包含tasks数组的对象".to_string()),
+        },
+        _ => return Err("JSON应为任务数组或包含tasks数组的对象".to_string()),
+    };
+
+    Ok(items
+        .into_iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let row = index + 1;
+            let Value::Object(object) = item else {
+                return Err(ImportRowError { row, field: None, message: "每一行必须是对象".to_string() });
+            };
+            let field = |name: &str| -> Result<Option<String>, ImportRowError> {
+                match object.get(name) {
+                    None | Some(Value::Null) => Ok(None),
+                    Some(Value::String(s)) => Ok(Some(s.clone())),
+                    Some(_) => Err(ImportRowError {
+                        row,
+                        field: Some(name.to_string()),
+                        message: "必须是字符串".to_string(),
+                    }),
+                }
+            };
+            Ok(RawRow {
+                title: field("title")?,
+                description: non_empty(field("description")?),
+                due_date: non_empty(field("due_date")?),
+                priority: non_empty(field("priority")?),
+                archived_at: non_empty(field("archived_at")?),
+            })
+        })
+        .collect())
+}
+
+// 截止时间和归档时间支持RFC3339或YYYY-MM-DD（按UTC零点），统一转换为UTC
+fn parse_timestamp(value: &str) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+    let value = value.trim();
+    chrono::DateTime::parse_from_rfc3339(value).ok().map(|dt| dt.with_timezone(&chrono::Utc).into()).or_else(|| {
+        chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .ok()
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|dt| dt.and_utc().into())
+    })
+}
+
+// 解析可选的时间字段，无法识别时返回该字段的错误
+fn timestamp_field(row: usize, field: &str, value: Option<&str>) -> Result<Option<chrono::DateTime<chrono::FixedOffset>>, ImportRowError> {
+    match value {
+        Some(value) => Ok(Some(parse_timestamp(value).ok_or_else(|| ImportRowError {
+            row,
+            field: Some(field.to_string()),
+            message: format!("无法识别的日期: {}", value),
+        })?)),
+        None => Ok(None),
+    }
+}
+
+// 把原始行转换为待创建任务和归档时间，按create_task的规则校验
+fn build_task(
+    row: usize,
+    raw: RawRow,
+    creator_id: &str,
+    invite_id: &str,
+) -> Result<(NewTask, Option<chrono::DateTime<chrono::FixedOffset>>), ImportRowError> {
+    let due_date = timestamp_field(row, "due_date", raw.due_date.as_deref())?;
+    let archived_at = timestamp_field(row, "archived_at", raw.archived_at.as_deref())?;
+    let priority = raw.priority.map(|p| p.trim().to_ascii_lowercase());
+    if let Some(value) = priority.as_deref().filter(|p| !PRIORITIES.contains(p)) {
+        return Err(ImportRowError {
+            row,
+            field: Some("priority".to_string()),
+            message: format!("无效的优先级: {}", value),
+        });
+    }
+
+    let new_task = NewTask {
+        title: raw.title.unwrap_or_default().trim().to_string(),
+        description: raw.description,
+        creator_id: creator_id.to_string(),
+        invite_id: invite_id.to_string(),
+        due_date,
+        priority,
+        custom_fields: None,
+    };
+    new_task.validate().map_err(|message| ImportRowError {
+        row,
+        field: Some("title".to_string()),
+        message,
+    })?;
+
+    Ok((new_task, archived_at))
+}
+
+// 批量导入任务，任意一行校验失败时不写入任何数据
+pub async fn import_tasks(
+    State(db): State<DatabaseConnection>,
+    State(config): State<Arc<Config>>,
+    Path(invite_id): Path<String>,
+    Query(query): Query<ImportQuery>,
+    body: String,
+) -> Result<Json<ImportResponse>, StatusCode> {
+    // 只有看板创建者可以导入
+    Invite::find()
+        .filter(invite::Column::Id.eq(&invite_id))
+        .filter(invite::Column::CreatorId.eq(&query.creator_id))
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    ensure_board_writable(&db, &invite_id).await?;
+
+    let dry_run = query.dry_run.unwrap_or(false);
+    let parsed = match query.format.as_deref().unwrap_or("csv") {
+        "csv" => parse_csv(&body),
+        "json" => parse_json(&body),
+        _ => return Err(StatusCode::BAD_REQUEST),
+    };
+
+    let mut response = ImportResponse {
+        dry_run,
+        total: 0,
+        valid: 0,
+        imported: 0,
+        errors: Vec::new(),
+        preview: Vec::new(),
+        tasks: Vec::new(),
+    };
+
+    let rows = match parsed {
+        Ok(rows) => rows,
+        Err(message) => {
+            response.errors.push(ImportRowError { row: 0, field: None, message });
+            return Ok(Json(response));
+        }
+    };
+    response.total = rows.len();
+    if rows.len() > MAX_IMPORT_ROWS {
+        response.errors.push(ImportRowError {
+            row: 0,
+            field: None,
+            message: format!("单次最多导入{}行", MAX_IMPORT_ROWS),
+        });
+        return Ok(Json(response));
+    }
+
+    let mut new_tasks = Vec::new();
+    for (index, raw) in rows.into_iter().enumerate() {
+        let row = index + 1;
+        match raw.and_then(|raw| build_task(row, raw, &query.creator_id, &invite_id)) {
+            Ok((new_task, archived_at)) => {
+                response.preview.push(ImportPreviewRow {
+                    row,
+                    title: new_task.title.clone(),
+                    description: new_task.description.clone(),
+                    due_date: new_task.due_date.map(|dt| dt.to_rfc3339()),
+                    priority: new_task.priority.clone(),
+                    archived_at: archived_at.map(|dt| dt.to_rfc3339()),
+                });
+                new_tasks.push((new_task, archived_at));
+            }
+            Err(error) => response.errors.push(error),
+        }
+    }
+    response.valid = new_tasks.len();
+
+    if dry_run || !response.errors.is_empty() || new_tasks.is_empty() {
+        return Ok(Json(response));
+    }
+
+    // 全部行在同一个事务中插入
+    let txn = db.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let mut task_ids = Vec::with_capacity(new_tasks.len());
+    for (new_task, archived_at) in new_tasks {
+        let task_id = insert_task(&txn, new_task).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        // 导出时已归档的任务导入后保持归档
+        if let Some(archived_at) = archived_at {
+            txn.execute(sea_orm::Statement::from_sql_and_values(
+                sea_orm::DatabaseBackend::Sqlite,
+                "UPDATE tasks SET archived_at = ? WHERE id = ?",
+                [archived_at.with_timezone(&chrono::Utc).into(), task_id.clone().into()],
+            ))
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        }
+        task_ids.push(task_id);
+    }
+    txn.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let tasks = Task::find()
+        .filter(task::Column::Id.is_in(task_ids.clone()))
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // 按文件中的顺序返回并发布事件
+    let mut tasks_by_id: HashMap<String, task::Model> =
+        tasks.into_iter().map(|task| (task.id.clone(), task)).collect();
+    for task_id in task_ids {
+        let Some(task) = tasks_by_id.remove(&task_id) else { continue };
+        events::publish(&db, &config, Event::TaskCreated {
+            task: task.clone(),
+            actor_id: Some(task.creator_id.clone()),
+        }).await;
+        sync_mentions(&db, &config, &task, MentionSource::Description, task.description.as_deref(), Some(&task.creator_id)).await;
+        response.tasks.push(task.into());
+    }
+    response.imported = response.tasks.len();
+    response.preview.clear();
+
+    Ok(Json(response))
+}
+
+#[derive(Deserialize)]
+pub struct ImportBoardRequest {
+    pub creator_id: String,
+    pub source: String, // "trello", "jira"
+    pub data: Value,    // 导出文件的JSON内容
+    pub status_map: Option<HashMap<String, String>>, // 外部列名 → 本系统状态，未指定的列自动推断
+    pub dry_run: Option<bool>,
+}
+
+#[derive(Serialize)]
+pub struct ColumnMapping {
+    pub column: String,
+    pub status: String,
+    pub tasks: usize,
+}
+
+#[derive(Serialize)]
+pub struct TaskMapping {
+    pub external_id: String,
+    pub task_id: Option<String>, // 预览时为空
+    pub title: String,
+    pub status: String,
+    pub labels: Vec<String>,
+    pub comments: usize,
+}
+
+#[derive(Serialize)]
+pub struct SkippedResponse {
+    pub external_id: String,
+    pub title: Option<String>,
+    pub reason: String,
+}
+
+#[derive(Serialize)]
+pub struct ImportBoardResponse {
+    pub dry_run: bool,
+    pub source: String,
+    pub board_name: String,
+    pub invite: Option<InviteResponse>, // 导入后新建的看板，预览时为空
+    pub columns: Vec<ColumnMapping>,
+    pub labels: Vec<String>,
+    pub tasks_imported: usize,
+    pub comments_imported: usize,
+    pub tasks: Vec<TaskMapping>,
+    pub skipped: Vec<SkippedResponse>,
+}
+
+impl From<SkippedItem> for SkippedResponse {
+    fn from(item: SkippedItem) -> Self {
+        SkippedResponse {
+            external_id: item.external_id,
+            title: item.title,
+            reason: item.reason,
+        }
+    }
+}
+
+// 从Trello或Jira导出文件导入为新看板，全部数据在同一个事务中写入
+pub async fn import_board(
+    State(db): State<DatabaseConnection>,
+    Json(payload): Json<ImportBoardRequest>,
+) -> Result<Json<ImportBoardResponse>, StatusCode> {
+    // 只有创建者可以导入看板
+    User::find()
+        .filter(user::Column::Id.eq(&payload.creator_id))
+        .filter(user::Column::Role.eq("creator"))
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::BAD_REQUEST)?;
+
+    // 导入的看板使用默认列
+    let workflow = Workflow::default_columns();
+    let status_map = payload.status_map.unwrap_or_default();
+    if status_map.values().any(|status| !workflow.contains(status)) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let board = match payload.source.as_str() {
+        "trello" => importers::trello::parse(&payload.data),
+        "jira" => importers::jira::parse(&payload.data),
+        _ => return Err(StatusCode::BAD_REQUEST),
+    }
+    .map_err(|message| {
+        tracing::warn!("解析{}导出文件失败: {}", payload.source, message);
+        StatusCode::BAD_REQUEST
+    })?;
+
+    let dry_run = payload.dry_run.unwrap_or(false);
+    let invite_id = uuid::Uuid::new_v4().to_string();
+    let mut response = ImportBoardResponse {
+        dry_run,
+        source: payload.source,
+        board_name: board.name,
+        invite: None,
+        columns: Vec::new(),
+        labels: Vec::new(),
+        tasks_imported: 0,
+        comments_imported: 0,
+        tasks: Vec::new(),
+        skipped: board.skipped.into_iter().map(Into::into).collect(),
+    };
+
+    // 标签：导出文件中的标签定义加上卡片上出现但未定义的标签
+    let mut labels: Vec<(String, Option<String>)> = Vec::new();
+    for label in board.labels {
+        if !labels.iter().any(|(name, _)| *name == label.name) {
+            labels.push((label.name, label.color));
+        }
+    }
+    for name in board.tasks.iter().flat_map(|task| task.labels.iter()) {
+        if !labels.iter().any(|(existing, _)| existing == name) {
+            labels.push((name.clone(), None));
+        }
+    }
+
+    // 先在内存中完成映射和校验，标题为空等无法导入的条目记入skipped
+    let mut rows = Vec::new();
+    for external in board.tasks {
+        let new_task = NewTask {
+            title: external.title.trim().to_string(),
+            description: external.description,
+            creator_id: payload.creator_id.clone(),
+            invite_id: invite_id.clone(),
+            due_date: external.due_date,
+            priority: None,
+            custom_fields: None,
+        };
+        if let Err(reason) = new_task.validate() {
+            response.skipped.push(SkippedResponse {
+                external_id: external.external_id,
+                title: None,
+                reason,
+            });
+            continue;
+        }
+
+        let status = map_status(&external.column, external.category, &status_map);
+        match response.columns.iter_mut().find(|c| c.column == external.column) {
+            Some(mapping) => mapping.tasks += 1,
+            None => response.columns.push(ColumnMapping {
+                column: external.column.clone(),
+                status: status.clone(),
+                tasks: 1,
+            }),
+        }
+
+        response.tasks.push(TaskMapping {
+            external_id: external.external_id,
+            task_id: None,
+            title: new_task.title.clone(),
+            status: status.clone(),
+            labels: external.labels.clone(),
+            comments: external.comments.len(),
+        });
+        rows.push((new_task, status, external.created_at, external.completed_at, external.labels, external.comments));
+    }
+    response.labels = labels.iter().map(|(name, _)| name.clone()).collect();
+
+    if dry_run {
+        return Ok(Json(response));
+    }
+
+    let txn = db.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let now = chrono::Utc::now();
+
+    // 新建看板（邀请码）
+    let invite_code = generate_invite_code();
+    txn.execute(
+        sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            r#"
+            INSERT INTO invites (id, code, creator_id, executor_id, status, created_at, used_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+            [
+                invite_id.clone().into(),
+                invite_code.clone().into(),
+                payload.creator_id.clone().into(),
+                sea_orm::Value::String(None),
+                "pending".into(),
+                now.into(),
+                sea_orm::Value::ChronoDateTimeWithTimeZone(None),
+            ]
+        )
+    ).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let mut label_ids = HashMap::new();
+    for (name, color) in labels {
+        let label_id = uuid::Uuid::new_v4().to_string();
+        txn.execute(
+            sea_orm::Statement::from_sql_and_values(
+                sea_orm::DatabaseBackend::Sqlite,
+                "INSERT INTO labels (id, invite_id, name, color, created_at) VALUES (?, ?, ?, ?, ?)",
+                [label_id.clone().into(), invite_id.clone().into(), name.clone().into(), color.into(), now.into()],
+            )
+        ).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        label_ids.insert(name, label_id);
+    }
+
+    for ((new_task, status, created_at, completed_at, task_labels, comments), mapping) in
+        rows.into_iter().zip(response.tasks.iter_mut())
+    {
+        let task_id = insert_task(&txn, new_task).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        // 保留外部数据的状态和时间，只有已完成的任务保留完成时间
+        let created_at = created_at.unwrap_or_else(|| now.into());
+        let category = workflow.category(&status).unwrap_or("not_started");
+        let completed_at = if category == "finished" { completed_at } else { None };
+        txn.execute(
+            sea_orm::Statement::from_sql_and_values(
+                sea_orm::DatabaseBackend::Sqlite,
+                "UPDATE tasks SET status = ?, status_category = ?, completed_at = ?, created_at = ?, updated_at = ? WHERE id = ?",
+                [
+                    status.clone().into(),
+                    category.into(),
+                    completed_at.into(),
+                    created_at.into(),
+                    completed_at.unwrap_or(created_at).into(),
+                    task_id.clone().into(),
+                ],
+            )
+        ).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        // 状态历史同样使用外部时间
+        txn.execute(
+            sea_orm::Statement::from_sql_and_values(
+                sea_orm::DatabaseBackend::Sqlite,
+                "UPDATE task_status_history SET changed_at = ? WHERE task_id = ?",
+                [created_at.into(), task_id.clone().into()],
+            )
+        ).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        if status != workflow.initial() {
+            let changed_at = completed_at.unwrap_or(created_at).with_timezone(&chrono::Utc);
+            record_status_change(&txn, &task_id, Some(workflow.initial()), &status, category, None, changed_at)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        }
+
+        for name in task_labels {
+            let Some(label_id) = label_ids.get(&name) else { continue };
+            txn.execute(
+                sea_orm::Statement::from_sql_and_values(
+                    sea_orm::DatabaseBackend::Sqlite,
+                    "INSERT OR IGNORE INTO task_labels (task_id, label_id) VALUES (?, ?)",
+                    [task_id.clone().into(), label_id.clone().into()],
+                )
+            ).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        }
+
+        for comment in comments {
+            txn.execute(
+                sea_orm::Statement::from_sql_and_values(
+                    sea_orm::DatabaseBackend::Sqlite,
+                    r#"
+                    INSERT INTO comments (id, task_id, author_id, author_name, body, created_at)
+                    VALUES (?, ?, ?, ?, ?, ?)
+                    "#,
+                    [
+                        uuid::Uuid::new_v4().to_string().into(),
+                        task_id.clone().into(),
+                        sea_orm::Value::String(None),
+                        comment.author.into(),
+                        comment.body.into(),
+                        comment.created_at.unwrap_or(created_at).into(),
+                    ],
+                )
+            ).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            response.comments_imported += 1;
+        }
+
+        mapping.task_id = Some(task_id);
+        response.tasks_imported += 1;
+    }
+
+    txn.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // 新看板还没有执行者和Webhook，不发布任务事件
+    response.invite = Some(InviteResponse {
+        id: invite_id,
+        code: invite_code,
+        creator_id: payload.creator_id,
+        executor_id: None,
+        status: "pending".to_string(),
+        created_at: now.to_rfc3339(),
+        used_at: None,
+        archived_at: None,
+        auto_archive_days: None,
+    });
+
+    Ok(Json(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    use crate::test_util::{create_user, test_db};
+
+    // 无法识别的导出文件和不支持的来源返回400，不创建看板
+    #[tokio::test]
+    async fn rejects_malformed_board_exports() {
+        let db = test_db().await;
+        let creator_id = create_user(&db, "creator", "creator").await;
+
+        for (source, data) in [
+            ("trello", json!({ "name": "没有cards" })),
+            ("jira", json!({ "issues": "不是数组" })),
+            ("asana", json!({ "data": [] })),
+        ] {
+            let result = import_board(
+                State(db.clone()),
+                Json(serde_json::from_value(json!({ "creator_id": creator_id, "source": source, "data": data })).unwrap()),
+            )
+            .await;
+            assert_eq!(result.err(), Some(StatusCode::BAD_REQUEST));
+        }
+        assert!(Invite::find().all(&db).await.unwrap().is_empty());
+    }
+}
@@ -1,9 +1,13 @@
-use axum::{extract::State, http::StatusCode, Json};
-use sea_orm::{ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, Set, TransactionTrait};
+use axum::{extract::{Path, Query, State}, http::{header, StatusCode}, response::{IntoResponse, Response}, Json};
+use sea_orm::{ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, Select, Set, TransactionTrait};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use chrono;
 
+use crate::config::Config;
+use crate::events::{self, Event};
 use crate::models::{invite, invite::Entity as Invite, user, user::Entity as User};
+use crate::signing;
 
 #[derive(Deserialize)]
 pub struct CreateInviteRequest {
@@ -19,6 +23,24 @@ pub struct InviteResponse {
     pub status: String,
     pub created_at: String,
     pub used_at: Option<String>,
+    pub archived_at: Option<String>,
+    pub auto_archive_days: Option<i32>,
+}
+
+impl From<invite::Model> for InviteResponse {
+    fn from(invite: invite::Model) -> Self {
+        InviteResponse {
+            id: invite.id,
+            code: invite.code,
+            creator_id: invite.creator_id,
+            executor_id: invite.executor_id,
+            status: invite.status,
+            created_at: invite.created_at.to_rfc3339(),
+            used_at: invite.used_at.map(|dt| dt.to_rfc3339()),
+            archived_at: invite.archived_at.map(|dt| dt.to_rfc3339()),
+            auto_archive_days: invite.auto_archive_days,
+        }
+    }
 }
 
 #[derive(Deserialize)]
@@ -33,11 +55,43 @@ pub struct UseInviteResponse {
     pub invite: InviteResponse,
 }
 
+#[derive(Deserialize)]
+pub struct InviteListQuery {
+    pub archived: Option<bool>, // true时只列出已归档的看板，默认只列出未归档的
+}
+
 #[derive(Serialize)]
 pub struct InviteListResponse {
     pub invites: Vec<InviteResponse>,
 }
 
+#[derive(Deserialize)]
+pub struct CreateInviteLinkRequest {
+    pub creator_id: String,
+    pub expires_in_hours: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct InviteLinkResponse {
+    pub invite_id: String,
+    pub token: String,
+    pub url: String,
+    pub expires_at: String,
+}
+
+#[derive(Deserialize)]
+pub struct InviteQrCodeQuery {
+    pub creator_id: String,
+    pub format: Option<String>, // "svg" or "png"
+    pub expires_in_hours: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct UseInviteLinkRequest {
+    pub token: String,
+    pub executor_id: String,
+}
+
 // 生成邀请码接口
 pub async fn create_invite(
     State(db): State<DatabaseConnection>,
@@ -119,6 +173,8 @@ pub async fn create_invite(
         status: "pending".to_string(),
         created_at: created_at.to_rfc3339(),
         used_at: None,
+        archived_at: None,
+        auto_archive_days: None,
     });
 
     tracing::info!("成功生成邀请码: {}", invite_code);
@@ -126,7 +182,7 @@ pub async fn create_invite(
 }
 
 // 生成邀请码的辅助函数
-fn generate_invite_code() -> String {
+pub fn generate_invite_code() -> String {
     use uuid::Uuid;
     Uuid::new_v4().to_string()[0..8].to_uppercase()
 }
@@ -134,73 +190,228 @@ fn generate_invite_code() -> String {
 // 使用邀请码接口
 pub async fn use_invite(
     State(db): State<DatabaseConnection>,
+    State(config): State<Arc<Config>>,
     Json(payload): Json<UseInviteRequest>,
 ) -> Result<Json<UseInviteResponse>, StatusCode> {
+    let query = Invite::find().filter(invite::Column::Code.eq(&payload.code));
+
+    redeem_invite(&db, &config, query, payload.executor_id).await.map(Json)
+}
+
+// 通过邀请链接使用邀请码，校验签名和有效期后与手动输入走同一流程
+pub async fn use_invite_link(
+    State(db): State<DatabaseConnection>,
+    State(config): State<Arc<Config>>,
+    Json(payload): Json<UseInviteLinkRequest>,
+) -> Result<Json<UseInviteResponse>, StatusCode> {
+    let secret = invite_link_secret(&config)?;
+    let invite_id = verify_invite_token(secret, &payload.token)
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    let query = Invite::find().filter(invite::Column::Id.eq(&invite_id));
+
+    redeem_invite(&db, &config, query, payload.executor_id).await.map(Json)
+}
+
+// 绑定执行者与邀请码
+async fn redeem_invite(
+    db: &DatabaseConnection,
+    config: &Config,
+    query: Select<Invite>,
+    executor_id: String,
+) -> Result<UseInviteResponse, StatusCode> {
     // 验证执行者是否存在且角色为executor
-    let executor = User::find()
-        .filter(user::Column::Id.eq(&payload.executor_id))
+    let _executor = User::find()
+        .filter(user::Column::Id.eq(&executor_id))
         .filter(user::Column::Role.eq("executor"))
-        .one(&db)
+        .one(db)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
         .ok_or(StatusCode::BAD_REQUEST)?;
 
     // 查找邀请码
-    let invite = Invite::find()
-        .filter(invite::Column::Code.eq(&payload.code))
+    let invite = query
         .filter(invite::Column::Status.eq("pending"))
-        .one(&db)
+        .filter(invite::Column::ArchivedAt.is_null())
+        .one(db)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
         .ok_or(StatusCode::NOT_FOUND)?;
 
+    // 超过有效期的邀请码视为不存在，等待定时任务清理
+    if config.invite_expiry_days > 0
+        && invite.created_at < chrono::Utc::now() - chrono::Duration::days(config.invite_expiry_days)
+    {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
     // 更新邀请码状态
     let mut invite_active: invite::ActiveModel = invite.into();
-    invite_active.executor_id = Set(Some(payload.executor_id));
+    invite_active.executor_id = Set(Some(executor_id));
     invite_active.status = Set("used".to_string());
     invite_active.used_at = Set(Some(chrono::Utc::now().into()));
 
     let updated_invite = invite_active
-        .update(&db)
+        .update(db)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    Ok(Json(UseInviteResponse {
+    events::publish(db, config, Event::InviteUsed {
+        invite: updated_invite.clone(),
+    }).await;
+
+    Ok(UseInviteResponse {
         message: "邀请码使用成功，已建立绑定关系".to_string(),
-        invite: InviteResponse {
-            id: updated_invite.id,
-            code: updated_invite.code,
-            creator_id: updated_invite.creator_id,
-            executor_id: updated_invite.executor_id,
-            status: updated_invite.status,
-            created_at: updated_invite.created_at.to_rfc3339(),
-            used_at: updated_invite.used_at.map(|dt| dt.to_rfc3339()),
-        },
-    }))
+        invite: updated_invite.into(),
+    })
+}
+
+// 生成邀请链接接口
+pub async fn create_invite_link(
+    State(db): State<DatabaseConnection>,
+    State(config): State<Arc<Config>>,
+    Path(invite_id): Path<String>,
+    Json(payload): Json<CreateInviteLinkRequest>,
+) -> Result<Json<InviteLinkResponse>, StatusCode> {
+    let invite = find_pending_creator_invite(&db, &invite_id, &payload.creator_id).await?;
+
+    Ok(Json(build_invite_link(&config, &invite.id, payload.expires_in_hours)?))
+}
+
+// 生成邀请链接二维码接口，返回SVG或PNG图片
+pub async fn get_invite_qrcode(
+    State(db): State<DatabaseConnection>,
+    State(config): State<Arc<Config>>,
+    Path(invite_id): Path<String>,
+    Query(query): Query<InviteQrCodeQuery>,
+) -> Result<Response, StatusCode> {
+    let invite = find_pending_creator_invite(&db, &invite_id, &query.creator_id).await?;
+    let link = build_invite_link(&config, &invite.id, query.expires_in_hours)?;
+
+    let code = qrcode::QrCode::new(link.url.as_bytes())
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    match query.format.as_deref().unwrap_or("svg") {
+        "svg" => {
+            let svg = code
+                .render::<qrcode::render::svg::Color>()
+                .min_dimensions(256, 256)
+                .build();
+            Ok(([(header::CONTENT_TYPE, "image/svg+xml")], svg).into_response())
+        }
+        "png" => {
+            let image = code
+                .render::<image::Luma<u8>>()
+                .min_dimensions(256, 256)
+                .build();
+            let mut png = Vec::new();
+            image::DynamicImage::ImageLuma8(image)
+                .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            Ok(([(header::CONTENT_TYPE, "image/png")], png).into_response())
+        }
+        _ => Err(StatusCode::BAD_REQUEST),
+    }
+}
+
+// 查找创建者名下尚未使用的邀请码
+async fn find_pending_creator_invite(
+    db: &DatabaseConnection,
+    invite_id: &str,
+    creator_id: &str,
+) -> Result<invite::Model, StatusCode> {
+    let invite = Invite::find()
+        .filter(invite::Column::Id.eq(invite_id))
+        .filter(invite::Column::CreatorId.eq(creator_id))
+        .one(db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    // 已使用的邀请码不再生成链接
+    if invite.status != "pending" {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    Ok(invite)
+}
+
+// 未配置签名密钥时邀请链接不可用
+fn invite_link_secret(config: &Config) -> Result<&str, StatusCode> {
+    config.invite_link_secret.as_deref().ok_or(StatusCode::SERVICE_UNAVAILABLE)
+}
+
+// 签发邀请链接
+fn build_invite_link(
+    config: &Config,
+    invite_id: &str,
+    expires_in_hours: Option<i64>,
+) -> Result<InviteLinkResponse, StatusCode> {
+    let secret = invite_link_secret(config)?;
+    let hours = expires_in_hours.unwrap_or(config.invite_link_ttl_hours);
+    if hours <= 0 {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let expires_at = chrono::Utc::now() + chrono::Duration::hours(hours);
+    let token = sign_invite_token(secret, invite_id, expires_at.timestamp());
+
+    Ok(InviteLinkResponse {
+        invite_id: invite_id.to_string(),
+        url: format!("{}/invite/use?token={}", config.public_base_url, token),
+        token,
+        expires_at: expires_at.to_rfc3339(),
+    })
+}
+
+// 邀请链接令牌格式：{invite_id}.{过期时间戳}.{签名}
+fn sign_invite_token(secret: &str, invite_id: &str, expires_at: i64) -> String {
+    let payload = format!("{}.{}", invite_id, expires_at);
+    let signature = signing::sign(secret, payload.as_bytes());
+    format!("{}.{}", payload, signature)
+}
+
+// 校验邀请链接令牌，返回邀请码ID
+fn verify_invite_token(secret: &str, token: &str) -> Option<String> {
+    let (payload, signature) = token.rsplit_once('.')?;
+    if !signing::verify(secret, payload.as_bytes(), signature) {
+        return None;
+    }
+
+    let (invite_id, expires_at) = payload.rsplit_once('.')?;
+    let expires_at: i64 = expires_at.parse().ok()?;
+    if chrono::Utc::now().timestamp() > expires_at {
+        return None;
+    }
+
+    Some(invite_id.to_string())
+}
+
+// 看板列表的归档过滤条件
+fn archived_filter(archived: Option<bool>) -> sea_orm::sea_query::SimpleExpr {
+    if archived.unwrap_or(false) {
+        invite::Column::ArchivedAt.is_not_null()
+    } else {
+        invite::Column::ArchivedAt.is_null()
+    }
 }
 
 // 获取用户的邀请码列表（创建者视角）
 pub async fn get_invites(
     State(db): State<DatabaseConnection>,
     axum::extract::Path(user_id): axum::extract::Path<String>,
+    Query(query): Query<InviteListQuery>,
 ) -> Result<Json<InviteListResponse>, StatusCode> {
     let invites = Invite::find()
         .filter(invite::Column::CreatorId.eq(&user_id))
+        .filter(archived_filter(query.archived))
         .all(&db)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
     let invite_responses: Vec<InviteResponse> = invites
         .into_iter()
-        .map(|invite| InviteResponse {
-            id: invite.id,
-            code: invite.code,
-            creator_id: invite.creator_id,
-            executor_id: invite.executor_id,
-            status: invite.status,
-            created_at: invite.created_at.to_rfc3339(),
-            used_at: invite.used_at.map(|dt| dt.to_rfc3339()),
-        })
+        .map(InviteResponse::from)
         .collect();
 
     Ok(Json(InviteListResponse {
@@ -212,24 +423,18 @@ pub async fn get_invites(
 pub async fn get_executor_invites(
     State(db): State<DatabaseConnection>,
     axum::extract::Path(executor_id): axum::extract::Path<String>,
+    Query(query): Query<InviteListQuery>,
 ) -> Result<Json<InviteListResponse>, StatusCode> {
     let invites = Invite::find()
         .filter(invite::Column::ExecutorId.eq(&executor_id))
+        .filter(archived_filter(query.archived))
         .all(&db)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
     let invite_responses: Vec<InviteResponse> = invites
         .into_iter()
-        .map(|invite| InviteResponse {
-            id: invite.id,
-            code: invite.code,
-            creator_id: invite.creator_id,
-            executor_id: invite.executor_id,
-            status: invite.status,
-            created_at: invite.created_at.to_rfc3339(),
-            used_at: invite.used_at.map(|dt| dt.to_rfc3339()),
-        })
+        .map(InviteResponse::from)
         .collect();
 
     Ok(Json(InviteListResponse {
@@ -250,13 +455,83 @@ pub async fn get_invite_details(
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
         .ok_or(StatusCode::NOT_FOUND)?;
 
-    Ok(Json(InviteResponse {
-        id: invite.id,
-        code: invite.code,
-        creator_id: invite.creator_id,
-        executor_id: invite.executor_id,
-        status: invite.status,
-        created_at: invite.created_at.to_rfc3339(),
-        used_at: invite.used_at.map(|dt| dt.to_rfc3339()),
-    }))
+    Ok(Json(invite.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    use crate::test_util::{create_board, create_user, test_config, test_db};
+
+    // 未配置签名密钥时邀请链接接口返回503，不影响服务启动
+    #[tokio::test]
+    async fn invite_links_unavailable_without_secret() {
+        let db = test_db().await;
+        let config = Arc::new(Config { invite_link_secret: None, ..test_config() });
+        let creator_id = create_user(&db, "creator", "creator").await;
+        let executor_id = create_user(&db, "executor", "executor").await;
+        let invite_id = create_board(&db, &creator_id, None).await;
+
+        let link = create_invite_link(
+            State(db.clone()),
+            State(config.clone()),
+            Path(invite_id.clone()),
+            Json(serde_json::from_value(json!({ "creator_id": creator_id })).unwrap()),
+        )
+        .await;
+        assert_eq!(link.err(), Some(StatusCode::SERVICE_UNAVAILABLE));
+
+        let used = use_invite_link(
+            State(db.clone()),
+            State(config),
+            Json(serde_json::from_value(json!({ "token": "token", "executor_id": executor_id })).unwrap()),
+        )
+        .await;
+        assert_eq!(used.err(), Some(StatusCode::SERVICE_UNAVAILABLE));
+    }
+
+    #[test]
+    fn verifies_signed_tokens() {
+        let expires_at = chrono::Utc::now().timestamp() + 3600;
+        let token = sign_invite_token("secret", "invite-1", expires_at);
+
+        assert_eq!(verify_invite_token("secret", &token).as_deref(), Some("invite-1"));
+        // 其他密钥签发的令牌无效
+        assert_eq!(verify_invite_token("other-secret", &token), None);
+    }
+
+    #[test]
+    fn rejects_tampered_tokens() {
+        let expires_at = chrono::Utc::now().timestamp() + 3600;
+        let token = sign_invite_token("secret", "invite-1", expires_at);
+        let (payload, signature) = token.rsplit_once('.').unwrap();
+
+        // 修改签名
+        let mut tampered = signature.to_string();
+        let last = if tampered.pop() == Some('0') { '1' } else { '0' };
+        tampered.push(last);
+        assert_eq!(verify_invite_token("secret", &format!("{}.{}", payload, tampered)), None);
+        // 修改邀请码ID或延长有效期，签名不再匹配
+        assert_eq!(verify_invite_token("secret", &token.replacen("invite-1", "invite-2", 1)), None);
+        let extended = format!("invite-1.{}.{}", expires_at + 3600, signature);
+        assert_eq!(verify_invite_token("secret", &extended), None);
+    }
+
+    #[test]
+    fn rejects_expired_tokens() {
+        let expires_at = chrono::Utc::now().timestamp() - 1;
+        let token = sign_invite_token("secret", "invite-1", expires_at);
+
+        assert_eq!(verify_invite_token("secret", &token), None);
+    }
+
+    #[test]
+    fn rejects_malformed_tokens() {
+        let signature = signing::sign("secret", b"invite-1.later");
+        for token in ["", "invite-1", "invite-1.signature", &format!("invite-1.later.{}", signature)] {
+            assert_eq!(verify_invite_token("secret", token), None);
+        }
+    }
 }
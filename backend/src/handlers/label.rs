@@ -0,0 +1,117 @@
+use axum::{extract::{Path, State}, http::StatusCode, Json};
+use sea_orm::{ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use serde::Serialize;
+
+use crate::models::{label, label::Entity as Label, task, task::Entity as Task, task_label, task_label::Entity as TaskLabel};
+
+#[derive(Serialize)]
+pub struct LabelResponse {
+    pub id: String,
+    pub invite_id: String,
+    pub name: String,
+    pub color: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Serialize)]
+pub struct LabelListResponse {
+    pub labels: Vec<LabelResponse>,
+}
+
+impl From<label::Model> for LabelResponse {
+    fn from(label: label::Model) -> Self {
+        LabelResponse {
+            id: label.id,
+            invite_id: label.invite_id,
+            name: label.name,
+            color: label.color,
+            created_at: label.created_at.to_rfc3339(),
+        }
+    }
+}
+
+// 按名称给任务添加标签，看板上还没有的标签自动创建
+pub async fn attach_labels<C: ConnectionTrait>(
+    conn: &C,
+    invite_id: &str,
+    task_id: &str,
+    names: &[String],
+) -> Result<(), DbErr> {
+    let now = chrono::Utc::now();
+    for name in names {
+        conn.execute(
+            sea_orm::Statement::from_sql_and_values(
+                sea_orm::DatabaseBackend::Sqlite,
+                "INSERT OR IGNORE INTO labels (id, invite_id, name, color, created_at) VALUES (?, ?, ?, ?, ?)",
+                [
+                    uuid::Uuid::new_v4().to_string().into(),
+                    invite_id.into(),
+                    name.clone().into(),
+                    sea_orm::Value::String(None),
+                    now.into(),
+                ],
+            )
+        ).await?;
+        conn.execute(
+            sea_orm::Statement::from_sql_and_values(
+                sea_orm::DatabaseBackend::Sqlite,
+                r#"
+                INSERT OR IGNORE INTO task_labels (task_id, label_id)
+                SELECT ?, id FROM labels WHERE invite_id = ? AND name = ?
+                "#,
+                [task_id.into(), invite_id.into(), name.clone().into()],
+            )
+        ).await?;
+    }
+    Ok(())
+}
+
+// 获取看板的全部标签
+pub async fn get_board_labels(
+    State(db): State<DatabaseConnection>,
+    Path(invite_id): Path<String>,
+) -> Result<Json<LabelListResponse>, StatusCode> {
+    let labels = Label::find()
+        .filter(label::Column::InviteId.eq(&invite_id))
+        .order_by_asc(label::Column::Name)
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(LabelListResponse {
+        labels: labels.into_iter().map(Into::into).collect(),
+    }))
+}
+
+// 获取任务的标签
+pub async fn get_task_labels(
+    State(db): State<DatabaseConnection>,
+    Path(task_id): Path<String>,
+) -> Result<Json<LabelListResponse>, StatusCode> {
+    Task::find_by_id(&task_id)
+        .filter(task::Column::DeletedAt.is_null())
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let label_ids: Vec<String> = TaskLabel::find()
+        .filter(task_label::Column::TaskId.eq(&task_id))
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .into_iter()
+        .map(|task_label| task_label.label_id)
+        .collect();
+
+    let labels = Label::find()
+        .filter(label::Column::Id.is_in(label_ids))
+        .order_by_asc(label::Column::Name)
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(LabelListResponse {
+        labels: labels.into_iter().map(Into::into).collect(),
+    }))
+}
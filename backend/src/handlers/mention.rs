@@ -0,0 +1,264 @@
+use axum::{extract::{Path, Query, State}, http::StatusCode, Json};
+use chrono::Utc;
+use sea_orm::sea_query::Query as SubQuery;
+use sea_orm::{ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::config::Config;
+use crate::events::{self, Event};
+use crate::models::{
+    comment, comment::Entity as Comment, invite::Entity as Invite, mention, mention::Entity as Mention, task,
+    task::Entity as Task, user, user::Entity as User,
+};
+
+pub const SOURCES: [&str; 3] = ["description", "completion_details", "comment"];
+
+// 提到用户的位置
+pub enum MentionSource<'a> {
+    Description,
+    CompletionDetails,
+    Comment(&'a str), // 评论ID
+}
+
+impl MentionSource<'_> {
+    fn key(&self) -> &'static str {
+        match self {
+            MentionSource::Description => "description",
+            MentionSource::CompletionDetails => "completion_details",
+            MentionSource::Comment(_) => "comment",
+        }
+    }
+
+    fn comment_id(&self) -> Option<&str> {
+        match self {
+            MentionSource::Comment(comment_id) => Some(comment_id),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct MentionListQuery {
+    pub source: Option<String>, // "description", "completion_details", "comment"
+    pub invite_id: Option<String>,
+    pub limit: Option<u64>,
+}
+
+#[derive(Serialize)]
+pub struct MentionResponse {
+    pub id: String,
+    pub source: String,
+    pub invite_id: String,
+    pub task_id: String,
+    pub task_title: String,
+    pub comment_id: Option<String>,
+    pub actor_id: Option<String>,
+    pub actor_name: Option<String>,
+    pub text: Option<String>, // 提到用户的描述、完成详情或评论的当前内容
+    pub created_at: String,
+}
+
+#[derive(Serialize)]
+pub struct MentionListResponse {
+    pub mentions: Vec<MentionResponse>,
+}
+
+// 文本中是否@提到了该用户名：@前不能是字母或数字（排除邮箱地址），用户名后不能紧跟字母、数字、下划线或连字符
+fn mentions_username(text: &str, username: &str) -> bool {
+    if username.is_empty() {
+        return false;
+    }
+    let needle = format!("@{}", username);
+    text.match_indices(&needle).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + needle.len()..].chars().next();
+        before.map_or(true, |c| !c.is_alphanumeric())
+            && after.map_or(true, |c| !(c.is_alphanumeric() || c == '_' || c == '-'))
+    })
+}
+
+// 同步文本中@提到的看板成员，在任务或评论保存后调用，失败时只记录日志，与发布事件一致
+pub async fn sync_mentions(
+    db: &DatabaseConnection,
+    config: &Config,
+    task: &task::Model,
+    source: MentionSource<'_>,
+    text: Option<&str>,
+    actor_id: Option<&str>,
+) {
+    if let Err(e) = save_mentions(db, config, task, source, text, actor_id).await {
+        tracing::error!("同步@提及失败: {:?}", e);
+    }
+}
+
+// 移除不再提到的成员（创建者和执行者），新提到的成员收到通知，提到自己不通知
+async fn save_mentions(
+    db: &DatabaseConnection,
+    config: &Config,
+    task: &task::Model,
+    source: MentionSource<'_>,
+    text: Option<&str>,
+    actor_id: Option<&str>,
+) -> Result<(), DbErr> {
+    let Some(invite) = Invite::find_by_id(&task.invite_id).one(db).await? else {
+        return Ok(());
+    };
+    let mut member_ids = vec![invite.creator_id];
+    member_ids.extend(invite.executor_id);
+
+    let mentioned: Vec<String> = match text {
+        Some(text) => User::find()
+            .filter(user::Column::Id.is_in(member_ids))
+            .all(db)
+            .await?
+            .into_iter()
+            .filter(|user| mentions_username(text, &user.username))
+            .map(|user| user.id)
+            .collect(),
+        None => Vec::new(),
+    };
+
+    let existing = Mention::find()
+        .filter(mention::Column::TaskId.eq(&task.id))
+        .filter(mention::Column::Source.eq(source.key()));
+    let existing = match source.comment_id() {
+        Some(comment_id) => existing.filter(mention::Column::CommentId.eq(comment_id)),
+        None => existing.filter(mention::Column::CommentId.is_null()),
+    };
+    let existing = existing.all(db).await?;
+
+    let stale: Vec<String> = existing
+        .iter()
+        .filter(|mention| !mentioned.contains(&mention.user_id))
+        .map(|mention| mention.id.clone())
+        .collect();
+    if !stale.is_empty() {
+        Mention::delete_many()
+            .filter(mention::Column::Id.is_in(stale))
+            .exec(db)
+            .await?;
+    }
+
+    let now = Utc::now();
+    for user_id in mentioned {
+        if Some(user_id.as_str()) == actor_id || existing.iter().any(|mention| mention.user_id == user_id) {
+            continue;
+        }
+
+        let mention_id = uuid::Uuid::new_v4().to_string();
+        db.execute(sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            r#"
+            INSERT INTO mentions (id, user_id, actor_id, invite_id, task_id, comment_id, source, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            [
+                mention_id.clone().into(),
+                user_id.into(),
+                actor_id.map(str::to_string).into(),
+                task.invite_id.clone().into(),
+                task.id.clone().into(),
+                source.comment_id().map(str::to_string).into(),
+                source.key().into(),
+                now.into(),
+            ],
+        ))
+        .await?;
+
+        if let Some(mention) = Mention::find_by_id(&mention_id).one(db).await? {
+            events::publish(db, config, Event::TaskMentioned {
+                task: task.clone(),
+                mention,
+            }).await;
+        }
+    }
+
+    Ok(())
+}
+
+// 获取提到用户的任务和评论，最近的在前，回收站中的任务不列出
+pub async fn get_user_mentions(
+    State(db): State<DatabaseConnection>,
+    Path(user_id): Path<String>,
+    Query(query): Query<MentionListQuery>,
+) -> Result<Json<MentionListResponse>, StatusCode> {
+    let mut select = Mention::find()
+        .filter(mention::Column::UserId.eq(&user_id))
+        .filter(
+            mention::Column::TaskId.in_subquery(
+                SubQuery::select()
+                    .column(task::Column::Id)
+                    .from(Task)
+                    .and_where(task::Column::DeletedAt.is_null())
+                    .to_owned(),
+            ),
+        );
+    if let Some(source) = &query.source {
+        if !SOURCES.contains(&source.as_str()) {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        select = select.filter(mention::Column::Source.eq(source));
+    }
+    if let Some(invite_id) = &query.invite_id {
+        select = select.filter(mention::Column::InviteId.eq(invite_id));
+    }
+
+    let mentions = select
+        .order_by_desc(mention::Column::CreatedAt)
+        .limit(query.limit.unwrap_or(50).min(200))
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let tasks: HashMap<String, task::Model> = Task::find()
+        .filter(task::Column::Id.is_in(mentions.iter().map(|mention| mention.task_id.clone())))
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .into_iter()
+        .map(|task| (task.id.clone(), task))
+        .collect();
+    let comments: HashMap<String, String> = Comment::find()
+        .filter(comment::Column::Id.is_in(mentions.iter().filter_map(|mention| mention.comment_id.clone())))
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .into_iter()
+        .map(|comment| (comment.id, comment.body))
+        .collect();
+    let usernames: HashMap<String, String> = User::find()
+        .filter(user::Column::Id.is_in(mentions.iter().filter_map(|mention| mention.actor_id.clone())))
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .into_iter()
+        .map(|user| (user.id, user.username))
+        .collect();
+
+    let mentions = mentions
+        .into_iter()
+        .filter_map(|mention| {
+            let task = tasks.get(&mention.task_id)?;
+            let text = match mention.source.as_str() {
+                "comment" => mention.comment_id.as_ref().and_then(|id| comments.get(id)).cloned(),
+                "completion_details" => task.completion_details.clone(),
+                _ => task.description.clone(),
+            };
+            Some(MentionResponse {
+                actor_name: mention.actor_id.as_ref().and_then(|id| usernames.get(id)).cloned(),
+                id: mention.id,
+                source: mention.source,
+                invite_id: mention.invite_id,
+                task_id: mention.task_id,
+                task_title: task.title.clone(),
+                comment_id: mention.comment_id,
+                actor_id: mention.actor_id,
+                text,
+                created_at: mention.created_at.to_rfc3339(),
+            })
+        })
+        .collect();
+
+    Ok(Json(MentionListResponse { mentions }))
+}
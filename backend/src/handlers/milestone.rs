@@ -0,0 +1,521 @@
+use axum::{extract::{Path, Query, State}, http::StatusCode, Json};
+use chrono::{Duration, NaiveDate, Utc};
+use sea_orm::{ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, ModelTrait, QueryFilter, QueryOrder, Set, TransactionTrait};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::handlers::archive::ensure_board_writable;
+use crate::handlers::stats::{start_of_day, status_at};
+use crate::handlers::task::TaskListResponse;
+use crate::models::{
+    invite, invite::Entity as Invite, milestone, milestone::Entity as Milestone, milestone_task,
+    milestone_task::Entity as MilestoneTask, task, task::Entity as Task, task_status_history,
+    task_status_history::Entity as TaskStatusHistory,
+};
+
+#[derive(Deserialize)]
+pub struct CreateMilestoneRequest {
+    pub creator_id: String,
+    pub name: String,
+    pub start_date: String, // YYYY-MM-DD
+    pub end_date: String,   // YYYY-MM-DD
+}
+
+#[derive(Deserialize)]
+pub struct UpdateMilestoneRequest {
+    pub creator_id: String,
+    pub name: Option<String>,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct AddMilestoneTasksRequest {
+    pub creator_id: String,
+    pub task_ids: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct CreatorQuery {
+    pub creator_id: String,
+}
+
+#[derive(Serialize)]
+pub struct MilestoneResponse {
+    pub id: String,
+    pub invite_id: String,
+    pub name: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Serialize)]
+pub struct MilestoneListResponse {
+    pub milestones: Vec<MilestoneResponse>,
+}
+
+#[derive(Serialize)]
+pub struct BurndownPoint {
+    pub date: String,
+    pub scope: usize,
+    pub completed: usize,
+    pub remaining: usize,
+    pub ideal_remaining: f64,
+}
+
+#[derive(Serialize)]
+pub struct BurndownResponse {
+    pub milestone_id: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub points: Vec<BurndownPoint>,
+}
+
+impl From<milestone::Model> for MilestoneResponse {
+    fn from(milestone: milestone::Model) -> Self {
+        MilestoneResponse {
+            id: milestone.id,
+            invite_id: milestone.invite_id,
+            name: milestone.name,
+            start_date: milestone.start_date.to_string(),
+            end_date: milestone.end_date.to_string(),
+            created_at: milestone.created_at.to_rfc3339(),
+            updated_at: milestone.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, StatusCode> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").map_err(|_| StatusCode::BAD_REQUEST)
+}
+
+// 创建里程碑接口
+pub async fn create_milestone(
+    State(db): State<DatabaseConnection>,
+    Path(invite_id): Path<String>,
+    Json(payload): Json<CreateMilestoneRequest>,
+) -> Result<Json<MilestoneResponse>, StatusCode> {
+    // 只有看板创建者可以管理里程碑
+    Invite::find()
+        .filter(invite::Column::Id.eq(&invite_id))
+        .filter(invite::Column::CreatorId.eq(&payload.creator_id))
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    ensure_board_writable(&db, &invite_id).await?;
+
+    let name = payload.name.trim().to_string();
+    let start_date = parse_date(&payload.start_date)?;
+    let end_date = parse_date(&payload.end_date)?;
+    if name.is_empty() || end_date < start_date {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let milestone_id = uuid::Uuid::new_v4().to_string();
+    let now = Utc::now();
+
+    db.execute(
+        sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            r#"
+            INSERT INTO milestones (id, invite_id, name, start_date, end_date, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+            [
+                milestone_id.clone().into(),
+                invite_id.into(),
+                name.into(),
+                start_date.into(),
+                end_date.into(),
+                now.into(),
+                now.into(),
+            ]
+        )
+    ).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let milestone = Milestone::find_by_id(&milestone_id)
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(milestone.into()))
+}
+
+// 获取看板的里程碑列表，按开始日期排序
+pub async fn get_milestones(
+    State(db): State<DatabaseConnection>,
+    Path(invite_id): Path<String>,
+) -> Result<Json<MilestoneListResponse>, StatusCode> {
+    let milestones = Milestone::find()
+        .filter(milestone::Column::InviteId.eq(&invite_id))
+        .order_by_asc(milestone::Column::StartDate)
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(MilestoneListResponse {
+        milestones: milestones.into_iter().map(Into::into).collect(),
+    }))
+}
+
+// 更新里程碑接口
+pub async fn update_milestone(
+    State(db): State<DatabaseConnection>,
+    Path(milestone_id): Path<String>,
+    Json(payload): Json<UpdateMilestoneRequest>,
+) -> Result<Json<MilestoneResponse>, StatusCode> {
+    let milestone = find_creator_milestone(&db, &milestone_id, &payload.creator_id).await?;
+
+    let start_date = match &payload.start_date {
+        Some(value) => parse_date(value)?,
+        None => milestone.start_date,
+    };
+    let end_date = match &payload.end_date {
+        Some(value) => parse_date(value)?,
+        None => milestone.end_date,
+    };
+    if end_date < start_date {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let mut milestone_active: milestone::ActiveModel = milestone.into();
+    if let Some(name) = payload.name {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        milestone_active.name = Set(name);
+    }
+    milestone_active.start_date = Set(start_date);
+    milestone_active.end_date = Set(end_date);
+    milestone_active.updated_at = Set(Utc::now().into());
+
+    let updated = milestone_active
+        .update(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(updated.into()))
+}
+
+// 删除里程碑接口，任务本身不受影响
+pub async fn delete_milestone(
+    State(db): State<DatabaseConnection>,
+    Path(milestone_id): Path<String>,
+    Query(query): Query<CreatorQuery>,
+) -> Result<StatusCode, StatusCode> {
+    let milestone = find_creator_milestone(&db, &milestone_id, &query.creator_id).await?;
+
+    milestone.delete(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// 向里程碑添加任务，任务必须属于同一看板，已在里程碑中的任务保持原加入时间
+pub async fn add_milestone_tasks(
+    State(db): State<DatabaseConnection>,
+    Path(milestone_id): Path<String>,
+    Json(payload): Json<AddMilestoneTasksRequest>,
+) -> Result<Json<TaskListResponse>, StatusCode> {
+    let milestone = find_creator_milestone(&db, &milestone_id, &payload.creator_id).await?;
+
+    if payload.task_ids.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let tasks = Task::find()
+        .filter(task::Column::DeletedAt.is_null())
+        .filter(task::Column::Id.is_in(payload.task_ids.clone()))
+        .filter(task::Column::InviteId.eq(&milestone.invite_id))
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    if payload.task_ids.iter().any(|id| !tasks.iter().any(|task| task.id == *id)) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let txn = db.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let now = Utc::now();
+    for task in &tasks {
+        txn.execute(
+            sea_orm::Statement::from_sql_and_values(
+                sea_orm::DatabaseBackend::Sqlite,
+                r#"
+                INSERT OR IGNORE INTO milestone_tasks (milestone_id, task_id, added_at)
+                VALUES (?, ?, ?)
+                "#,
+                [milestone_id.clone().into(), task.id.clone().into(), now.into()]
+            )
+        ).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+    txn.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(TaskListResponse {
+        tasks: milestone_tasks(&db, &milestone_id).await?.into_iter().map(Into::into).collect(),
+    }))
+}
+
+// 从里程碑移除任务
+pub async fn remove_milestone_task(
+    State(db): State<DatabaseConnection>,
+    Path((milestone_id, task_id)): Path<(String, String)>,
+    Query(query): Query<CreatorQuery>,
+) -> Result<StatusCode, StatusCode> {
+    find_creator_milestone(&db, &milestone_id, &query.creator_id).await?;
+
+    let membership = MilestoneTask::find_by_id((milestone_id, task_id))
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    membership.delete(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// 获取里程碑中的任务
+pub async fn get_milestone_tasks(
+    State(db): State<DatabaseConnection>,
+    Path(milestone_id): Path<String>,
+) -> Result<Json<TaskListResponse>, StatusCode> {
+    Milestone::find_by_id(&milestone_id)
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(Json(TaskListResponse {
+        tasks: milestone_tasks(&db, &milestone_id).await?.into_iter().map(Into::into).collect(),
+    }))
+}
+
+// 燃尽/燃起图数据：从开始日期到结束日期（或今天）每天结束时的范围和完成数
+pub async fn get_milestone_burndown(
+    State(db): State<DatabaseConnection>,
+    Path(milestone_id): Path<String>,
+) -> Result<Json<BurndownResponse>, StatusCode> {
+    let milestone = Milestone::find_by_id(&milestone_id)
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let memberships = MilestoneTask::find()
+        .filter(milestone_task::Column::MilestoneId.eq(&milestone_id))
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let added_at: HashMap<String, chrono::DateTime<Utc>> = memberships
+        .into_iter()
+        .map(|m| (m.task_id, m.added_at.with_timezone(&Utc)))
+        .collect();
+
+    let tasks = Task::find()
+        .filter(task::Column::DeletedAt.is_null())
+        .filter(task::Column::Id.is_in(added_at.keys().cloned()))
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let mut history: HashMap<String, Vec<task_status_history::Model>> = HashMap::new();
+    for entry in TaskStatusHistory::find()
+        .filter(task_status_history::Column::TaskId.is_in(added_at.keys().cloned()))
+        .order_by_asc(task_status_history::Column::ChangedAt)
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    {
+        history.entry(entry.task_id.clone()).or_default().push(entry);
+    }
+
+    let now = Utc::now();
+    let today = now.date_naive();
+    let last_date = milestone.end_date.min(today);
+    let total_days = (milestone.end_date - milestone.start_date).num_days();
+    let empty = Vec::new();
+
+    let mut points: Vec<BurndownPoint> = Vec::new();
+    let mut date = milestone.start_date;
+    while date <= last_date {
+        let at = if date == today { now } else { start_of_day(date + Duration::days(1)) };
+
+        let mut scope = 0;
+        let mut completed = 0;
+        for task in &tasks {
+            if added_at.get(&task.id).is_some_and(|added| *added > at) {
+                continue;
+            }
+            match status_at(task, history.get(&task.id).unwrap_or(&empty), at) {
+                Some((_, "finished")) => {
+                    scope += 1;
+                    completed += 1;
+                }
+                Some(_) => scope += 1,
+                None => {}
+            }
+        }
+
+        points.push(BurndownPoint {
+            date: date.to_string(),
+            scope,
+            completed,
+            remaining: scope - completed,
+            ideal_remaining: 0.0,
+        });
+        date += Duration::days(1);
+    }
+
+    // 理想线按最新范围从开始日期线性下降到结束日期为0，里程碑开始后才加入的任务也计入
+    let final_scope = points.last().map_or(0, |point| point.scope) as f64;
+    for (day, point) in points.iter_mut().enumerate() {
+        if total_days > 0 {
+            let ideal = final_scope * (total_days - day as i64) as f64 / total_days as f64;
+            point.ideal_remaining = (ideal * 100.0).round() / 100.0;
+        }
+    }
+
+    Ok(Json(BurndownResponse {
+        milestone_id,
+        start_date: milestone.start_date.to_string(),
+        end_date: milestone.end_date.to_string(),
+        points,
+    }))
+}
+
+// 查找创建者看板上的里程碑
+async fn find_creator_milestone(
+    db: &DatabaseConnection,
+    milestone_id: &str,
+    creator_id: &str,
+) -> Result<milestone::Model, StatusCode> {
+    let milestone = Milestone::find_by_id(milestone_id)
+        .one(db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    Invite::find()
+        .filter(invite::Column::Id.eq(&milestone.invite_id))
+        .filter(invite::Column::CreatorId.eq(creator_id))
+        .one(db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    ensure_board_writable(db, &milestone.invite_id).await?;
+
+    Ok(milestone)
+}
+
+// 里程碑中的任务，按加入时间排序
+async fn milestone_tasks(db: &DatabaseConnection, milestone_id: &str) -> Result<Vec<task::Model>, StatusCode> {
+    let task_ids: Vec<String> = MilestoneTask::find()
+        .filter(milestone_task::Column::MilestoneId.eq(milestone_id))
+        .order_by_asc(milestone_task::Column::AddedAt)
+        .all(db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .into_iter()
+        .map(|m| m.task_id)
+        .collect();
+
+    let mut tasks = Task::find()
+        .filter(task::Column::DeletedAt.is_null())
+        .filter(task::Column::Id.is_in(task_ids.clone()))
+        .all(db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    tasks.sort_by_key(|task| task_ids.iter().position(|id| *id == task.id));
+    Ok(tasks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    use crate::handlers::task::record_status_change;
+    use crate::test_util::{create_board, create_task, create_user, test_db};
+
+    async fn execute(db: &DatabaseConnection, sql: &str, values: Vec<sea_orm::Value>) {
+        db.execute(sea_orm::Statement::from_sql_and_values(sea_orm::DatabaseBackend::Sqlite, sql, values))
+            .await
+            .unwrap();
+    }
+
+    // 开始后加入的任务增加范围，完成后重新打开的任务不再计入完成数
+    #[tokio::test]
+    async fn burndown_tracks_scope_increases_and_reopened_tasks() {
+        let db = test_db().await;
+        let creator_id = create_user(&db, "creator", "creator").await;
+        let invite_id = create_board(&db, &creator_id, None).await;
+
+        let today = Utc::now().date_naive();
+        let start = today - Duration::days(4);
+        let Json(milestone) = create_milestone(
+            State(db.clone()),
+            Path(invite_id.clone()),
+            Json(serde_json::from_value(json!({
+                "creator_id": creator_id,
+                "name": "迭代1",
+                "start_date": start.to_string(),
+                "end_date": (start + Duration::days(7)).to_string(),
+            })).unwrap()),
+        )
+        .await
+        .unwrap();
+
+        let reopened = create_task(&db, &invite_id, &creator_id, None).await;
+        let added_later = create_task(&db, &invite_id, &creator_id, None).await;
+        let Json(_) = add_milestone_tasks(
+            State(db.clone()),
+            Path(milestone.id.clone()),
+            Json(serde_json::from_value(json!({
+                "creator_id": creator_id,
+                "task_ids": [reopened.id, added_later.id],
+            })).unwrap()),
+        )
+        .await
+        .unwrap();
+
+        // 两个任务都在开始前一天创建；第一个任务开始前加入，第三天中午才加入第二个任务
+        let created_at = start_of_day(start) - Duration::days(1);
+        let noon = |day: i64| start_of_day(start + Duration::days(day)) + Duration::hours(12);
+        for task_id in [&reopened.id, &added_later.id] {
+            execute(&db, "UPDATE tasks SET created_at = ? WHERE id = ?", vec![created_at.into(), task_id.as_str().into()]).await;
+            execute(&db, "UPDATE task_status_history SET changed_at = ? WHERE task_id = ?", vec![created_at.into(), task_id.as_str().into()]).await;
+        }
+        execute(&db, "UPDATE milestone_tasks SET added_at = ? WHERE task_id = ?", vec![created_at.into(), reopened.id.as_str().into()]).await;
+        execute(&db, "UPDATE milestone_tasks SET added_at = ? WHERE task_id = ?", vec![noon(2).into(), added_later.id.as_str().into()]).await;
+
+        // 第一个任务第二天完成，第四天重新打开
+        record_status_change(&db, &reopened.id, Some("todo"), "done", "finished", None, noon(1)).await.unwrap();
+        record_status_change(&db, &reopened.id, Some("done"), "todo", "not_started", None, noon(3)).await.unwrap();
+
+        let Json(burndown) = get_milestone_burndown(State(db.clone()), Path(milestone.id.clone())).await.unwrap();
+        let points: Vec<(&str, usize, usize, usize)> = burndown
+            .points
+            .iter()
+            .map(|point| (point.date.as_str(), point.scope, point.completed, point.remaining))
+            .collect();
+        let dates: Vec<String> = (0..5).map(|day| (start + Duration::days(day)).to_string()).collect();
+        assert_eq!(points, [
+            (dates[0].as_str(), 1, 0, 1),
+            (dates[1].as_str(), 1, 1, 0),
+            (dates[2].as_str(), 2, 1, 1),
+            (dates[3].as_str(), 2, 0, 2),
+            (dates[4].as_str(), 2, 0, 2),
+        ]);
+
+        // 理想线按最终范围计算
+        assert_eq!(burndown.points[0].ideal_remaining, 2.0);
+        assert_eq!(burndown.points[1].ideal_remaining, 1.71);
+    }
+}
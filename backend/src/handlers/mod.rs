@@ -1,7 +1,30 @@
+pub mod admin;
+pub mod archive;
+// SeaORM版本的认证接口，当前路由使用auth_simple
+#[allow(dead_code)]
 pub mod auth;
 pub mod auth_simple;
+pub mod bulk;
+pub mod calendar;
+pub mod column;
+pub mod comment;
+pub mod completion;
+pub mod custom_field;
+pub mod export;
+pub mod import;
 pub mod invite;
+pub mod label;
+pub mod mention;
+pub mod milestone;
+pub mod notification;
+pub mod recurring_task;
+pub mod report;
+pub mod review;
+pub mod stats;
 pub mod task;
+pub mod template;
+pub mod time_entry;
+pub mod webhook;
 
 use axum::{extract::State, http::StatusCode, Json};
 use sea_orm::DatabaseConnection;
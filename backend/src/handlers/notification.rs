@@ -0,0 +1,290 @@
+use axum::{extract::{Path, Query, State}, http::StatusCode, Json};
+use sea_orm::{ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set};
+use sea_orm::sea_query::Expr;
+use serde::{Deserialize, Serialize};
+use chrono;
+
+use crate::mailer;
+use crate::models::{notification, notification::Entity as Notification, notification_preference, user::Entity as User};
+
+#[derive(Deserialize)]
+pub struct NotificationListQuery {
+    pub unread: Option<bool>,
+    pub kind: Option<String>,
+    pub invite_id: Option<String>,
+    pub task_id: Option<String>,
+    pub limit: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub struct MarkNotificationRequest {
+    pub user_id: String,
+    pub read: bool,
+}
+
+#[derive(Serialize)]
+pub struct NotificationResponse {
+    pub id: String,
+    pub user_id: String,
+    pub actor_id: Option<String>,
+    pub kind: String,
+    pub invite_id: Option<String>,
+    pub task_id: Option<String>,
+    pub message: String,
+    pub read: bool,
+    pub read_at: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Serialize)]
+pub struct NotificationListResponse {
+    pub notifications: Vec<NotificationResponse>,
+}
+
+#[derive(Serialize)]
+pub struct UnreadCountResponse {
+    pub count: u64,
+}
+
+#[derive(Serialize)]
+pub struct MarkAllReadResponse {
+    pub updated: u64,
+}
+
+#[derive(Deserialize)]
+pub struct UpdatePreferencesRequest {
+    pub email_invite_redeemed: Option<bool>,
+    pub email_task_completed: Option<bool>,
+    pub email_task_overdue: Option<bool>,
+    pub daily_digest: Option<bool>,
+    pub digest_hour: Option<i32>,
+}
+
+#[derive(Serialize)]
+pub struct PreferencesResponse {
+    pub user_id: String,
+    pub email_invite_redeemed: bool,
+    pub email_task_completed: bool,
+    pub email_task_overdue: bool,
+    pub daily_digest: bool,
+    pub digest_hour: i32,
+    pub last_digest_at: Option<String>,
+}
+
+impl From<notification_preference::Model> for PreferencesResponse {
+    fn from(preference: notification_preference::Model) -> Self {
+        PreferencesResponse {
+            user_id: preference.user_id,
+            email_invite_redeemed: preference.email_invite_redeemed,
+            email_task_completed: preference.email_task_completed,
+            email_task_overdue: preference.email_task_overdue,
+            daily_digest: preference.daily_digest,
+            digest_hour: preference.digest_hour,
+            last_digest_at: preference.last_digest_at.map(|dt| dt.to_rfc3339()),
+        }
+    }
+}
+
+impl From<notification::Model> for NotificationResponse {
+    fn from(notification: notification::Model) -> Self {
+        NotificationResponse {
+            id: notification.id,
+            user_id: notification.user_id,
+            actor_id: notification.actor_id,
+            kind: notification.kind,
+            invite_id: notification.invite_id,
+            task_id: notification.task_id,
+            message: notification.message,
+            read: notification.read_at.is_some(),
+            read_at: notification.read_at.map(|dt| dt.to_rfc3339()),
+            created_at: notification.created_at.to_rfc3339(),
+        }
+    }
+}
+
+// 获取用户通知列表，支持按已读状态、类型、看板和任务过滤
+pub async fn get_notifications(
+    State(db): State<DatabaseConnection>,
+    Path(user_id): Path<String>,
+    Query(query): Query<NotificationListQuery>,
+) -> Result<Json<NotificationListResponse>, StatusCode> {
+    let mut select = Notification::find()
+        .filter(notification::Column::UserId.eq(&user_id));
+
+    if let Some(unread) = query.unread {
+        select = if unread {
+            select.filter(notification::Column::ReadAt.is_null())
+        } else {
+            select.filter(notification::Column::ReadAt.is_not_null())
+        };
+    }
+    if let Some(kind) = &query.kind {
+        select = select.filter(notification::Column::Kind.eq(kind));
+    }
+    if let Some(invite_id) = &query.invite_id {
+        select = select.filter(notification::Column::InviteId.eq(invite_id));
+    }
+    if let Some(task_id) = &query.task_id {
+        select = select.filter(notification::Column::TaskId.eq(task_id));
+    }
+
+    let notifications = select
+        .order_by_desc(notification::Column::CreatedAt)
+        .limit(query.limit.unwrap_or(50).min(200))
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(NotificationListResponse {
+        notifications: notifications.into_iter().map(Into::into).collect(),
+    }))
+}
+
+// 获取未读通知数量
+pub async fn get_unread_count(
+    State(db): State<DatabaseConnection>,
+    Path(user_id): Path<String>,
+) -> Result<Json<UnreadCountResponse>, StatusCode> {
+    let count = Notification::find()
+        .filter(notification::Column::UserId.eq(&user_id))
+        .filter(notification::Column::ReadAt.is_null())
+        .count(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(UnreadCountResponse { count }))
+}
+
+// 标记单条通知为已读或未读
+pub async fn mark_notification(
+    State(db): State<DatabaseConnection>,
+    Path(notification_id): Path<String>,
+    Json(payload): Json<MarkNotificationRequest>,
+) -> Result<Json<NotificationResponse>, StatusCode> {
+    // 只能操作自己的通知
+    let notification = Notification::find_by_id(&notification_id)
+        .filter(notification::Column::UserId.eq(&payload.user_id))
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let mut notification_active: notification::ActiveModel = notification.into();
+    notification_active.read_at = Set(if payload.read {
+        Some(chrono::Utc::now().into())
+    } else {
+        None
+    });
+
+    let updated = notification_active
+        .update(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(updated.into()))
+}
+
+// 将用户的全部未读通知标记为已读
+pub async fn mark_all_read(
+    State(db): State<DatabaseConnection>,
+    Path(user_id): Path<String>,
+) -> Result<Json<MarkAllReadResponse>, StatusCode> {
+    let now: chrono::DateTime<chrono::FixedOffset> = chrono::Utc::now().into();
+    let result = Notification::update_many()
+        .col_expr(notification::Column::ReadAt, Expr::value(now))
+        .filter(notification::Column::UserId.eq(&user_id))
+        .filter(notification::Column::ReadAt.is_null())
+        .exec(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(MarkAllReadResponse {
+        updated: result.rows_affected,
+    }))
+}
+
+// 获取用户的通知偏好
+pub async fn get_preferences(
+    State(db): State<DatabaseConnection>,
+    Path(user_id): Path<String>,
+) -> Result<Json<PreferencesResponse>, StatusCode> {
+    User::find_by_id(&user_id)
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let preference = mailer::preferences(&db, &user_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(preference.into()))
+}
+
+// 更新用户的通知偏好，未传的字段保持不变
+pub async fn update_preferences(
+    State(db): State<DatabaseConnection>,
+    Path(user_id): Path<String>,
+    Json(payload): Json<UpdatePreferencesRequest>,
+) -> Result<Json<PreferencesResponse>, StatusCode> {
+    if payload.digest_hour.is_some_and(|hour| !(0..=23).contains(&hour)) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    User::find_by_id(&user_id)
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let mut preference = mailer::preferences(&db, &user_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if let Some(value) = payload.email_invite_redeemed {
+        preference.email_invite_redeemed = value;
+    }
+    if let Some(value) = payload.email_task_completed {
+        preference.email_task_completed = value;
+    }
+    if let Some(value) = payload.email_task_overdue {
+        preference.email_task_overdue = value;
+    }
+    if let Some(value) = payload.daily_digest {
+        preference.daily_digest = value;
+    }
+    if let Some(value) = payload.digest_hour {
+        preference.digest_hour = value;
+    }
+    preference.updated_at = chrono::Utc::now().into();
+
+    // 使用原生SQL写入，不存在时插入，存在时更新
+    db.execute(
+        sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            r#"
+            INSERT INTO notification_preferences (user_id, email_invite_redeemed, email_task_completed, email_task_overdue, daily_digest, digest_hour, last_digest_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(user_id) DO UPDATE SET
+                email_invite_redeemed = excluded.email_invite_redeemed,
+                email_task_completed = excluded.email_task_completed,
+                email_task_overdue = excluded.email_task_overdue,
+                daily_digest = excluded.daily_digest,
+                digest_hour = excluded.digest_hour,
+                updated_at = excluded.updated_at
+            "#,
+            [
+                preference.user_id.clone().into(),
+                preference.email_invite_redeemed.into(),
+                preference.email_task_completed.into(),
+                preference.email_task_overdue.into(),
+                preference.daily_digest.into(),
+                preference.digest_hour.into(),
+                preference.last_digest_at.into(),
+                preference.updated_at.into(),
+            ]
+        )
+    ).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(preference.into()))
+}
@@ -0,0 +1,244 @@
+use axum::{extract::{Path, Query, State}, http::StatusCode, Json};
+use chrono::{DateTime, FixedOffset, Utc};
+use sea_orm::{ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, ModelTrait, QueryFilter, QueryOrder, Set};
+use serde::{Deserialize, Serialize};
+
+use crate::handlers::archive::ensure_board_writable;
+use crate::models::{invite, invite::Entity as Invite, recurring_task, recurring_task::Entity as RecurringTask};
+use crate::recurrence::Rule;
+
+#[derive(Deserialize)]
+pub struct CreateRecurringTaskRequest {
+    pub creator_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub rrule: String,                   // 如 "FREQ=WEEKLY;INTERVAL=1;UNTIL=20261231"
+    pub start_at: DateTime<FixedOffset>, // 第一次重复的时间
+    pub due_in_hours: Option<i64>,       // 截止时间相对每次重复的小时数，为空表示不设截止时间
+    pub paused: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateRecurringTaskRequest {
+    pub creator_id: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub rrule: Option<String>,
+    pub start_at: Option<DateTime<FixedOffset>>,
+    pub due_in_hours: Option<i64>,
+    pub paused: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub struct CreatorQuery {
+    pub creator_id: String,
+}
+
+#[derive(Serialize)]
+pub struct RecurringTaskResponse {
+    pub id: String,
+    pub invite_id: String,
+    pub creator_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub rrule: String,
+    pub start_at: String,
+    pub due_in_hours: Option<i64>,
+    pub paused: bool,
+    pub next_run_at: Option<String>,
+    pub last_run_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Serialize)]
+pub struct RecurringTaskListResponse {
+    pub recurring_tasks: Vec<RecurringTaskResponse>,
+}
+
+impl From<recurring_task::Model> for RecurringTaskResponse {
+    fn from(template: recurring_task::Model) -> Self {
+        RecurringTaskResponse {
+            id: template.id,
+            invite_id: template.invite_id,
+            creator_id: template.creator_id,
+            title: template.title,
+            description: template.description,
+            rrule: template.rrule,
+            start_at: template.start_at.to_rfc3339(),
+            due_in_hours: template.due_in_hours,
+            paused: template.paused,
+            next_run_at: template.next_run_at.map(|dt| dt.to_rfc3339()),
+            last_run_at: template.last_run_at.map(|dt| dt.to_rfc3339()),
+            created_at: template.created_at.to_rfc3339(),
+            updated_at: template.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+// 从现在起的第一次重复，已经过去的重复不再补建
+fn first_run(rule: &Rule, start_at: DateTime<FixedOffset>) -> Option<DateTime<FixedOffset>> {
+    rule.next_on_or_after(start_at.with_timezone(&Utc), Utc::now())
+        .map(Into::into)
+}
+
+// 创建周期任务接口
+pub async fn create_recurring_task(
+    State(db): State<DatabaseConnection>,
+    Path(invite_id): Path<String>,
+    Json(payload): Json<CreateRecurringTaskRequest>,
+) -> Result<Json<RecurringTaskResponse>, StatusCode> {
+    // 只有看板创建者可以管理周期任务
+    Invite::find()
+        .filter(invite::Column::Id.eq(&invite_id))
+        .filter(invite::Column::CreatorId.eq(&payload.creator_id))
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    ensure_board_writable(&db, &invite_id).await?;
+
+    let title = payload.title.trim().to_string();
+    let rule = Rule::parse(&payload.rrule).map_err(|_| StatusCode::BAD_REQUEST)?;
+    if title.is_empty()
+        || payload.due_in_hours.is_some_and(|hours| hours < 0)
+        || rule.until.is_some_and(|until| until < payload.start_at)
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let template_id = uuid::Uuid::new_v4().to_string();
+    let now = Utc::now();
+
+    db.execute(
+        sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            r#"
+            INSERT INTO recurring_tasks (id, invite_id, creator_id, title, description, rrule, start_at, due_in_hours, paused, next_run_at, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            [
+                template_id.clone().into(),
+                invite_id.into(),
+                payload.creator_id.into(),
+                title.into(),
+                payload.description.into(),
+                rule.to_string().into(),
+                payload.start_at.with_timezone(&Utc).into(),
+                payload.due_in_hours.into(),
+                payload.paused.unwrap_or(false).into(),
+                first_run(&rule, payload.start_at).into(),
+                now.into(),
+                now.into(),
+            ]
+        )
+    ).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let template = RecurringTask::find_by_id(&template_id)
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(template.into()))
+}
+
+// 获取看板的周期任务列表
+pub async fn get_recurring_tasks(
+    State(db): State<DatabaseConnection>,
+    Path(invite_id): Path<String>,
+) -> Result<Json<RecurringTaskListResponse>, StatusCode> {
+    let templates = RecurringTask::find()
+        .filter(recurring_task::Column::InviteId.eq(&invite_id))
+        .order_by_asc(recurring_task::Column::CreatedAt)
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(RecurringTaskListResponse {
+        recurring_tasks: templates.into_iter().map(Into::into).collect(),
+    }))
+}
+
+// 更新周期任务接口，修改规则、开始时间或恢复暂停时从现在起重新计算下次执行时间
+pub async fn update_recurring_task(
+    State(db): State<DatabaseConnection>,
+    Path(template_id): Path<String>,
+    Json(payload): Json<UpdateRecurringTaskRequest>,
+) -> Result<Json<RecurringTaskResponse>, StatusCode> {
+    let template = find_creator_template(&db, &template_id, &payload.creator_id).await?;
+
+    let rule = Rule::parse(payload.rrule.as_deref().unwrap_or(&template.rrule))
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+    let start_at = payload.start_at.map(|dt| dt.with_timezone(&Utc).into()).unwrap_or(template.start_at);
+    if payload.due_in_hours.is_some_and(|hours| hours < 0)
+        || rule.until.is_some_and(|until| until < start_at)
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let resumed = template.paused && payload.paused == Some(false);
+    let reschedule = payload.rrule.is_some() || payload.start_at.is_some() || resumed;
+
+    let mut template_active: recurring_task::ActiveModel = template.into();
+    if let Some(title) = payload.title {
+        let title = title.trim().to_string();
+        if title.is_empty() {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        template_active.title = Set(title);
+    }
+    if let Some(description) = payload.description {
+        template_active.description = Set(Some(description));
+    }
+    if let Some(due_in_hours) = payload.due_in_hours {
+        template_active.due_in_hours = Set(Some(due_in_hours));
+    }
+    if let Some(paused) = payload.paused {
+        template_active.paused = Set(paused);
+    }
+    if reschedule {
+        template_active.rrule = Set(rule.to_string());
+        template_active.start_at = Set(start_at);
+        template_active.next_run_at = Set(first_run(&rule, start_at));
+    }
+    template_active.updated_at = Set(Utc::now().into());
+
+    let updated = template_active
+        .update(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(updated.into()))
+}
+
+// 删除周期任务接口，已经创建的任务保留
+pub async fn delete_recurring_task(
+    State(db): State<DatabaseConnection>,
+    Path(template_id): Path<String>,
+    Query(query): Query<CreatorQuery>,
+) -> Result<StatusCode, StatusCode> {
+    let template = find_creator_template(&db, &template_id, &query.creator_id).await?;
+
+    template.delete(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// 查找创建者名下的周期任务
+async fn find_creator_template(
+    db: &DatabaseConnection,
+    template_id: &str,
+    creator_id: &str,
+) -> Result<recurring_task::Model, StatusCode> {
+    let template = RecurringTask::find_by_id(template_id)
+        .filter(recurring_task::Column::CreatorId.eq(creator_id))
+        .one(db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    ensure_board_writable(db, &template.invite_id).await?;
+
+    Ok(template)
+}
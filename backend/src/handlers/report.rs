@@ -0,0 +1,654 @@
+use axum::{extract::{Query, State}, http::StatusCode, Json};
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::handlers::stats::{completion_time, hours_between, round_hours, start_of_day, start_time};
+use crate::handlers::time_entry::duration_seconds;
+use crate::models::{
+    invite, invite::Entity as Invite, label, label::Entity as Label, task, task::Entity as Task, task_label,
+    task_label::Entity as TaskLabel, task_status_history, task_status_history::Entity as TaskStatusHistory,
+    time_entry, time_entry::Entity as TimeEntry, user, user::Entity as User,
+};
+
+#[derive(Deserialize)]
+pub struct WorkloadQuery {
+    pub user_id: String,             // 查看报表的用户
+    pub executor_id: Option<String>, // 只看某个执行者
+    pub period: Option<String>,      // "week"（默认）或 "month"
+    pub periods: Option<i64>,        // 统计的周期数，默认8
+}
+
+#[derive(Serialize)]
+pub struct PeriodCount {
+    pub period_start: String,
+    pub completed: usize,
+}
+
+#[derive(Serialize)]
+pub struct BoardWorkload {
+    pub invite_id: String,
+    pub open: usize,
+    pub overdue: usize,
+    pub completed: usize,
+}
+
+#[derive(Serialize)]
+pub struct ExecutorWorkload {
+    pub executor_id: String,
+    pub username: Option<String>,
+    pub open: usize,
+    pub overdue: usize,
+    pub completed: usize,
+    pub completed_per_period: Vec<PeriodCount>,
+    pub average_completion_hours: Option<f64>,
+    pub boards: Vec<BoardWorkload>,
+}
+
+#[derive(Serialize)]
+pub struct WorkloadReportResponse {
+    pub period: String,
+    pub executors: Vec<ExecutorWorkload>,
+}
+
+#[derive(Default)]
+struct Accumulator {
+    open: usize,
+    overdue: usize,
+    completed: usize,
+    per_period: Vec<usize>,
+    completion_hours: Vec<f64>,
+    boards: BTreeMap<String, (usize, usize, usize)>,
+}
+
+#[derive(Deserialize)]
+pub struct EstimateReportQuery {
+    pub user_id: String,             // 查看报表的用户
+    pub executor_id: Option<String>, // 只看某个执行者
+    pub invite_id: Option<String>,   // 只看某个看板
+    pub since: Option<NaiveDate>,    // 只统计该日期（UTC）之后完成的任务
+}
+
+// 以小时预估的任务：预估与实际工时、周期时长的对比
+#[derive(Serialize, Default)]
+pub struct HoursAccuracy {
+    pub tasks: usize,
+    pub estimated_hours: f64,
+    pub tracked_hours: f64,
+    pub cycle_hours: f64,
+    pub tracked_ratio: Option<f64>, // 实际工时 / 预估工时
+    pub cycle_ratio: Option<f64>,   // 周期时长 / 预估工时
+}
+
+// 以故事点预估的任务：每个故事点对应的实际工时和周期时长
+#[derive(Serialize, Default)]
+pub struct PointsAccuracy {
+    pub tasks: usize,
+    pub points: f64,
+    pub tracked_hours: f64,
+    pub cycle_hours: f64,
+    pub tracked_hours_per_point: Option<f64>,
+    pub cycle_hours_per_point: Option<f64>,
+}
+
+#[derive(Serialize)]
+pub struct ExecutorEstimates {
+    pub executor_id: String,
+    pub username: Option<String>,
+    pub hours: HoursAccuracy,
+    pub points: PointsAccuracy,
+}
+
+#[derive(Serialize)]
+pub struct LabelEstimates {
+    pub label_id: String,
+    pub invite_id: String,
+    pub name: String,
+    pub hours: HoursAccuracy,
+    pub points: PointsAccuracy,
+}
+
+#[derive(Serialize)]
+pub struct EstimateReportResponse {
+    pub unestimated_completed: usize, // 已完成但没有预估的任务数
+    pub executors: Vec<ExecutorEstimates>,
+    pub labels: Vec<LabelEstimates>,
+}
+
+#[derive(Default)]
+struct EstimateAccumulator {
+    hours: HoursAccuracy,
+    points: PointsAccuracy,
+}
+
+impl EstimateAccumulator {
+    fn add(&mut self, unit: &str, estimate: f64, tracked_hours: f64, cycle_hours: f64) {
+        if unit == "points" {
+            self.points.tasks += 1;
+            self.points.points += estimate;
+            self.points.tracked_hours += tracked_hours;
+            self.points.cycle_hours += cycle_hours;
+        } else {
+            self.hours.tasks += 1;
+            self.hours.estimated_hours += estimate;
+            self.hours.tracked_hours += tracked_hours;
+            self.hours.cycle_hours += cycle_hours;
+        }
+    }
+
+    // 计算比值并保留两位小数
+    fn finish(self) -> (HoursAccuracy, PointsAccuracy) {
+        let ratio = |actual: f64, estimate: f64| (estimate > 0.0).then(|| round_hours(actual / estimate));
+        let hours = HoursAccuracy {
+            tracked_ratio: ratio(self.hours.tracked_hours, self.hours.estimated_hours),
+            cycle_ratio: ratio(self.hours.cycle_hours, self.hours.estimated_hours),
+            estimated_hours: round_hours(self.hours.estimated_hours),
+            tracked_hours: round_hours(self.hours.tracked_hours),
+            cycle_hours: round_hours(self.hours.cycle_hours),
+            tasks: self.hours.tasks,
+        };
+        let points = PointsAccuracy {
+            tracked_hours_per_point: ratio(self.points.tracked_hours, self.points.points),
+            cycle_hours_per_point: ratio(self.points.cycle_hours, self.points.points),
+            points: round_hours(self.points.points),
+            tracked_hours: round_hours(self.points.tracked_hours),
+            cycle_hours: round_hours(self.points.cycle_hours),
+            tasks: self.points.tasks,
+        };
+        (hours, points)
+    }
+}
+
+// 周期起始日：周一或每月1日
+fn period_start(date: NaiveDate, monthly: bool) -> NaiveDate {
+    if monthly {
+        date.with_day(1).expect("每月1日总是有效日期")
+    } else {
+        date - Duration::days(date.weekday().num_days_from_monday() as i64)
+    }
+}
+
+// 从最早到最近的周期起始日
+fn period_starts(today: NaiveDate, monthly: bool, periods: i64) -> Vec<NaiveDate> {
+    let mut starts = Vec::with_capacity(periods as usize);
+    let mut start = period_start(today, monthly);
+    for _ in 0..periods {
+        starts.push(start);
+        start = period_start(start - Duration::days(1), monthly);
+    }
+    starts.reverse();
+    starts
+}
+
+// 执行者工作量报表：执行者只能看自己在所有看板的数据，创建者只能看自己看板上的数据
+pub async fn get_workload_report(
+    State(db): State<DatabaseConnection>,
+    Query(query): Query<WorkloadQuery>,
+) -> Result<Json<WorkloadReportResponse>, StatusCode> {
+    let monthly = match query.period.as_deref().unwrap_or("week") {
+        "week" => false,
+        "month" => true,
+        _ => return Err(StatusCode::BAD_REQUEST),
+    };
+    let periods = query.periods.unwrap_or(8);
+    if !(1..=52).contains(&periods) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let viewer = User::find_by_id(&query.user_id)
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let executor_filter = executor_filter(&viewer, query.executor_id)?;
+    let owned_tasks = owned_tasks(&db, &viewer, executor_filter.as_ref()).await?;
+
+    let done_ids: Vec<String> = owned_tasks
+        .iter()
+        .filter(|(_, task)| task.status_category == "finished")
+        .map(|(_, task)| task.id.clone())
+        .collect();
+    let mut history: HashMap<String, Vec<task_status_history::Model>> = HashMap::new();
+    for entry in TaskStatusHistory::find()
+        .filter(task_status_history::Column::TaskId.is_in(done_ids))
+        .order_by_asc(task_status_history::Column::ChangedAt)
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    {
+        history.entry(entry.task_id.clone()).or_default().push(entry);
+    }
+
+    let now = Utc::now();
+    let starts = period_starts(now.date_naive(), monthly, periods);
+    let empty = Vec::new();
+
+    let mut accumulators: BTreeMap<String, Accumulator> = BTreeMap::new();
+    if let Some(executor_id) = &executor_filter {
+        accumulators.entry(executor_id.clone()).or_default();
+    }
+    for (executor_id, task) in owned_tasks {
+        let acc = accumulators.entry(executor_id).or_default();
+        acc.per_period.resize(starts.len(), 0);
+        let board = acc.boards.entry(task.invite_id.clone()).or_default();
+
+        if task.status_category == "finished" {
+            acc.completed += 1;
+            board.2 += 1;
+            let Some(completed_at) = completion_time(&task, history.get(&task.id).unwrap_or(&empty)) else { continue };
+
+            let start = period_start(completed_at.date_naive(), monthly);
+            if let Some(index) = starts.iter().position(|s| *s == start) {
+                acc.per_period[index] += 1;
+            }
+
+            let created_at = task.created_at.with_timezone(&Utc);
+            if completed_at >= created_at {
+                acc.completion_hours.push(hours_between(created_at, completed_at));
+            }
+        } else {
+            acc.open += 1;
+            board.0 += 1;
+            if task.due_date.is_some_and(|due| due.with_timezone(&Utc) < now) {
+                acc.overdue += 1;
+                board.1 += 1;
+            }
+        }
+    }
+
+    let usernames: HashMap<String, String> = User::find()
+        .filter(user::Column::Id.is_in(accumulators.keys().cloned()))
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .into_iter()
+        .map(|user| (user.id, user.username))
+        .collect();
+
+    let executors = accumulators
+        .into_iter()
+        .map(|(executor_id, mut acc)| {
+            acc.per_period.resize(starts.len(), 0);
+            let average = (!acc.completion_hours.is_empty())
+                .then(|| acc.completion_hours.iter().sum::<f64>() / acc.completion_hours.len() as f64);
+            ExecutorWorkload {
+                username: usernames.get(&executor_id).cloned(),
+                executor_id,
+                open: acc.open,
+                overdue: acc.overdue,
+                completed: acc.completed,
+                completed_per_period: starts
+                    .iter()
+                    .zip(acc.per_period)
+                    .map(|(start, completed)| PeriodCount {
+                        period_start: start.to_string(),
+                        completed,
+                    })
+                    .collect(),
+                average_completion_hours: average.map(round_hours),
+                boards: acc
+                    .boards
+                    .into_iter()
+                    .map(|(invite_id, (open, overdue, completed))| BoardWorkload {
+                        invite_id,
+                        open,
+                        overdue,
+                        completed,
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+
+    Ok(Json(WorkloadReportResponse {
+        period: if monthly { "month" } else { "week" }.to_string(),
+        executors,
+    }))
+}
+
+// 预估准确度报表：对比已完成任务的预估与实际工时、周期时长，按执行者和标签汇总
+pub async fn get_estimate_report(
+    State(db): State<DatabaseConnection>,
+    Query(query): Query<EstimateReportQuery>,
+) -> Result<Json<EstimateReportResponse>, StatusCode> {
+    let viewer = User::find_by_id(&query.user_id)
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let executor_filter = executor_filter(&viewer, query.executor_id)?;
+    let done_tasks: Vec<(String, task::Model)> = owned_tasks(&db, &viewer, executor_filter.as_ref())
+        .await?
+        .into_iter()
+        .filter(|(_, task)| task.status_category == "finished")
+        .filter(|(_, task)| query.invite_id.as_ref().map_or(true, |id| *id == task.invite_id))
+        .collect();
+    let done_ids: Vec<String> = done_tasks.iter().map(|(_, task)| task.id.clone()).collect();
+
+    let mut history: HashMap<String, Vec<task_status_history::Model>> = HashMap::new();
+    for entry in TaskStatusHistory::find()
+        .filter(task_status_history::Column::TaskId.is_in(done_ids.clone()))
+        .order_by_asc(task_status_history::Column::ChangedAt)
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    {
+        history.entry(entry.task_id.clone()).or_default().push(entry);
+    }
+
+    let now = Utc::now();
+    let mut tracked: HashMap<String, i64> = HashMap::new();
+    for entry in TimeEntry::find()
+        .filter(time_entry::Column::TaskId.is_in(done_ids.clone()))
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    {
+        *tracked.entry(entry.task_id.clone()).or_default() += duration_seconds(&entry, now);
+    }
+
+    let mut task_labels: HashMap<String, Vec<String>> = HashMap::new();
+    for task_label in TaskLabel::find()
+        .filter(task_label::Column::TaskId.is_in(done_ids))
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    {
+        task_labels.entry(task_label.task_id).or_default().push(task_label.label_id);
+    }
+
+    let since = query.since.map(start_of_day);
+    let empty = Vec::new();
+    let mut unestimated_completed = 0;
+    let mut executors: BTreeMap<String, EstimateAccumulator> = BTreeMap::new();
+    let mut labels: HashMap<String, EstimateAccumulator> = HashMap::new();
+    for (executor_id, task) in done_tasks {
+        let task_history = history.get(&task.id).unwrap_or(&empty);
+        let Some(completed_at) = completion_time(&task, task_history) else { continue };
+        if since.is_some_and(|since| completed_at < since) {
+            continue;
+        }
+        let Some(estimate) = task.estimate else {
+            unestimated_completed += 1;
+            continue;
+        };
+        let unit = task.estimate_unit.as_deref().unwrap_or("hours");
+
+        // 周期时长从第一次开始处理算起，没有开始记录时从创建算起
+        let started_at = start_time(task_history).unwrap_or(task.created_at.with_timezone(&Utc));
+        let cycle_hours = hours_between(started_at, completed_at).max(0.0);
+        let tracked_hours = tracked.get(&task.id).copied().unwrap_or(0) as f64 / 3600.0;
+
+        executors.entry(executor_id).or_default().add(unit, estimate, tracked_hours, cycle_hours);
+        for label_id in task_labels.get(&task.id).unwrap_or(&Vec::new()) {
+            labels.entry(label_id.clone()).or_default().add(unit, estimate, tracked_hours, cycle_hours);
+        }
+    }
+
+    let usernames: HashMap<String, String> = User::find()
+        .filter(user::Column::Id.is_in(executors.keys().cloned()))
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .into_iter()
+        .map(|user| (user.id, user.username))
+        .collect();
+
+    let label_models = Label::find()
+        .filter(label::Column::Id.is_in(labels.keys().cloned()))
+        .order_by_asc(label::Column::Name)
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(EstimateReportResponse {
+        unestimated_completed,
+        executors: executors
+            .into_iter()
+            .map(|(executor_id, acc)| {
+                let (hours, points) = acc.finish();
+                ExecutorEstimates {
+                    username: usernames.get(&executor_id).cloned(),
+                    executor_id,
+                    hours,
+                    points,
+                }
+            })
+            .collect(),
+        labels: label_models
+            .into_iter()
+            .filter_map(|label| {
+                let (hours, points) = labels.remove(&label.id)?.finish();
+                Some(LabelEstimates {
+                    label_id: label.id,
+                    invite_id: label.invite_id,
+                    name: label.name,
+                    hours,
+                    points,
+                })
+            })
+            .collect(),
+    }))
+}
+
+// 执行者只能看自己的数据，创建者可以指定执行者
+fn executor_filter(viewer: &user::Model, executor_id: Option<String>) -> Result<Option<String>, StatusCode> {
+    match viewer.role.as_str() {
+        "creator" => Ok(executor_id),
+        _ => {
+            if executor_id.as_ref().is_some_and(|id| *id != viewer.id) {
+                return Err(StatusCode::FORBIDDEN);
+            }
+            Ok(Some(viewer.id.clone()))
+        }
+    }
+}
+
+// 报表范围内的任务及其执行者：创建者看自己的看板，执行者看自己参与的看板
+async fn owned_tasks(
+    db: &DatabaseConnection,
+    viewer: &user::Model,
+    executor_filter: Option<&String>,
+) -> Result<Vec<(String, task::Model)>, StatusCode> {
+    let mut invites = Invite::find();
+    invites = if viewer.role == "creator" {
+        invites.filter(invite::Column::CreatorId.eq(&viewer.id))
+    } else {
+        invites.filter(invite::Column::ExecutorId.eq(&viewer.id))
+    };
+    let invites = invites
+        .all(db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let board_executors: HashMap<String, Option<String>> = invites
+        .into_iter()
+        .map(|invite| (invite.id, invite.executor_id))
+        .collect();
+
+    let tasks = Task::find()
+        .filter(task::Column::DeletedAt.is_null())
+        .filter(task::Column::InviteId.is_in(board_executors.keys().cloned()))
+        .all(db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // 未单独指定执行者的任务算在看板执行者名下
+    Ok(tasks
+        .into_iter()
+        .filter_map(|task| {
+            let executor_id = task
+                .executor_id
+                .clone()
+                .or_else(|| board_executors.get(&task.invite_id).cloned().flatten())?;
+            if executor_filter.is_some_and(|filter| *filter != executor_id) {
+                return None;
+            }
+            Some((executor_id, task))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::ConnectionTrait;
+
+    use crate::test_util::{create_board, create_task, create_user, test_db};
+
+    // 把任务标记为已完成，创建后cycle_hours小时完成
+    async fn complete(db: &DatabaseConnection, task_id: &str, cycle_hours: i64) {
+        let created_at = Utc::now() - Duration::days(2);
+        db.execute(sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            "UPDATE tasks SET status = 'done', status_category = 'finished', created_at = ?, completed_at = ? WHERE id = ?",
+            [created_at.into(), (created_at + Duration::hours(cycle_hours)).into(), task_id.into()],
+        ))
+        .await
+        .unwrap();
+    }
+
+    async fn set_estimate(db: &DatabaseConnection, task_id: &str, estimate: f64, unit: Option<&str>) {
+        db.execute(sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            "UPDATE tasks SET estimate = ?, estimate_unit = ? WHERE id = ?",
+            [estimate.into(), unit.map(str::to_string).into(), task_id.into()],
+        ))
+        .await
+        .unwrap();
+    }
+
+    // 记录一段已结束的计时
+    async fn track(db: &DatabaseConnection, task_id: &str, user_id: &str, hours: i64) {
+        let ended_at = Utc::now();
+        db.execute(sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            "INSERT INTO time_entries (id, task_id, user_id, started_at, ended_at, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
+            [
+                uuid::Uuid::new_v4().to_string().into(),
+                task_id.into(),
+                user_id.into(),
+                (ended_at - Duration::hours(hours)).into(),
+                ended_at.into(),
+                ended_at.into(),
+                ended_at.into(),
+            ],
+        ))
+        .await
+        .unwrap();
+    }
+
+    async fn workload(db: &DatabaseConnection, user_id: &str, executor_id: Option<&str>) -> Result<WorkloadReportResponse, StatusCode> {
+        let query = WorkloadQuery {
+            user_id: user_id.to_string(),
+            executor_id: executor_id.map(str::to_string),
+            period: None,
+            periods: None,
+        };
+        get_workload_report(State(db.clone()), Query(query)).await.map(|Json(report)| report)
+    }
+
+    // 创建者只看到自己看板上的任务，执行者看到自己参与的所有看板
+    #[tokio::test]
+    async fn workload_is_scoped_by_viewer_role() {
+        let db = test_db().await;
+        let alice = create_user(&db, "alice", "creator").await;
+        let bob = create_user(&db, "bob", "creator").await;
+        let carol = create_user(&db, "carol", "executor").await;
+        let dave = create_user(&db, "dave", "executor").await;
+
+        let alice_board = create_board(&db, &alice, Some(&carol)).await;
+        let bob_board = create_board(&db, &bob, Some(&carol)).await;
+        let dave_board = create_board(&db, &bob, Some(&dave)).await;
+
+        create_task(&db, &alice_board, &alice, None).await;
+        let done = create_task(&db, &alice_board, &alice, Some(&carol)).await;
+        complete(&db, &done.id, 5).await;
+        create_task(&db, &bob_board, &bob, Some(&carol)).await;
+        create_task(&db, &dave_board, &bob, Some(&dave)).await;
+
+        // 创建者看不到别人看板上同一执行者的任务
+        let report = workload(&db, &alice, None).await.unwrap();
+        assert_eq!(report.executors.len(), 1);
+        let executor = &report.executors[0];
+        assert_eq!(executor.executor_id, carol);
+        assert_eq!(executor.username.as_deref(), Some("carol"));
+        assert_eq!((executor.open, executor.completed), (1, 1));
+        assert_eq!(executor.average_completion_hours, Some(5.0));
+        assert_eq!(executor.boards.len(), 1);
+
+        // 执行者看到自己在两个创建者看板上的任务
+        let report = workload(&db, &carol, None).await.unwrap();
+        assert_eq!(report.executors.len(), 1);
+        assert_eq!((report.executors[0].open, report.executors[0].completed), (2, 1));
+        let mut boards: Vec<&str> = report.executors[0].boards.iter().map(|board| board.invite_id.as_str()).collect();
+        boards.sort();
+        let mut expected = vec![alice_board.as_str(), bob_board.as_str()];
+        expected.sort();
+        assert_eq!(boards, expected);
+
+        // 创建者可以按执行者筛选，执行者不能查看其他执行者
+        let report = workload(&db, &bob, Some(&dave)).await.unwrap();
+        assert_eq!(report.executors.len(), 1);
+        assert_eq!(report.executors[0].executor_id, dave);
+        assert_eq!(report.executors[0].open, 1);
+        assert_eq!(workload(&db, &carol, Some(&dave)).await.err(), Some(StatusCode::FORBIDDEN));
+
+        // 没有任务的执行者在筛选时也返回空统计
+        let report = workload(&db, &alice, Some(&dave)).await.unwrap();
+        assert_eq!(report.executors.len(), 1);
+        assert_eq!((report.executors[0].open, report.executors[0].completed), (0, 0));
+    }
+
+    // 小时和点数分开汇总，未设置单位按小时计，未完成和没有预估的任务不计入
+    #[tokio::test]
+    async fn estimates_are_totalled_per_unit() {
+        let db = test_db().await;
+        let creator_id = create_user(&db, "creator", "creator").await;
+        let executor_id = create_user(&db, "executor", "executor").await;
+        let invite_id = create_board(&db, &creator_id, Some(&executor_id)).await;
+
+        let hours = create_task(&db, &invite_id, &creator_id, None).await;
+        set_estimate(&db, &hours.id, 4.0, Some("hours")).await;
+        track(&db, &hours.id, &executor_id, 2).await;
+        complete(&db, &hours.id, 8).await;
+
+        let default_unit = create_task(&db, &invite_id, &creator_id, None).await;
+        set_estimate(&db, &default_unit.id, 2.0, None).await;
+        track(&db, &default_unit.id, &executor_id, 3).await;
+        complete(&db, &default_unit.id, 2).await;
+
+        let points = create_task(&db, &invite_id, &creator_id, None).await;
+        set_estimate(&db, &points.id, 3.0, Some("points")).await;
+        track(&db, &points.id, &executor_id, 6).await;
+        complete(&db, &points.id, 12).await;
+
+        let unestimated = create_task(&db, &invite_id, &creator_id, None).await;
+        complete(&db, &unestimated.id, 1).await;
+
+        let open = create_task(&db, &invite_id, &creator_id, None).await;
+        set_estimate(&db, &open.id, 10.0, Some("points")).await;
+
+        let query = EstimateReportQuery {
+            user_id: creator_id.clone(),
+            executor_id: None,
+            invite_id: None,
+            since: None,
+        };
+        let Json(report) = get_estimate_report(State(db.clone()), Query(query)).await.unwrap();
+        assert_eq!(report.unestimated_completed, 1);
+        assert_eq!(report.executors.len(), 1);
+
+        let hours = &report.executors[0].hours;
+        assert_eq!(hours.tasks, 2);
+        assert_eq!((hours.estimated_hours, hours.tracked_hours, hours.cycle_hours), (6.0, 5.0, 10.0));
+        assert_eq!((hours.tracked_ratio, hours.cycle_ratio), (Some(0.83), Some(1.67)));
+
+        let points = &report.executors[0].points;
+        assert_eq!(points.tasks, 1);
+        assert_eq!((points.points, points.tracked_hours, points.cycle_hours), (3.0, 6.0, 12.0));
+        assert_eq!((points.tracked_hours_per_point, points.cycle_hours_per_point), (Some(2.0), Some(4.0)));
+    }
+}
@@ -0,0 +1,306 @@
+use axum::{extract::{Path, State}, http::StatusCode, Json};
+use chrono::Utc;
+use sea_orm::{ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set, TransactionTrait};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::config::Config;
+use crate::events::{self, Event};
+use crate::handlers::column::{check_wip_limit, load_workflow, MoveError, REVIEW_STATUS};
+use crate::handlers::task::{ensure_task_writable, record_status_change, TaskResponse};
+use crate::handlers::time_entry::stop_running_timers;
+use crate::models::{
+    invite, invite::Entity as Invite, task, task::Entity as Task, task_review, task_review::Entity as TaskReview,
+};
+
+#[derive(Deserialize)]
+pub struct ReviewTaskRequest {
+    pub creator_id: String,
+    pub decision: String, // "accepted" 或 "changes_requested"
+    pub comment: Option<String>,
+    #[serde(default)]
+    pub override_wip_limit: bool, // 越过目标列的WIP上限
+}
+
+#[derive(Serialize)]
+pub struct TaskReviewResponse {
+    pub id: String,
+    pub task_id: String,
+    pub reviewer_id: String,
+    pub decision: String,
+    pub comment: Option<String>,
+    pub completion_details: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Serialize)]
+pub struct TaskReviewListResponse {
+    pub reviews: Vec<TaskReviewResponse>,
+}
+
+#[derive(Serialize)]
+pub struct ReviewTaskResponse {
+    pub task: TaskResponse,
+    pub review: TaskReviewResponse,
+}
+
+impl From<task_review::Model> for TaskReviewResponse {
+    fn from(review: task_review::Model) -> Self {
+        TaskReviewResponse {
+            id: review.id,
+            task_id: review.task_id,
+            reviewer_id: review.reviewer_id,
+            decision: review.decision,
+            comment: review.comment,
+            completion_details: review.completion_details,
+            created_at: review.created_at.to_rfc3339(),
+        }
+    }
+}
+
+// 验收任务接口：通过则移到第一个完成列，要求修改则退回第一个进行中列，提交的完成详情保留在验收记录中
+pub async fn review_task(
+    State(db): State<DatabaseConnection>,
+    State(config): State<Arc<Config>>,
+    Path(task_id): Path<String>,
+    Json(payload): Json<ReviewTaskRequest>,
+) -> Result<Json<ReviewTaskResponse>, MoveError> {
+    let task = Task::find_by_id(&task_id)
+        .filter(task::Column::DeletedAt.is_null())
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    // 只有看板创建者可以验收
+    Invite::find()
+        .filter(invite::Column::Id.eq(&task.invite_id))
+        .filter(invite::Column::CreatorId.eq(&payload.creator_id))
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    // 已归档的任务和归档看板上的任务不能验收
+    ensure_task_writable(&db, &task).await?;
+
+    if task.status != REVIEW_STATUS {
+        return Err(StatusCode::CONFLICT.into());
+    }
+
+    // 要求修改时必须说明原因
+    let comment = payload.comment.map(|comment| comment.trim().to_string()).filter(|comment| !comment.is_empty());
+    let workflow = load_workflow(&db, &task.invite_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let (new_status, new_category) = match payload.decision.as_str() {
+        "accepted" => (workflow.first_in("finished").ok_or(StatusCode::CONFLICT)?, "finished"),
+        "changes_requested" if comment.is_some() => match workflow.first_in("active") {
+            Some(status) => (status, "active"),
+            None => (workflow.initial(), "not_started"),
+        },
+        _ => return Err(StatusCode::BAD_REQUEST.into()),
+    };
+    // 验收人就是看板创建者，可以显式越过WIP上限
+    if !payload.override_wip_limit {
+        check_wip_limit(&db, &workflow, &task.invite_id, new_status).await?;
+    }
+
+    let review_id = uuid::Uuid::new_v4().to_string();
+    let now = Utc::now();
+    let old_status = task.status.clone();
+
+    let txn = db.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    txn.execute(
+        sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            r#"
+            INSERT INTO task_reviews (id, task_id, reviewer_id, decision, comment, completion_details, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+            [
+                review_id.clone().into(),
+                task_id.clone().into(),
+                payload.creator_id.clone().into(),
+                payload.decision.into(),
+                comment.into(),
+                task.completion_details.clone().into(),
+                now.into(),
+            ]
+        )
+    ).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let mut task_active: task::ActiveModel = task.into();
+    task_active.status = Set(new_status.to_string());
+    task_active.status_category = Set(new_category.to_string());
+    if new_category == "finished" {
+        task_active.completed_at = Set(Some(now.into()));
+    }
+    task_active.updated_at = Set(now.into());
+    let updated_task = task_active
+        .update(&txn)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if new_category == "finished" {
+        stop_running_timers(&txn, &task_id, now)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+    record_status_change(&txn, &task_id, Some(&old_status), new_status, new_category, Some(&payload.creator_id), now)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    txn.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let review = TaskReview::find_by_id(&review_id)
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    events::publish(&db, &config, Event::TaskStatusChanged {
+        task: updated_task.clone(),
+        old_status,
+        actor_id: Some(payload.creator_id.clone()),
+    }).await;
+    events::publish(&db, &config, Event::TaskReviewed {
+        task: updated_task.clone(),
+        review: review.clone(),
+        actor_id: Some(payload.creator_id),
+    }).await;
+
+    Ok(Json(ReviewTaskResponse {
+        task: updated_task.into(),
+        review: review.into(),
+    }))
+}
+
+// 获取任务的验收记录，按时间排序
+pub async fn get_task_reviews(
+    State(db): State<DatabaseConnection>,
+    Path(task_id): Path<String>,
+) -> Result<Json<TaskReviewListResponse>, StatusCode> {
+    Task::find_by_id(&task_id)
+        .filter(task::Column::DeletedAt.is_null())
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let reviews = TaskReview::find()
+        .filter(task_review::Column::TaskId.eq(&task_id))
+        .order_by_asc(task_review::Column::CreatedAt)
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(TaskReviewListResponse {
+        reviews: reviews.into_iter().map(Into::into).collect(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    use crate::handlers::task::{update_task, update_task_status};
+    use crate::models::{notification, notification::Entity as Notification};
+    use crate::test_util::{create_board, create_task, create_user, test_config, test_db};
+
+    // 执行者通过任意接口移到完成列都进入待验收，创建者验收后执行者收到验收通知
+    #[tokio::test]
+    async fn executor_moves_go_through_review() {
+        let db = test_db().await;
+        let config = Arc::new(test_config());
+        let creator_id = create_user(&db, "creator", "creator").await;
+        let executor_id = create_user(&db, "executor", "executor").await;
+        let invite_id = create_board(&db, &creator_id, Some(&executor_id)).await;
+        let task = create_task(&db, &invite_id, &creator_id, Some(&executor_id)).await;
+
+
+        let Json(moved) = update_task(
+            State(db.clone()),
+            State(config.clone()),
+            Path(task.id.clone()),
+            Json(serde_json::from_value(json!({ "status": "done", "user_id": executor_id })).unwrap()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(moved.status, REVIEW_STATUS);
+        assert!(moved.completed_at.is_none());
+
+        let Json(reviewed) = review_task(
+            State(db.clone()),
+            State(config.clone()),
+            Path(task.id.clone()),
+            Json(serde_json::from_value(json!({ "creator_id": creator_id, "decision": "accepted" })).unwrap()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(reviewed.task.status, "done");
+
+        let notifications = Notification::find()
+            .filter(notification::Column::UserId.eq(&executor_id))
+            .filter(notification::Column::Kind.eq("task_reviewed"))
+            .all(&db)
+            .await
+            .unwrap();
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].actor_id.as_deref(), Some(creator_id.as_str()));
+        assert_eq!(notifications[0].task_id.as_deref(), Some(task.id.as_str()));
+    }
+
+    // 没有提供操作者时（如前端的完成按钮）按执行者处理，移到完成列进入待验收
+    #[tokio::test]
+    async fn moves_without_actor_go_to_review() {
+        let db = test_db().await;
+        let config = Arc::new(test_config());
+        let creator_id = create_user(&db, "creator", "creator").await;
+        let executor_id = create_user(&db, "executor", "executor").await;
+        let invite_id = create_board(&db, &creator_id, Some(&executor_id)).await;
+        let task = create_task(&db, &invite_id, &creator_id, Some(&executor_id)).await;
+
+        let Json(moved) = update_task_status(
+            State(db.clone()),
+            State(config.clone()),
+            Path(task.id.clone()),
+            Json(serde_json::from_value(json!({ "status": "done", "completion_details": "已完成" })).unwrap()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(moved.status, REVIEW_STATUS);
+        assert_eq!(moved.completion_details.as_deref(), Some("已完成"));
+        assert!(moved.completed_at.is_none());
+    }
+
+    // 已归档的待验收任务不能验收
+    #[tokio::test]
+    async fn archived_tasks_cannot_be_reviewed() {
+        let db = test_db().await;
+        let config = Arc::new(test_config());
+        let creator_id = create_user(&db, "creator", "creator").await;
+        let executor_id = create_user(&db, "executor", "executor").await;
+        let invite_id = create_board(&db, &creator_id, Some(&executor_id)).await;
+        let task = create_task(&db, &invite_id, &creator_id, Some(&executor_id)).await;
+        let mut task_active: task::ActiveModel = task.into();
+        task_active.status = Set(REVIEW_STATUS.to_string());
+        task_active.status_category = Set("active".to_string());
+        task_active.archived_at = Set(Some(Utc::now().into()));
+        let task = task_active.update(&db).await.unwrap();
+
+        let result = review_task(
+            State(db.clone()),
+            State(config),
+            Path(task.id.clone()),
+            Json(serde_json::from_value(json!({ "creator_id": creator_id, "decision": "accepted" })).unwrap()),
+        )
+        .await;
+        assert!(matches!(result, Err(MoveError::Status(StatusCode::CONFLICT))));
+
+        let task = Task::find_by_id(&task.id).one(&db).await.unwrap().unwrap();
+        assert_eq!(task.status, REVIEW_STATUS);
+        assert!(TaskReview::find().all(&db).await.unwrap().is_empty());
+    }
+}
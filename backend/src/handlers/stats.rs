@@ -0,0 +1,371 @@
+use axum::{extract::{Path, Query, State}, http::StatusCode, Json};
+use chrono::{Datelike, Duration, NaiveDate, TimeZone, Utc};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::models::{invite::Entity as Invite, task, task::Entity as Task, task_status_history, task_status_history::Entity as TaskStatusHistory};
+
+type Timestamp = chrono::DateTime<Utc>;
+
+#[derive(Deserialize)]
+pub struct StatsQuery {
+    pub weeks: Option<i64>, // 吞吐量统计的周数，默认12
+    pub days: Option<i64>,  // 累积流图的天数，默认30
+}
+
+// 按列分类统计任务数，by_status 为每一列的任务数
+#[derive(Serialize, Default)]
+pub struct StatusCounts {
+    pub not_started: usize,
+    pub active: usize,
+    pub finished: usize,
+    pub total: usize,
+    pub by_status: BTreeMap<String, usize>,
+}
+
+#[derive(Serialize)]
+pub struct WeeklyThroughput {
+    pub week_start: String, // 周一（UTC）
+    pub completed: usize,
+}
+
+#[derive(Serialize)]
+pub struct DurationStats {
+    pub count: usize,
+    pub average_hours: Option<f64>,
+    pub p50_hours: Option<f64>,
+    pub p85_hours: Option<f64>,
+    pub p95_hours: Option<f64>,
+}
+
+#[derive(Serialize)]
+pub struct CumulativeFlowPoint {
+    pub date: String,
+    pub not_started: usize,
+    pub active: usize,
+    pub finished: usize,
+    pub by_status: BTreeMap<String, usize>,
+}
+
+#[derive(Serialize)]
+pub struct BoardStatsResponse {
+    pub invite_id: String,
+    pub counts: StatusCounts,
+    pub throughput: Vec<WeeklyThroughput>,
+    pub cycle_time: DurationStats,
+    pub lead_time: DurationStats,
+    pub cumulative_flow: Vec<CumulativeFlowPoint>,
+}
+
+impl StatusCounts {
+    fn add(&mut self, status: &str, category: &str) {
+        match category {
+            "not_started" => self.not_started += 1,
+            "active" => self.active += 1,
+            "finished" => self.finished += 1,
+            _ => {}
+        }
+        *self.by_status.entry(status.to_string()).or_insert(0) += 1;
+        self.total += 1;
+    }
+}
+
+pub fn round_hours(hours: f64) -> f64 {
+    (hours * 100.0).round() / 100.0
+}
+
+// 按最近排名法计算百分位，输入需已排序
+fn percentile(sorted: &[f64], p: f64) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+fn duration_stats(mut hours: Vec<f64>) -> DurationStats {
+    hours.sort_by(|a, b| a.total_cmp(b));
+    let average = (!hours.is_empty()).then(|| hours.iter().sum::<f64>() / hours.len() as f64);
+    DurationStats {
+        count: hours.len(),
+        average_hours: average.map(round_hours),
+        p50_hours: percentile(&hours, 50.0).map(round_hours),
+        p85_hours: percentile(&hours, 85.0).map(round_hours),
+        p95_hours: percentile(&hours, 95.0).map(round_hours),
+    }
+}
+
+pub fn hours_between(start: Timestamp, end: Timestamp) -> f64 {
+    (end - start).num_seconds() as f64 / 3600.0
+}
+
+pub fn start_of_day(date: NaiveDate) -> Timestamp {
+    Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).expect("零点总是有效时间"))
+}
+
+// 任务的完成时间：优先使用completed_at，没有时取最后一次进入完成列的时间
+pub fn completion_time(task: &task::Model, history: &[task_status_history::Model]) -> Option<Timestamp> {
+    task.completed_at
+        .or_else(|| history.iter().rev().find(|h| h.to_category == "finished").map(|h| h.changed_at))
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+// 任务的开始时间：第一次进入进行中列的时间
+pub fn start_time(history: &[task_status_history::Model]) -> Option<Timestamp> {
+    history
+        .iter()
+        .find(|h| h.to_category == "active")
+        .map(|h| h.changed_at.with_timezone(&Utc))
+}
+
+// 任务在某一时刻的状态和分类，根据状态变更历史回放；任务尚未创建时返回None
+pub fn status_at<'a>(task: &'a task::Model, history: &'a [task_status_history::Model], at: Timestamp) -> Option<(&'a str, &'a str)> {
+    if task.created_at.with_timezone(&Utc) > at {
+        return None;
+    }
+    let status = match history.iter().rev().find(|h| h.changed_at.with_timezone(&Utc) <= at) {
+        Some(entry) => (entry.to_status.as_str(), entry.to_category.as_str()),
+        // 没有历史记录的任务按完成时间判断
+        None if task.completed_at.is_some_and(|dt| dt.with_timezone(&Utc) <= at) => ("done", "finished"),
+        None => ("todo", "not_started"),
+    };
+    Some(status)
+}
+
+// 看板统计：各列和分类的任务数量、每周吞吐量、周期时间、前置时间和累积流
+pub async fn get_board_stats(
+    State(db): State<DatabaseConnection>,
+    Path(invite_id): Path<String>,
+    Query(query): Query<StatsQuery>,
+) -> Result<Json<BoardStatsResponse>, StatusCode> {
+    let weeks = query.weeks.unwrap_or(12);
+    let days = query.days.unwrap_or(30);
+    if !(1..=104).contains(&weeks) || !(1..=365).contains(&days) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    Invite::find_by_id(&invite_id)
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let tasks = Task::find()
+        .filter(task::Column::DeletedAt.is_null())
+        .filter(task::Column::InviteId.eq(&invite_id))
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let task_ids: Vec<String> = tasks.iter().map(|task| task.id.clone()).collect();
+    let mut history: HashMap<String, Vec<task_status_history::Model>> = HashMap::new();
+    for entry in TaskStatusHistory::find()
+        .filter(task_status_history::Column::TaskId.is_in(task_ids))
+        .order_by_asc(task_status_history::Column::ChangedAt)
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    {
+        history.entry(entry.task_id.clone()).or_default().push(entry);
+    }
+
+    let now = Utc::now();
+    let today = now.date_naive();
+    let empty = Vec::new();
+
+    // 当前各列和分类的任务数量
+    let mut counts = StatusCounts::default();
+    for task in &tasks {
+        counts.add(&task.status, &task.status_category);
+    }
+
+    // 每周吞吐量，按当前已完成任务的完成时间所在周统计
+    let current_week = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+    let first_week = current_week - Duration::weeks(weeks - 1);
+    let mut throughput: Vec<WeeklyThroughput> = (0..weeks)
+        .map(|i| WeeklyThroughput {
+            week_start: (first_week + Duration::weeks(i)).to_string(),
+            completed: 0,
+        })
+        .collect();
+
+    let mut cycle_hours = Vec::new();
+    let mut lead_hours = Vec::new();
+    for task in tasks.iter().filter(|task| task.status_category == "finished") {
+        let task_history = history.get(&task.id).unwrap_or(&empty);
+        let Some(completed_at) = completion_time(task, task_history) else { continue };
+
+        let week_index = (completed_at.date_naive() - first_week).num_days().div_euclid(7);
+        if (0..weeks).contains(&week_index) {
+            throughput[week_index as usize].completed += 1;
+        }
+
+        // 前置时间：创建到完成
+        let created_at = task.created_at.with_timezone(&Utc);
+        if completed_at >= created_at {
+            lead_hours.push(hours_between(created_at, completed_at));
+        }
+
+        // 周期时间：第一次进入进行中列到完成，没有经过进行中列的任务不计入
+        if let Some(started_at) = start_time(task_history).filter(|started_at| *started_at <= completed_at) {
+            cycle_hours.push(hours_between(started_at, completed_at));
+        }
+    }
+
+    // 累积流：每天结束时（当天为当前时刻）各状态的任务数
+    let cumulative_flow = (0..days)
+        .rev()
+        .map(|offset| {
+            let date = today - Duration::days(offset);
+            let at = if offset == 0 { now } else { start_of_day(date + Duration::days(1)) };
+
+            let mut point = StatusCounts::default();
+            for task in &tasks {
+                if let Some((status, category)) = status_at(task, history.get(&task.id).unwrap_or(&empty), at) {
+                    point.add(status, category);
+                }
+            }
+
+            CumulativeFlowPoint {
+                date: date.to_string(),
+                not_started: point.not_started,
+                active: point.active,
+                finished: point.finished,
+                by_status: point.by_status,
+            }
+        })
+        .collect();
+
+    Ok(Json(BoardStatsResponse {
+        invite_id,
+        counts,
+        throughput,
+        cycle_time: duration_stats(cycle_hours),
+        lead_time: duration_stats(lead_hours),
+        cumulative_flow,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::ConnectionTrait;
+
+    use crate::handlers::task::record_status_change;
+    use crate::test_util::{create_board, create_task, create_user, test_db};
+
+    fn history_entry(task_id: &str, to_status: &str, to_category: &str, changed_at: Timestamp) -> task_status_history::Model {
+        task_status_history::Model {
+            id: uuid::Uuid::new_v4().to_string(),
+            task_id: task_id.to_string(),
+            from_status: None,
+            to_status: to_status.to_string(),
+            to_category: to_category.to_string(),
+            actor_id: None,
+            changed_at: changed_at.into(),
+        }
+    }
+
+    #[test]
+    fn percentiles_use_nearest_rank() {
+        assert_eq!(percentile(&[], 50.0), None);
+        assert_eq!(percentile(&[3.0], 50.0), Some(3.0));
+        assert_eq!(percentile(&[3.0], 95.0), Some(3.0));
+
+        let hours: Vec<f64> = (1..=20).map(f64::from).collect();
+        assert_eq!(percentile(&hours, 50.0), Some(10.0));
+        assert_eq!(percentile(&hours, 85.0), Some(17.0));
+        assert_eq!(percentile(&hours, 95.0), Some(19.0));
+        assert_eq!(percentile(&hours, 0.0), Some(1.0));
+
+        let empty = duration_stats(Vec::new());
+        assert_eq!(empty.count, 0);
+        assert_eq!(empty.average_hours, None);
+        assert_eq!(empty.p50_hours, None);
+
+        let single = duration_stats(vec![1.0 / 3.0]);
+        assert_eq!(single.count, 1);
+        assert_eq!(single.average_hours, Some(0.33));
+        assert_eq!(single.p95_hours, Some(0.33));
+    }
+
+    #[tokio::test]
+    async fn status_at_replays_history() {
+        let db = test_db().await;
+        let creator_id = create_user(&db, "creator", "creator").await;
+        let invite_id = create_board(&db, &creator_id, None).await;
+
+        let midnight = start_of_day(NaiveDate::from_ymd_opt(2024, 3, 2).unwrap());
+        let mut task = create_task(&db, &invite_id, &creator_id, None).await;
+        task.created_at = (midnight - Duration::days(1)).into();
+
+        // 创建前没有状态；没有历史记录时按未开始处理
+        assert_eq!(status_at(&task, &[], midnight - Duration::days(2)), None);
+        assert_eq!(status_at(&task, &[], midnight), Some(("todo", "not_started")));
+
+        // 零点的变更计入前一天结束时的状态
+        let history = vec![
+            history_entry(&task.id, "todo", "not_started", midnight - Duration::days(1)),
+            history_entry(&task.id, "in_progress", "active", midnight),
+        ];
+        assert_eq!(status_at(&task, &history, midnight - Duration::seconds(1)), Some(("todo", "not_started")));
+        assert_eq!(status_at(&task, &history, midnight), Some(("in_progress", "active")));
+
+        // 没有历史记录的已完成任务按完成时间判断
+        task.completed_at = Some(midnight.into());
+        assert_eq!(status_at(&task, &[], midnight - Duration::seconds(1)), Some(("todo", "not_started")));
+        assert_eq!(status_at(&task, &[], midnight), Some(("done", "finished")));
+    }
+
+    #[tokio::test]
+    async fn cumulative_flow_counts_status_at_end_of_day() {
+        let db = test_db().await;
+        let creator_id = create_user(&db, "creator", "creator").await;
+        let invite_id = create_board(&db, &creator_id, None).await;
+
+        let stats = |days| {
+            get_board_stats(
+                State(db.clone()),
+                Path(invite_id.clone()),
+                Query(StatsQuery { weeks: None, days: Some(days) }),
+            )
+        };
+
+        // 空看板每天都是0
+        let Json(empty) = stats(3).await.unwrap();
+        assert_eq!(empty.cumulative_flow.len(), 3);
+        assert!(empty.cumulative_flow.iter().all(|point| point.not_started + point.active + point.finished == 0));
+        assert_eq!(empty.cycle_time.count, 0);
+
+        // 任务三天前创建，今天零点开始处理
+        let task = create_task(&db, &invite_id, &creator_id, None).await;
+        let today = Utc::now().date_naive();
+        let created_at = start_of_day(today) - Duration::days(3);
+        db.execute(sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            "UPDATE tasks SET created_at = ? WHERE id = ?",
+            [created_at.into(), task.id.clone().into()],
+        ))
+        .await
+        .unwrap();
+        db.execute(sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            "UPDATE task_status_history SET changed_at = ? WHERE task_id = ?",
+            [created_at.into(), task.id.clone().into()],
+        ))
+        .await
+        .unwrap();
+        record_status_change(&db, &task.id, Some("todo"), "in_progress", "active", None, start_of_day(today))
+            .await
+            .unwrap();
+
+        let Json(flow) = stats(4).await.unwrap();
+        let active: Vec<usize> = flow.cumulative_flow.iter().map(|point| point.active).collect();
+        let not_started: Vec<usize> = flow.cumulative_flow.iter().map(|point| point.not_started).collect();
+        assert_eq!(flow.cumulative_flow[3].date, today.to_string());
+        assert_eq!(not_started, [1, 1, 0, 0]);
+        assert_eq!(active, [0, 0, 1, 1]);
+        assert_eq!(flow.cumulative_flow[0].by_status.get("todo"), Some(&1));
+    }
+}
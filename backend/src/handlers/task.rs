@@ -1,8 +1,19 @@
-use axum::{extract::{Path, State}, http::StatusCode, Json};
-use sea_orm::{ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, ModelTrait, QueryFilter, Set, TransactionTrait};
+use axum::{extract::{Path, Query, State}, http::StatusCode, Json};
+use sea_orm::{ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder, Set, TransactionTrait};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
 use chrono;
 
+use crate::config::Config;
+use crate::events::{self, Event};
+use crate::handlers::archive::ensure_board_writable;
+use crate::handlers::column::{check_override, check_wip_limit, load_workflow, MoveError, REVIEW_STATUS};
+use crate::handlers::completion::record_submission;
+use crate::handlers::custom_field::{self, board_fields, merge_values, parse_values};
+use crate::handlers::mention::{sync_mentions, MentionSource};
+use crate::handlers::time_entry::stop_running_timers;
 use crate::models::{task, task::Entity as Task, invite, invite::Entity as Invite};
 
 #[derive(Deserialize)]
@@ -11,20 +22,34 @@ pub struct CreateTaskRequest {
     pub description: Option<String>,
     pub creator_id: String,
     pub invite_id: String,
+    pub due_date: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub priority: Option<String>, // "low", "medium", "high"
+    pub custom_fields: Option<Map<String, Value>>, // 以字段ID为键
 }
 
 #[derive(Deserialize)]
 pub struct UpdateTaskRequest {
     pub title: Option<String>,
     pub description: Option<String>,
-    pub status: Option<String>,
+    pub status: Option<String>, // 看板列的键
     pub executor_id: Option<String>,
+    pub due_date: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub priority: Option<String>,
+    pub estimate: Option<f64>,
+    pub estimate_unit: Option<String>, // "points" 或 "hours"，首次预估时默认为 "hours"
+    pub custom_fields: Option<Map<String, Value>>, // 只修改传入的字段，值为null表示清除
+    pub user_id: Option<String>, // 操作者ID，用于通知
+    #[serde(default)]
+    pub override_wip_limit: bool, // 看板创建者越过WIP上限
 }
 
 #[derive(Deserialize)]
 pub struct UpdateTaskStatusRequest {
-    pub status: String, // "todo", "in_progress", "done"
+    pub status: String, // 看板列的键，默认为 "todo", "in_progress", "in_review", "done"
     pub completion_details: Option<String>,
+    pub user_id: Option<String>, // 操作者ID，用于通知
+    #[serde(default)]
+    pub override_wip_limit: bool, // 看板创建者越过WIP上限
 }
 
 #[derive(Serialize)]
@@ -33,11 +58,19 @@ pub struct TaskResponse {
     pub title: String,
     pub description: Option<String>,
     pub status: String,
+    pub status_category: String,
     pub creator_id: String,
     pub executor_id: Option<String>,
     pub invite_id: String,
     pub completion_details: Option<String>,
     pub completed_at: Option<String>,
+    pub due_date: Option<String>,
+    pub priority: Option<String>,
+    pub estimate: Option<f64>,
+    pub estimate_unit: Option<String>,
+    pub custom_fields: Map<String, Value>,
+    pub deleted_at: Option<String>,
+    pub archived_at: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -47,49 +80,184 @@ pub struct TaskListResponse {
     pub tasks: Vec<TaskResponse>,
 }
 
-// 创建任务接口
-pub async fn create_task(
-    State(db): State<DatabaseConnection>,
-    Json(payload): Json<CreateTaskRequest>,
-) -> Result<Json<TaskResponse>, StatusCode> {
-    // 验证邀请码是否存在且创建者有权限（不检查邀请码状态，允许使用已使用的邀请码创建任务）
-    let _invite = Invite::find()
-        .filter(invite::Column::Id.eq(&payload.invite_id))
-        .filter(invite::Column::CreatorId.eq(&payload.creator_id))
-        .one(&db)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-        .ok_or(StatusCode::BAD_REQUEST)?;
+#[derive(Serialize)]
+pub struct TrashedTaskResponse {
+    #[serde(flatten)]
+    pub task: TaskResponse,
+    pub purge_at: Option<String>, // 彻底删除的时间，不自动清理时为空
+}
 
-    // 使用事务创建任务，避免SeaORM insert方法的栈溢出问题
-    let txn = db.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+#[derive(Serialize)]
+pub struct TrashListResponse {
+    pub tasks: Vec<TrashedTaskResponse>,
+}
+
+impl From<task::Model> for TaskResponse {
+    fn from(task: task::Model) -> Self {
+        TaskResponse {
+            id: task.id,
+            title: task.title,
+            description: task.description,
+            status: task.status,
+            status_category: task.status_category,
+            creator_id: task.creator_id,
+            executor_id: task.executor_id,
+            invite_id: task.invite_id,
+            completion_details: task.completion_details,
+            completed_at: task.completed_at.map(|dt| dt.to_rfc3339()),
+            due_date: task.due_date.map(|dt| dt.to_rfc3339()),
+            priority: task.priority,
+            estimate: task.estimate,
+            estimate_unit: task.estimate_unit,
+            custom_fields: parse_values(task.custom_fields.as_deref()),
+            deleted_at: task.deleted_at.map(|dt| dt.to_rfc3339()),
+            archived_at: task.archived_at.map(|dt| dt.to_rfc3339()),
+            created_at: task.created_at.to_rfc3339(),
+            updated_at: task.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+pub const PRIORITIES: [&str; 3] = ["low", "medium", "high"];
+pub const ESTIMATE_UNITS: [&str; 2] = ["points", "hours"];
 
+// 待创建的任务，创建任务接口和批量导入共用
+pub struct NewTask {
+    pub title: String,
+    pub description: Option<String>,
+    pub creator_id: String,
+    pub invite_id: String,
+    pub due_date: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub priority: Option<String>,
+    pub custom_fields: Option<String>, // 已校验的自定义字段值JSON
+}
+
+impl NewTask {
+    // 校验任务字段，返回错误描述
+    pub fn validate(&self) -> Result<(), String> {
+        if self.title.trim().is_empty() {
+            return Err("标题不能为空".to_string());
+        }
+        if self.priority.as_ref().is_some_and(|p| !PRIORITIES.contains(&p.as_str())) {
+            return Err("优先级只能是low、medium或high".to_string());
+        }
+        Ok(())
+    }
+}
+
+// 使用原生SQL插入任务，任务放在看板的第一个未开始列，返回任务ID
+pub async fn insert_task<C: ConnectionTrait>(conn: &C, new_task: NewTask) -> Result<String, DbErr> {
     let task_id = uuid::Uuid::new_v4().to_string();
     let now = chrono::Utc::now();
-    
-    // 使用原生SQL插入任务
-    txn.execute(
+    let workflow = load_workflow(conn, &new_task.invite_id).await?;
+    let status = workflow.initial().to_string();
+
+    conn.execute(
         sea_orm::Statement::from_sql_and_values(
             sea_orm::DatabaseBackend::Sqlite,
             r#"
-            INSERT INTO tasks (id, title, description, status, creator_id, executor_id, invite_id, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO tasks (id, title, description, status, status_category, creator_id, executor_id, invite_id, due_date, priority, custom_fields, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
             [
                 task_id.clone().into(),
-                payload.title.into(),
-                payload.description.into(),
-                "todo".into(),
-                payload.creator_id.into(),
+                new_task.title.into(),
+                new_task.description.into(),
+                status.clone().into(),
+                "not_started".into(),
+                new_task.creator_id.clone().into(),
                 sea_orm::Value::String(None),
-                payload.invite_id.into(),
+                new_task.invite_id.into(),
+                new_task.due_date.map(|dt| dt.with_timezone(&chrono::Utc)).into(),
+                new_task.priority.into(),
+                new_task.custom_fields.into(),
                 now.into(),
                 now.into(),
             ]
         )
-    ).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    ).await?;
+
+    record_status_change(conn, &task_id, None, &status, "not_started", Some(&new_task.creator_id), now).await?;
+
+    Ok(task_id)
+}
+
+// 已归档的任务需要先取消归档才能修改，所在看板也必须未归档
+pub async fn ensure_task_writable(db: &DatabaseConnection, task: &task::Model) -> Result<invite::Model, StatusCode> {
+    if task.archived_at.is_some() {
+        return Err(StatusCode::CONFLICT);
+    }
+    ensure_board_writable(db, &task.invite_id).await
+}
+
+// 记录任务状态变更历史，同时记录目标列的分类
+pub async fn record_status_change<C: ConnectionTrait>(
+    conn: &C,
+    task_id: &str,
+    from_status: Option<&str>,
+    to_status: &str,
+    to_category: &str,
+    actor_id: Option<&str>,
+    changed_at: chrono::DateTime<chrono::Utc>,
+) -> Result<(), DbErr> {
+    conn.execute(
+        sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            r#"
+            INSERT INTO task_status_history (id, task_id, from_status, to_status, to_category, actor_id, changed_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+            [
+                uuid::Uuid::new_v4().to_string().into(),
+                task_id.into(),
+                from_status.map(str::to_string).into(),
+                to_status.into(),
+                to_category.into(),
+                actor_id.map(str::to_string).into(),
+                changed_at.into(),
+            ]
+        )
+    ).await?;
+
+    Ok(())
+}
+
+// 创建任务接口
+pub async fn create_task(
+    State(db): State<DatabaseConnection>,
+    State(config): State<Arc<Config>>,
+    Json(payload): Json<CreateTaskRequest>,
+) -> Result<Json<TaskResponse>, StatusCode> {
+    // 验证邀请码是否存在且创建者有权限（不检查邀请码状态，允许使用已使用的邀请码创建任务）
+    Invite::find()
+        .filter(invite::Column::Id.eq(&payload.invite_id))
+        .filter(invite::Column::CreatorId.eq(&payload.creator_id))
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::BAD_REQUEST)?;
+    ensure_board_writable(&db, &payload.invite_id).await?;
 
-    // 提交事务
+    let fields = board_fields(&db, &payload.invite_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let custom_fields = merge_values(&fields, None, payload.custom_fields.unwrap_or_default())
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+
+    let new_task = NewTask {
+        title: payload.title,
+        description: payload.description,
+        creator_id: payload.creator_id,
+        invite_id: payload.invite_id,
+        due_date: payload.due_date,
+        priority: payload.priority,
+        custom_fields,
+    };
+    new_task.validate().map_err(|_| StatusCode::BAD_REQUEST)?;
+
+    // 使用事务创建任务，避免SeaORM insert方法的栈溢出问题
+    let txn = db.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let task_id = insert_task(&txn, new_task).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     txn.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
     // 查询刚创建的任务
@@ -99,174 +267,446 @@ pub async fn create_task(
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
         .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    Ok(Json(TaskResponse {
-        id: task.id,
-        title: task.title,
-        description: task.description,
-        status: task.status,
-        creator_id: task.creator_id,
-        executor_id: task.executor_id,
-        invite_id: task.invite_id,
-        completion_details: task.completion_details,
-        completed_at: task.completed_at.map(|dt| dt.to_rfc3339()),
-        created_at: task.created_at.to_rfc3339(),
-        updated_at: task.updated_at.to_rfc3339(),
-    }))
+    events::publish(&db, &config, Event::TaskCreated {
+        task: task.clone(),
+        actor_id: Some(task.creator_id.clone()),
+    }).await;
+    sync_mentions(&db, &config, &task, MentionSource::Description, task.description.as_deref(), Some(&task.creator_id)).await;
+
+    Ok(Json(task.into()))
 }
 
-// 获取任务列表接口
+// 获取任务列表接口，支持按自定义字段过滤：cf.<字段ID或字段名>=值
 pub async fn get_tasks(
     State(db): State<DatabaseConnection>,
     Path(invite_id): Path<String>,
+    Query(query): Query<HashMap<String, String>>,
 ) -> Result<Json<TaskListResponse>, StatusCode> {
-    let tasks = Task::find()
+    // 默认不列出已归档的任务，archived=true时只列出已归档的任务
+    let archived = match query.get("archived").map(String::as_str) {
+        Some("true") => task::Column::ArchivedAt.is_not_null(),
+        None | Some("false") => task::Column::ArchivedAt.is_null(),
+        Some(_) => return Err(StatusCode::BAD_REQUEST),
+    };
+    let mut tasks = Task::find()
+        .filter(task::Column::DeletedAt.is_null())
         .filter(task::Column::InviteId.eq(&invite_id))
+        .filter(archived)
         .all(&db)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    let task_responses: Vec<TaskResponse> = tasks
-        .into_iter()
-        .map(|task| TaskResponse {
-            id: task.id,
-            title: task.title,
-            description: task.description,
-            status: task.status,
-            creator_id: task.creator_id,
-            executor_id: task.executor_id,
-            invite_id: task.invite_id,
-            completion_details: task.completion_details,
-            completed_at: task.completed_at.map(|dt| dt.to_rfc3339()),
-            created_at: task.created_at.to_rfc3339(),
-            updated_at: task.updated_at.to_rfc3339(),
-        })
+    let field_filters: Vec<(&str, &String)> = query
+        .iter()
+        .filter_map(|(key, value)| Some((key.strip_prefix("cf.")?, value)))
         .collect();
+    if !field_filters.is_empty() {
+        let fields = board_fields(&db, &invite_id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        let mut filters = Vec::new();
+        for (key, expected) in field_filters {
+            let field = fields
+                .get(key)
+                .or_else(|| fields.values().find(|field| field.name == key))
+                .ok_or(StatusCode::BAD_REQUEST)?;
+            filters.push((field, expected));
+        }
+        tasks.retain(|task| {
+            let values = parse_values(task.custom_fields.as_deref());
+            filters.iter().all(|(field, expected)| custom_field::matches(field, &values, expected))
+        });
+    }
 
     Ok(Json(TaskListResponse {
-        tasks: task_responses,
+        tasks: tasks.into_iter().map(Into::into).collect(),
     }))
 }
 
 // 更新任务接口
 pub async fn update_task(
     State(db): State<DatabaseConnection>,
+    State(config): State<Arc<Config>>,
     Path(task_id): Path<String>,
     Json(payload): Json<UpdateTaskRequest>,
-) -> Result<Json<TaskResponse>, StatusCode> {
+) -> Result<Json<TaskResponse>, MoveError> {
     // 查找任务
     let task = Task::find_by_id(&task_id)
+        .filter(task::Column::DeletedAt.is_null())
         .one(&db)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
         .ok_or(StatusCode::NOT_FOUND)?;
+    let invite = ensure_task_writable(&db, &task).await?;
+
+    let old_executor_id = task.executor_id.clone();
+    let has_estimate_unit = task.estimate_unit.is_some();
+    let custom_fields = match payload.custom_fields {
+        Some(changes) => {
+            let fields = board_fields(&db, &task.invite_id)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            Some(merge_values(&fields, task.custom_fields.as_deref(), changes).map_err(|_| StatusCode::BAD_REQUEST)?)
+        }
+        None => None,
+    };
+
+    let txn = db.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // 修改状态与更新任务状态接口一样按工作流移动，包括验收、WIP上限和完成时间
+    let (task, old_status) = match payload.status {
+        Some(status) if status != task.status => {
+            let request = UpdateTaskStatusRequest {
+                status,
+                completion_details: None,
+                user_id: payload.user_id.clone(),
+                override_wip_limit: payload.override_wip_limit,
+            };
+            move_task(&txn, task, &invite, request).await?
+        }
+        // 状态不变时只校验是否为看板上的列
+        Some(status) => {
+            let workflow = load_workflow(&txn, &task.invite_id)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            workflow.category(&status).ok_or(StatusCode::BAD_REQUEST)?;
+            let old_status = task.status.clone();
+            (task, old_status)
+        }
+        None => {
+            let old_status = task.status.clone();
+            (task, old_status)
+        }
+    };
 
     // 更新任务
+    let description_changed = payload.description.is_some();
     let mut task_active: task::ActiveModel = task.into();
-    
+
     if let Some(title) = payload.title {
         task_active.title = Set(title);
     }
     if let Some(description) = payload.description {
         task_active.description = Set(Some(description));
     }
-    if let Some(status) = payload.status {
-        // 验证状态值
-        if !["todo", "in_progress", "done"].contains(&status.as_str()) {
-            return Err(StatusCode::BAD_REQUEST);
-        }
-        task_active.status = Set(status);
-    }
     if let Some(executor_id) = payload.executor_id {
         task_active.executor_id = Set(Some(executor_id));
     }
-    
+    if let Some(due_date) = payload.due_date {
+        // 截止时间变更后重新计算逾期提醒
+        task_active.due_date = Set(Some(due_date.with_timezone(&chrono::Utc).into()));
+        task_active.due_soon_notified_at = Set(None);
+        task_active.overdue_notified_at = Set(None);
+    }
+    if let Some(priority) = payload.priority {
+        if !PRIORITIES.contains(&priority.as_str()) {
+            return Err(StatusCode::BAD_REQUEST.into());
+        }
+        task_active.priority = Set(Some(priority));
+    }
+    if let Some(estimate) = payload.estimate {
+        if !estimate.is_finite() || estimate < 0.0 {
+            return Err(StatusCode::BAD_REQUEST.into());
+        }
+        task_active.estimate = Set(Some(estimate));
+    }
+    if let Some(estimate_unit) = payload.estimate_unit {
+        if !ESTIMATE_UNITS.contains(&estimate_unit.as_str()) {
+            return Err(StatusCode::BAD_REQUEST.into());
+        }
+        task_active.estimate_unit = Set(Some(estimate_unit));
+    } else if payload.estimate.is_some() && !has_estimate_unit {
+        task_active.estimate_unit = Set(Some("hours".to_string()));
+    }
+    if let Some(custom_fields) = custom_fields {
+        task_active.custom_fields = Set(custom_fields);
+    }
+
     task_active.updated_at = Set(chrono::Utc::now().into());
 
     let updated_task = task_active
-        .update(&db)
+        .update(&txn)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    txn.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    Ok(Json(TaskResponse {
-        id: updated_task.id,
-        title: updated_task.title,
-        description: updated_task.description,
-        status: updated_task.status,
-        creator_id: updated_task.creator_id,
-        executor_id: updated_task.executor_id,
-        invite_id: updated_task.invite_id,
-        completion_details: updated_task.completion_details,
-        completed_at: updated_task.completed_at.map(|dt| dt.to_rfc3339()),
-        created_at: updated_task.created_at.to_rfc3339(),
-        updated_at: updated_task.updated_at.to_rfc3339(),
-    }))
+    if updated_task.executor_id.is_some() && updated_task.executor_id != old_executor_id {
+        events::publish(&db, &config, Event::TaskAssigned {
+            task: updated_task.clone(),
+            actor_id: payload.user_id.clone(),
+        }).await;
+    }
+    if updated_task.status != old_status {
+        events::publish(&db, &config, Event::TaskStatusChanged {
+            task: updated_task.clone(),
+            old_status,
+            actor_id: payload.user_id.clone(),
+        }).await;
+    }
+    if description_changed {
+        sync_mentions(&db, &config, &updated_task, MentionSource::Description, updated_task.description.as_deref(), payload.user_id.as_deref()).await;
+    }
+    events::publish(&db, &config, Event::TaskUpdated {
+        task: updated_task.clone(),
+        actor_id: payload.user_id,
+    }).await;
+
+    Ok(Json(updated_task.into()))
 }
 
 // 更新任务状态接口
 pub async fn update_task_status(
     State(db): State<DatabaseConnection>,
+    State(config): State<Arc<Config>>,
     Path(task_id): Path<String>,
     Json(payload): Json<UpdateTaskStatusRequest>,
-) -> Result<Json<TaskResponse>, StatusCode> {
-    // 验证状态值
-    if !["todo", "in_progress", "done"].contains(&payload.status.as_str()) {
-        return Err(StatusCode::BAD_REQUEST);
-    }
-
+) -> Result<Json<TaskResponse>, MoveError> {
     // 查找任务
     let task = Task::find_by_id(&task_id)
+        .filter(task::Column::DeletedAt.is_null())
         .one(&db)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
         .ok_or(StatusCode::NOT_FOUND)?;
 
+    let invite = ensure_task_writable(&db, &task).await?;
+    let actor_id = payload.user_id.clone();
+    let old_completion_details = task.completion_details.clone();
+    // 状态、完成详情、计时和状态历史在同一个事务中更新，WIP上限的计数也在事务内读取
+    let txn = db.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let (updated_task, old_status) = move_task(&txn, task, &invite, payload).await?;
+    txn.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // 提交了新的完成详情时同步其中提到的成员
+    if updated_task.completion_details != old_completion_details {
+        sync_mentions(&db, &config, &updated_task, MentionSource::CompletionDetails, updated_task.completion_details.as_deref(), actor_id.as_deref()).await;
+    }
+
+    if updated_task.status != old_status {
+        events::publish(&db, &config, Event::TaskStatusChanged {
+            task: updated_task.clone(),
+            old_status,
+            actor_id,
+        }).await;
+    }
+
+    Ok(Json(updated_task.into()))
+}
+
+// 按看板工作流移动任务：校验目标列、处理验收和WIP上限，记录完成详情和状态历史
+// 返回更新后的任务和原来的状态，由调用方发布事件
+pub async fn move_task<C: ConnectionTrait>(
+    conn: &C,
+    task: task::Model,
+    invite: &invite::Model,
+    payload: UpdateTaskStatusRequest,
+) -> Result<(task::Model, String), MoveError> {
+    // 状态必须是看板上的列
+    let workflow = load_workflow(conn, &task.invite_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let category = workflow.category(&payload.status).ok_or(StatusCode::BAD_REQUEST)?;
+
+    // 看板有待验收列时，创建者以外的人移到完成列会进入待验收，由创建者验收后才算完成
+    // 没有提供操作者时按执行者处理，同样需要验收
+    let reviewer_required = payload.user_id.as_deref() != Some(invite.creator_id.as_str());
+    let (status, category) = if category == "finished" && reviewer_required && workflow.contains(REVIEW_STATUS) {
+        (REVIEW_STATUS.to_string(), workflow.category(REVIEW_STATUS).unwrap_or("active").to_string())
+    } else {
+        (payload.status, category.to_string())
+    };
+    let old_status = task.status.clone();
+
+    // 移入其他列时检查WIP上限，看板创建者可以显式越过
+    check_override(payload.override_wip_limit, payload.user_id.as_deref(), &invite.creator_id)?;
+    if status != old_status && !payload.override_wip_limit {
+        check_wip_limit(conn, &workflow, &invite.id, &status).await?;
+    }
+
     // 更新任务状态
     let mut task_active: task::ActiveModel = task.into();
-    task_active.status = Set(payload.status.clone());
+    task_active.status = Set(status.clone());
+    task_active.status_category = Set(category.clone());
     task_active.updated_at = Set(chrono::Utc::now().into());
-    
-    // 如果状态为完成，处理完成详情和完成时间
-    if payload.status == "done" {
-        if let Some(completion_details) = payload.completion_details {
-            task_active.completion_details = Set(Some(completion_details));
-        }
+
+    // 提交验收或完成时处理完成详情，完成时记录完成时间
+    let submission = payload.completion_details.filter(|_| status == REVIEW_STATUS || category == "finished");
+    if let Some(completion_details) = &submission {
+        task_active.completion_details = Set(Some(completion_details.clone()));
+    }
+    if category == "finished" {
         task_active.completed_at = Set(Some(chrono::Utc::now().into()));
     }
 
     let updated_task = task_active
-        .update(&db)
+        .update(conn)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    Ok(Json(TaskResponse {
-        id: updated_task.id,
-        title: updated_task.title,
-        description: updated_task.description,
-        status: updated_task.status,
-        creator_id: updated_task.creator_id,
-        executor_id: updated_task.executor_id,
-        invite_id: updated_task.invite_id,
-        completion_details: updated_task.completion_details,
-        completed_at: updated_task.completed_at.map(|dt| dt.to_rfc3339()),
-        created_at: updated_task.created_at.to_rfc3339(),
-        updated_at: updated_task.updated_at.to_rfc3339(),
-    }))
+    // 每次提交的完成详情单独保存一个版本
+    if let Some(completion_details) = &submission {
+        record_submission(conn, &updated_task.id, payload.user_id.as_deref(), completion_details, chrono::Utc::now())
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+
+    if updated_task.status != old_status {
+        // 任务完成时停止所有计时
+        if updated_task.status_category == "finished" {
+            stop_running_timers(conn, &updated_task.id, chrono::Utc::now())
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        }
+        record_status_change(conn, &updated_task.id, Some(&old_status), &updated_task.status, &updated_task.status_category, payload.user_id.as_deref(), chrono::Utc::now())
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+
+    Ok((updated_task, old_status))
 }
 
-// 删除任务接口
+// 删除任务接口：移到回收站，停止计时，保留期内可以恢复
 pub async fn delete_task(
     State(db): State<DatabaseConnection>,
+    State(config): State<Arc<Config>>,
     Path(task_id): Path<String>,
 ) -> Result<StatusCode, StatusCode> {
     let task = Task::find_by_id(&task_id)
+        .filter(task::Column::DeletedAt.is_null())
         .one(&db)
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
         .ok_or(StatusCode::NOT_FOUND)?;
+    ensure_board_writable(&db, &task.invite_id).await?;
 
-    task.delete(&db)
+    let txn = db.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let deleted_task = trash_task(&txn, task, chrono::Utc::now())
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    txn.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    events::publish(&db, &config, Event::TaskDeleted {
+        task: deleted_task,
+        actor_id: None,
+    }).await;
 
     Ok(StatusCode::NO_CONTENT)
 }
+
+// 把任务移到回收站并停止任务上的计时
+pub async fn trash_task<C: ConnectionTrait>(conn: &C, task: task::Model, now: chrono::DateTime<chrono::Utc>) -> Result<task::Model, DbErr> {
+    let task_id = task.id.clone();
+    let mut task_active: task::ActiveModel = task.into();
+    task_active.deleted_at = Set(Some(now.into()));
+    let deleted_task = task_active.update(conn).await?;
+    stop_running_timers(conn, &task_id, now).await?;
+    Ok(deleted_task)
+}
+
+// 获取看板回收站中的任务，最近删除的在前
+pub async fn get_trash(
+    State(db): State<DatabaseConnection>,
+    State(config): State<Arc<Config>>,
+    Path(invite_id): Path<String>,
+) -> Result<Json<TrashListResponse>, StatusCode> {
+    let tasks = Task::find()
+        .filter(task::Column::InviteId.eq(&invite_id))
+        .filter(task::Column::DeletedAt.is_not_null())
+        .order_by_desc(task::Column::DeletedAt)
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let retention = (config.trash_retention_days > 0).then(|| chrono::Duration::days(config.trash_retention_days));
+    Ok(Json(TrashListResponse {
+        tasks: tasks
+            .into_iter()
+            .map(|task| TrashedTaskResponse {
+                purge_at: task
+                    .deleted_at
+                    .zip(retention)
+                    .map(|(deleted_at, retention)| (deleted_at + retention).to_rfc3339()),
+                task: task.into(),
+            })
+            .collect(),
+    }))
+}
+
+// 从回收站恢复任务，恢复到删除前所在的列
+pub async fn restore_task(
+    State(db): State<DatabaseConnection>,
+    State(config): State<Arc<Config>>,
+    Path(task_id): Path<String>,
+) -> Result<Json<TaskResponse>, StatusCode> {
+    let task = Task::find_by_id(&task_id)
+        .filter(task::Column::DeletedAt.is_not_null())
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    ensure_board_writable(&db, &task.invite_id).await?;
+
+    let mut task_active: task::ActiveModel = task.into();
+    task_active.deleted_at = Set(None);
+    task_active.updated_at = Set(chrono::Utc::now().into());
+    let restored_task = task_active
+        .update(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    events::publish(&db, &config, Event::TaskRestored {
+        task: restored_task.clone(),
+        actor_id: None,
+    }).await;
+
+    Ok(Json(restored_task.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_util::{self, create_board, create_user, test_config, test_db};
+
+    async fn list_tasks(db: &DatabaseConnection, invite_id: &str) -> Vec<String> {
+        let Json(list) = get_tasks(State(db.clone()), Path(invite_id.to_string()), Query(HashMap::new())).await.unwrap();
+        list.tasks.into_iter().map(|task| task.id).collect()
+    }
+
+    // 删除的任务从列表中隐藏，恢复后回到删除前所在的列
+    #[tokio::test]
+    async fn deleted_tasks_are_hidden_until_restored() {
+        let db = test_db().await;
+        let config = Arc::new(test_config());
+        let creator_id = create_user(&db, "creator", "creator").await;
+        let invite_id = create_board(&db, &creator_id, None).await;
+        let task = test_util::create_task(&db, &invite_id, &creator_id, None).await;
+        let mut task_active: task::ActiveModel = task.into();
+        task_active.status = Set("in_progress".to_string());
+        task_active.status_category = Set("active".to_string());
+        let task = task_active.update(&db).await.unwrap();
+
+        let status = delete_task(State(db.clone()), State(config.clone()), Path(task.id.clone())).await.unwrap();
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        assert!(list_tasks(&db, &invite_id).await.is_empty());
+        let Json(trash) = get_trash(State(db.clone()), State(config.clone()), Path(invite_id.clone())).await.unwrap();
+        assert_eq!(trash.tasks.len(), 1);
+        assert!(trash.tasks[0].purge_at.is_some());
+
+        // 回收站中的任务不能修改
+        let updated = update_task(
+            State(db.clone()),
+            State(config.clone()),
+            Path(task.id.clone()),
+            Json(serde_json::from_value(serde_json::json!({ "title": "新标题" })).unwrap()),
+        )
+        .await;
+        assert!(matches!(updated, Err(MoveError::Status(StatusCode::NOT_FOUND))));
+
+        let Json(restored) = restore_task(State(db.clone()), State(config.clone()), Path(task.id.clone())).await.unwrap();
+        assert_eq!(restored.status, "in_progress");
+        assert_eq!(list_tasks(&db, &invite_id).await, vec![task.id]);
+        let Json(trash) = get_trash(State(db.clone()), State(config), Path(invite_id)).await.unwrap();
+        assert!(trash.tasks.is_empty());
+    }
+}
@@ -0,0 +1,663 @@
+use axum::{extract::{Path, Query, State}, http::StatusCode, Json};
+use chrono::{Datelike, Utc};
+use sea_orm::{ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, ModelTrait, QueryFilter, QueryOrder, Set, TransactionTrait};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::config::Config;
+use crate::events::{self, Event};
+use crate::handlers::archive::ensure_board_writable;
+use crate::handlers::invite::{generate_invite_code, InviteResponse};
+use crate::handlers::label::attach_labels;
+use crate::handlers::mention::{sync_mentions, MentionSource};
+use crate::handlers::task::{insert_task, NewTask, TaskResponse, PRIORITIES};
+use crate::models::{
+    board_template, board_template::Entity as BoardTemplate, invite, invite::Entity as Invite, task,
+    task::Entity as Task, task_template, task_template::Entity as TaskTemplate, user, user::Entity as User,
+};
+
+// 模板中的任务定义，任务模板和看板模板共用
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TaskSpec {
+    pub title_pattern: String, // 支持 {date}、{week}、{month} 和自定义变量
+    pub description: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    #[serde(default)]
+    pub checklist: Vec<String>,
+    pub priority: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct CreateTaskTemplateRequest {
+    pub creator_id: String,
+    pub name: String,
+    #[serde(flatten)]
+    pub spec: TaskSpec,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateTaskTemplateRequest {
+    pub creator_id: String,
+    pub name: Option<String>,
+    pub title_pattern: Option<String>,
+    pub description: Option<String>,
+    pub labels: Option<Vec<String>>,
+    pub checklist: Option<Vec<String>>,
+    pub priority: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct CreateBoardTemplateRequest {
+    pub creator_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub tasks: Vec<TaskSpec>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateBoardTemplateRequest {
+    pub creator_id: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub tasks: Option<Vec<TaskSpec>>,
+}
+
+#[derive(Deserialize)]
+pub struct CreateTaskFromTemplateRequest {
+    pub creator_id: String,
+    pub invite_id: String,
+    pub due_date: Option<chrono::DateTime<chrono::FixedOffset>>,
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+pub struct CreateBoardFromTemplateRequest {
+    pub creator_id: String,
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+pub struct CreatorQuery {
+    pub creator_id: String,
+}
+
+#[derive(Serialize)]
+pub struct TaskTemplateResponse {
+    pub id: String,
+    pub creator_id: String,
+    pub name: String,
+    pub title_pattern: String,
+    pub description: Option<String>,
+    pub labels: Vec<String>,
+    pub checklist: Vec<String>,
+    pub priority: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Serialize)]
+pub struct TaskTemplateListResponse {
+    pub templates: Vec<TaskTemplateResponse>,
+}
+
+#[derive(Serialize)]
+pub struct BoardTemplateResponse {
+    pub id: String,
+    pub creator_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub tasks: Vec<TaskSpec>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Serialize)]
+pub struct BoardTemplateListResponse {
+    pub templates: Vec<BoardTemplateResponse>,
+}
+
+#[derive(Serialize)]
+pub struct BoardFromTemplateResponse {
+    pub invite: InviteResponse,
+    pub tasks: Vec<TaskResponse>,
+}
+
+impl From<task_template::Model> for TaskTemplateResponse {
+    fn from(template: task_template::Model) -> Self {
+        TaskTemplateResponse {
+            id: template.id,
+            creator_id: template.creator_id,
+            name: template.name,
+            title_pattern: template.title_pattern,
+            description: template.description,
+            labels: serde_json::from_str(&template.labels).unwrap_or_default(),
+            checklist: serde_json::from_str(&template.checklist).unwrap_or_default(),
+            priority: template.priority,
+            created_at: template.created_at.to_rfc3339(),
+            updated_at: template.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+impl From<board_template::Model> for BoardTemplateResponse {
+    fn from(template: board_template::Model) -> Self {
+        BoardTemplateResponse {
+            id: template.id,
+            creator_id: template.creator_id,
+            name: template.name,
+            description: template.description,
+            tasks: serde_json::from_str(&template.tasks).unwrap_or_default(),
+            created_at: template.created_at.to_rfc3339(),
+            updated_at: template.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+impl TaskSpec {
+    fn from_template(template: &task_template::Model) -> Self {
+        TaskSpec {
+            title_pattern: template.title_pattern.clone(),
+            description: template.description.clone(),
+            labels: serde_json::from_str(&template.labels).unwrap_or_default(),
+            checklist: serde_json::from_str(&template.checklist).unwrap_or_default(),
+            priority: template.priority.clone(),
+        }
+    }
+
+    // 去掉首尾空白和重复标签，标题为空、清单项为空或优先级无效时返回错误
+    fn normalize(self) -> Result<Self, StatusCode> {
+        let title_pattern = self.title_pattern.trim().to_string();
+        let mut labels: Vec<String> = Vec::new();
+        for label in self.labels.iter().map(|label| label.trim()) {
+            if label.is_empty() {
+                return Err(StatusCode::BAD_REQUEST);
+            }
+            if !labels.iter().any(|existing| existing == label) {
+                labels.push(label.to_string());
+            }
+        }
+        let checklist: Vec<String> = self.checklist.iter().map(|item| item.trim().to_string()).collect();
+        if title_pattern.is_empty()
+            || checklist.iter().any(|item| item.is_empty())
+            || self.priority.as_ref().is_some_and(|p| !PRIORITIES.contains(&p.as_str()))
+        {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+
+        Ok(TaskSpec {
+            title_pattern,
+            description: self.description,
+            labels,
+            checklist,
+            priority: self.priority,
+        })
+    }
+
+    // 代入变量生成待创建的任务，清单以Markdown任务列表追加到描述末尾
+    fn instantiate(
+        &self,
+        creator_id: &str,
+        invite_id: &str,
+        variables: &HashMap<String, String>,
+        due_date: Option<chrono::DateTime<chrono::FixedOffset>>,
+    ) -> NewTask {
+        let description = self
+            .description
+            .as_deref()
+            .map(|description| render(description, variables))
+            .filter(|description| !description.trim().is_empty());
+        let checklist: Vec<String> = self.checklist.iter().map(|item| format!("- [ ] {}", item)).collect();
+        let description = match (description, checklist.is_empty()) {
+            (description, true) => description,
+            (Some(description), false) => Some(format!("{}\n\n{}", description.trim_end(), checklist.join("\n"))),
+            (None, false) => Some(checklist.join("\n")),
+        };
+
+        NewTask {
+            title: render(&self.title_pattern, variables).trim().to_string(),
+            description,
+            creator_id: creator_id.to_string(),
+            invite_id: invite_id.to_string(),
+            due_date,
+            priority: self.priority.clone(),
+            custom_fields: None,
+        }
+    }
+}
+
+// 替换 {变量名} 占位符，内置 date（YYYY-MM-DD）、week（YYYY-Www）、month（YYYY-MM），
+// 自定义变量可以覆盖内置变量，未知的占位符原样保留
+fn render(pattern: &str, variables: &HashMap<String, String>) -> String {
+    let today = Utc::now().date_naive();
+    let week = today.iso_week();
+    let builtin = |name: &str| match name {
+        "date" => Some(today.format("%Y-%m-%d").to_string()),
+        "week" => Some(format!("{}-W{:02}", week.year(), week.week())),
+        "month" => Some(today.format("%Y-%m").to_string()),
+        _ => None,
+    };
+
+    let mut output = String::with_capacity(pattern.len());
+    let mut rest = pattern;
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('}') else {
+            rest = &rest[start..];
+            break;
+        };
+        let name = &after[..end];
+        match variables.get(name).cloned().or_else(|| builtin(name)) {
+            Some(value) => output.push_str(&value),
+            None => output.push_str(&rest[start..start + end + 2]),
+        }
+        rest = &after[end + 1..];
+    }
+    output.push_str(rest);
+    output
+}
+
+// 校验用户是否为创建者
+async fn ensure_creator(db: &DatabaseConnection, creator_id: &str) -> Result<(), StatusCode> {
+    User::find()
+        .filter(user::Column::Id.eq(creator_id))
+        .filter(user::Column::Role.eq("creator"))
+        .one(db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::BAD_REQUEST)?;
+    Ok(())
+}
+
+fn non_empty_name(name: &str) -> Result<String, StatusCode> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    Ok(name.to_string())
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, StatusCode> {
+    serde_json::to_string(value).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+// 创建任务模板接口
+pub async fn create_task_template(
+    State(db): State<DatabaseConnection>,
+    Json(payload): Json<CreateTaskTemplateRequest>,
+) -> Result<Json<TaskTemplateResponse>, StatusCode> {
+    ensure_creator(&db, &payload.creator_id).await?;
+    let name = non_empty_name(&payload.name)?;
+    let spec = payload.spec.normalize()?;
+
+    let template_id = uuid::Uuid::new_v4().to_string();
+    let now = Utc::now();
+
+    db.execute(
+        sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            r#"
+            INSERT INTO task_templates (id, creator_id, name, title_pattern, description, labels, checklist, priority, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            [
+                template_id.clone().into(),
+                payload.creator_id.into(),
+                name.into(),
+                spec.title_pattern.into(),
+                spec.description.into(),
+                to_json(&spec.labels)?.into(),
+                to_json(&spec.checklist)?.into(),
+                spec.priority.into(),
+                now.into(),
+                now.into(),
+            ]
+        )
+    ).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let template = TaskTemplate::find_by_id(&template_id)
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(template.into()))
+}
+
+// 获取创建者的任务模板列表
+pub async fn get_task_templates(
+    State(db): State<DatabaseConnection>,
+    Query(query): Query<CreatorQuery>,
+) -> Result<Json<TaskTemplateListResponse>, StatusCode> {
+    let templates = TaskTemplate::find()
+        .filter(task_template::Column::CreatorId.eq(&query.creator_id))
+        .order_by_asc(task_template::Column::Name)
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(TaskTemplateListResponse {
+        templates: templates.into_iter().map(Into::into).collect(),
+    }))
+}
+
+// 更新任务模板接口
+pub async fn update_task_template(
+    State(db): State<DatabaseConnection>,
+    Path(template_id): Path<String>,
+    Json(payload): Json<UpdateTaskTemplateRequest>,
+) -> Result<Json<TaskTemplateResponse>, StatusCode> {
+    let template = find_task_template(&db, &template_id, &payload.creator_id).await?;
+
+    let mut spec = TaskSpec::from_template(&template);
+    if let Some(title_pattern) = payload.title_pattern {
+        spec.title_pattern = title_pattern;
+    }
+    if let Some(description) = payload.description {
+        spec.description = Some(description);
+    }
+    if let Some(labels) = payload.labels {
+        spec.labels = labels;
+    }
+    if let Some(checklist) = payload.checklist {
+        spec.checklist = checklist;
+    }
+    if let Some(priority) = payload.priority {
+        spec.priority = Some(priority);
+    }
+    let spec = spec.normalize()?;
+
+    let mut template_active: task_template::ActiveModel = template.into();
+    if let Some(name) = payload.name {
+        template_active.name = Set(non_empty_name(&name)?);
+    }
+    template_active.title_pattern = Set(spec.title_pattern);
+    template_active.description = Set(spec.description);
+    template_active.labels = Set(to_json(&spec.labels)?);
+    template_active.checklist = Set(to_json(&spec.checklist)?);
+    template_active.priority = Set(spec.priority);
+    template_active.updated_at = Set(Utc::now().into());
+
+    let updated = template_active
+        .update(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(updated.into()))
+}
+
+// 删除任务模板接口，已创建的任务不受影响
+pub async fn delete_task_template(
+    State(db): State<DatabaseConnection>,
+    Path(template_id): Path<String>,
+    Query(query): Query<CreatorQuery>,
+) -> Result<StatusCode, StatusCode> {
+    let template = find_task_template(&db, &template_id, &query.creator_id).await?;
+
+    template.delete(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// 从任务模板创建任务，与创建任务接口相同地发布任务创建事件
+pub async fn create_task_from_template(
+    State(db): State<DatabaseConnection>,
+    State(config): State<Arc<Config>>,
+    Path(template_id): Path<String>,
+    Json(payload): Json<CreateTaskFromTemplateRequest>,
+) -> Result<Json<TaskResponse>, StatusCode> {
+    let template = find_task_template(&db, &template_id, &payload.creator_id).await?;
+
+    Invite::find()
+        .filter(invite::Column::Id.eq(&payload.invite_id))
+        .filter(invite::Column::CreatorId.eq(&payload.creator_id))
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::BAD_REQUEST)?;
+    ensure_board_writable(&db, &payload.invite_id).await?;
+
+    let spec = TaskSpec::from_template(&template);
+    let new_task = spec.instantiate(&payload.creator_id, &payload.invite_id, &payload.variables, payload.due_date);
+    new_task.validate().map_err(|_| StatusCode::BAD_REQUEST)?;
+
+    let txn = db.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let task_id = insert_task(&txn, new_task).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    attach_labels(&txn, &payload.invite_id, &task_id, &spec.labels)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    txn.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let task = Task::find_by_id(&task_id)
+        .filter(task::Column::DeletedAt.is_null())
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    events::publish(&db, &config, Event::TaskCreated {
+        task: task.clone(),
+        actor_id: Some(task.creator_id.clone()),
+    }).await;
+    sync_mentions(&db, &config, &task, MentionSource::Description, task.description.as_deref(), Some(&task.creator_id)).await;
+
+    Ok(Json(task.into()))
+}
+
+// 创建看板模板接口
+pub async fn create_board_template(
+    State(db): State<DatabaseConnection>,
+    Json(payload): Json<CreateBoardTemplateRequest>,
+) -> Result<Json<BoardTemplateResponse>, StatusCode> {
+    ensure_creator(&db, &payload.creator_id).await?;
+    let name = non_empty_name(&payload.name)?;
+    let tasks = payload
+        .tasks
+        .into_iter()
+        .map(TaskSpec::normalize)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let template_id = uuid::Uuid::new_v4().to_string();
+    let now = Utc::now();
+
+    db.execute(
+        sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            r#"
+            INSERT INTO board_templates (id, creator_id, name, description, tasks, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+            [
+                template_id.clone().into(),
+                payload.creator_id.into(),
+                name.into(),
+                payload.description.into(),
+                to_json(&tasks)?.into(),
+                now.into(),
+                now.into(),
+            ]
+        )
+    ).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let template = BoardTemplate::find_by_id(&template_id)
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(template.into()))
+}
+
+// 获取创建者的看板模板列表
+pub async fn get_board_templates(
+    State(db): State<DatabaseConnection>,
+    Query(query): Query<CreatorQuery>,
+) -> Result<Json<BoardTemplateListResponse>, StatusCode> {
+    let templates = BoardTemplate::find()
+        .filter(board_template::Column::CreatorId.eq(&query.creator_id))
+        .order_by_asc(board_template::Column::Name)
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(BoardTemplateListResponse {
+        templates: templates.into_iter().map(Into::into).collect(),
+    }))
+}
+
+// 更新看板模板接口，传入tasks时整体替换任务列表
+pub async fn update_board_template(
+    State(db): State<DatabaseConnection>,
+    Path(template_id): Path<String>,
+    Json(payload): Json<UpdateBoardTemplateRequest>,
+) -> Result<Json<BoardTemplateResponse>, StatusCode> {
+    let template = find_board_template(&db, &template_id, &payload.creator_id).await?;
+
+    let mut template_active: board_template::ActiveModel = template.into();
+    if let Some(name) = payload.name {
+        template_active.name = Set(non_empty_name(&name)?);
+    }
+    if let Some(description) = payload.description {
+        template_active.description = Set(Some(description));
+    }
+    if let Some(tasks) = payload.tasks {
+        let tasks = tasks
+            .into_iter()
+            .map(TaskSpec::normalize)
+            .collect::<Result<Vec<_>, _>>()?;
+        template_active.tasks = Set(to_json(&tasks)?);
+    }
+    template_active.updated_at = Set(Utc::now().into());
+
+    let updated = template_active
+        .update(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(updated.into()))
+}
+
+// 删除看板模板接口，已创建的看板不受影响
+pub async fn delete_board_template(
+    State(db): State<DatabaseConnection>,
+    Path(template_id): Path<String>,
+    Query(query): Query<CreatorQuery>,
+) -> Result<StatusCode, StatusCode> {
+    let template = find_board_template(&db, &template_id, &query.creator_id).await?;
+
+    template.delete(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// 从看板模板创建新看板（邀请码）和其中的任务，全部在同一个事务中写入
+pub async fn create_board_from_template(
+    State(db): State<DatabaseConnection>,
+    Path(template_id): Path<String>,
+    Json(payload): Json<CreateBoardFromTemplateRequest>,
+) -> Result<Json<BoardFromTemplateResponse>, StatusCode> {
+    let template = find_board_template(&db, &template_id, &payload.creator_id).await?;
+    let specs: Vec<TaskSpec> = serde_json::from_str(&template.tasks).unwrap_or_default();
+
+    let invite_id = uuid::Uuid::new_v4().to_string();
+    let new_tasks = specs
+        .iter()
+        .map(|spec| {
+            let new_task = spec.instantiate(&payload.creator_id, &invite_id, &payload.variables, None);
+            new_task.validate().map(|_| new_task).map_err(|_| StatusCode::BAD_REQUEST)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let txn = db.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let now = Utc::now();
+
+    let invite_code = generate_invite_code();
+    txn.execute(
+        sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            r#"
+            INSERT INTO invites (id, code, creator_id, executor_id, status, created_at, used_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+            [
+                invite_id.clone().into(),
+                invite_code.clone().into(),
+                payload.creator_id.clone().into(),
+                sea_orm::Value::String(None),
+                "pending".into(),
+                now.into(),
+                sea_orm::Value::ChronoDateTimeWithTimeZone(None),
+            ]
+        )
+    ).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let mut task_ids = Vec::with_capacity(new_tasks.len());
+    for (spec, new_task) in specs.iter().zip(new_tasks) {
+        let task_id = insert_task(&txn, new_task).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        attach_labels(&txn, &invite_id, &task_id, &spec.labels)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        task_ids.push(task_id);
+    }
+
+    txn.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let mut tasks = Task::find()
+        .filter(task::Column::InviteId.eq(&invite_id))
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    tasks.sort_by_key(|task| task_ids.iter().position(|id| *id == task.id));
+
+    // 新看板还没有执行者和Webhook，不发布任务事件
+    Ok(Json(BoardFromTemplateResponse {
+        invite: InviteResponse {
+            id: invite_id,
+            code: invite_code,
+            creator_id: payload.creator_id,
+            executor_id: None,
+            status: "pending".to_string(),
+            created_at: now.to_rfc3339(),
+            used_at: None,
+            archived_at: None,
+            auto_archive_days: None,
+        },
+        tasks: tasks.into_iter().map(Into::into).collect(),
+    }))
+}
+
+// 查找创建者名下的任务模板
+async fn find_task_template(
+    db: &DatabaseConnection,
+    template_id: &str,
+    creator_id: &str,
+) -> Result<task_template::Model, StatusCode> {
+    TaskTemplate::find_by_id(template_id)
+        .filter(task_template::Column::CreatorId.eq(creator_id))
+        .one(db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+// 查找创建者名下的看板模板
+async fn find_board_template(
+    db: &DatabaseConnection,
+    template_id: &str,
+    creator_id: &str,
+) -> Result<board_template::Model, StatusCode> {
+    BoardTemplate::find_by_id(template_id)
+        .filter(board_template::Column::CreatorId.eq(creator_id))
+        .one(db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)
+}
@@ -0,0 +1,497 @@
+use axum::{extract::{Path, Query, State}, http::StatusCode, Json};
+use chrono::{DateTime, FixedOffset, Utc};
+use sea_orm::{ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, ModelTrait, QueryFilter, QueryOrder, Set, SqlErr};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::handlers::archive::ensure_task_board_writable;
+use crate::handlers::stats::round_hours;
+use crate::models::{
+    invite::Entity as Invite, task, task::Entity as Task, time_entry, time_entry::Entity as TimeEntry, user,
+    user::Entity as User,
+};
+
+#[derive(Deserialize)]
+pub struct StartTimerRequest {
+    pub user_id: String,
+    pub note: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct StopTimerRequest {
+    pub user_id: String,
+}
+
+#[derive(Deserialize)]
+pub struct CreateTimeEntryRequest {
+    pub user_id: String,
+    pub started_at: DateTime<FixedOffset>,
+    pub ended_at: DateTime<FixedOffset>,
+    pub note: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateTimeEntryRequest {
+    pub user_id: String,
+    pub started_at: Option<DateTime<FixedOffset>>,
+    pub ended_at: Option<DateTime<FixedOffset>>, // 计时中的记录设置后即停止
+    pub note: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct UserQuery {
+    pub user_id: String,
+}
+
+#[derive(Serialize)]
+pub struct TimeEntryResponse {
+    pub id: String,
+    pub task_id: String,
+    pub user_id: String,
+    pub started_at: String,
+    pub ended_at: Option<String>,
+    pub note: Option<String>,
+    pub running: bool,
+    pub duration_seconds: i64, // 计时中的记录计算到当前时间
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Serialize)]
+pub struct UserTotal {
+    pub user_id: String,
+    pub username: Option<String>,
+    pub total_seconds: i64,
+    pub total_hours: f64,
+}
+
+#[derive(Serialize)]
+pub struct TaskTimeEntriesResponse {
+    pub task_id: String,
+    pub total_seconds: i64,
+    pub total_hours: f64,
+    pub users: Vec<UserTotal>,
+    pub entries: Vec<TimeEntryResponse>,
+}
+
+#[derive(Serialize)]
+pub struct TaskTotal {
+    pub task_id: String,
+    pub title: String,
+    pub total_seconds: i64,
+    pub total_hours: f64,
+}
+
+#[derive(Serialize)]
+pub struct BoardTimeTotalsResponse {
+    pub invite_id: String,
+    pub total_seconds: i64,
+    pub total_hours: f64,
+    pub tasks: Vec<TaskTotal>,
+    pub users: Vec<UserTotal>,
+}
+
+impl From<time_entry::Model> for TimeEntryResponse {
+    fn from(entry: time_entry::Model) -> Self {
+        TimeEntryResponse {
+            duration_seconds: duration_seconds(&entry, Utc::now()),
+            id: entry.id,
+            task_id: entry.task_id,
+            user_id: entry.user_id,
+            started_at: entry.started_at.to_rfc3339(),
+            ended_at: entry.ended_at.map(|dt| dt.to_rfc3339()),
+            note: entry.note,
+            running: entry.ended_at.is_none(),
+            created_at: entry.created_at.to_rfc3339(),
+            updated_at: entry.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+// 记录时长，计时中的记录计算到now
+pub fn duration_seconds(entry: &time_entry::Model, now: DateTime<Utc>) -> i64 {
+    let ended_at = entry.ended_at.map(|dt| dt.with_timezone(&Utc)).unwrap_or(now);
+    (ended_at - entry.started_at.with_timezone(&Utc)).num_seconds().max(0)
+}
+
+fn hours(seconds: i64) -> f64 {
+    round_hours(seconds as f64 / 3600.0)
+}
+
+// 停止任务上所有计时中的记录，任务完成时调用
+pub async fn stop_running_timers<C: ConnectionTrait>(conn: &C, task_id: &str, at: DateTime<Utc>) -> Result<(), DbErr> {
+    conn.execute(
+        sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            "UPDATE time_entries SET ended_at = ?, updated_at = ? WHERE task_id = ? AND ended_at IS NULL",
+            [at.into(), at.into(), task_id.into()],
+        )
+    ).await?;
+    Ok(())
+}
+
+// 开始计时，每个用户同时只能有一个计时中的记录
+pub async fn start_timer(
+    State(db): State<DatabaseConnection>,
+    Path(task_id): Path<String>,
+    Json(payload): Json<StartTimerRequest>,
+) -> Result<(StatusCode, Json<TimeEntryResponse>), StatusCode> {
+    let task = find_member_task(&db, &task_id, &payload.user_id).await?;
+    if task.status_category == "finished" {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let entry_id = uuid::Uuid::new_v4().to_string();
+    let now = Utc::now();
+    db.execute(
+        sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            r#"
+            INSERT INTO time_entries (id, task_id, user_id, started_at, ended_at, note, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            [
+                entry_id.clone().into(),
+                task_id.into(),
+                payload.user_id.into(),
+                now.into(),
+                sea_orm::Value::ChronoDateTimeWithTimeZone(None),
+                payload.note.into(),
+                now.into(),
+                now.into(),
+            ]
+        )
+    ).await.map_err(|e| match e.sql_err() {
+        // 唯一索引保证已有计时中的记录时无法再开始
+        Some(SqlErr::UniqueConstraintViolation(_)) => StatusCode::CONFLICT,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    })?;
+
+    let entry = find_entry(&db, &entry_id).await?;
+    Ok((StatusCode::CREATED, Json(entry.into())))
+}
+
+// 停止当前用户在该任务上的计时
+pub async fn stop_timer(
+    State(db): State<DatabaseConnection>,
+    Path(task_id): Path<String>,
+    Json(payload): Json<StopTimerRequest>,
+) -> Result<Json<TimeEntryResponse>, StatusCode> {
+    let entry = TimeEntry::find()
+        .filter(time_entry::Column::TaskId.eq(&task_id))
+        .filter(time_entry::Column::UserId.eq(&payload.user_id))
+        .filter(time_entry::Column::EndedAt.is_null())
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let now = Utc::now();
+    let mut entry_active: time_entry::ActiveModel = entry.into();
+    entry_active.ended_at = Set(Some(now.into()));
+    entry_active.updated_at = Set(now.into());
+
+    let updated = entry_active
+        .update(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(updated.into()))
+}
+
+// 获取用户当前计时中的记录
+pub async fn get_running_timer(
+    State(db): State<DatabaseConnection>,
+    Path(user_id): Path<String>,
+) -> Result<Json<TimeEntryResponse>, StatusCode> {
+    let entry = TimeEntry::find()
+        .filter(time_entry::Column::UserId.eq(&user_id))
+        .filter(time_entry::Column::EndedAt.is_null())
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(Json(entry.into()))
+}
+
+// 手动添加工时记录
+pub async fn create_time_entry(
+    State(db): State<DatabaseConnection>,
+    Path(task_id): Path<String>,
+    Json(payload): Json<CreateTimeEntryRequest>,
+) -> Result<(StatusCode, Json<TimeEntryResponse>), StatusCode> {
+    find_member_task(&db, &task_id, &payload.user_id).await?;
+    if payload.ended_at < payload.started_at {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let entry_id = uuid::Uuid::new_v4().to_string();
+    let now = Utc::now();
+    db.execute(
+        sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            r#"
+            INSERT INTO time_entries (id, task_id, user_id, started_at, ended_at, note, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            [
+                entry_id.clone().into(),
+                task_id.into(),
+                payload.user_id.into(),
+                payload.started_at.with_timezone(&Utc).into(),
+                payload.ended_at.with_timezone(&Utc).into(),
+                payload.note.into(),
+                now.into(),
+                now.into(),
+            ]
+        )
+    ).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let entry = find_entry(&db, &entry_id).await?;
+    Ok((StatusCode::CREATED, Json(entry.into())))
+}
+
+// 获取任务的工时记录和按用户汇总的时长
+pub async fn get_task_time_entries(
+    State(db): State<DatabaseConnection>,
+    Path(task_id): Path<String>,
+) -> Result<Json<TaskTimeEntriesResponse>, StatusCode> {
+    Task::find_by_id(&task_id)
+        .filter(task::Column::DeletedAt.is_null())
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let entries = TimeEntry::find()
+        .filter(time_entry::Column::TaskId.eq(&task_id))
+        .order_by_asc(time_entry::Column::StartedAt)
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let now = Utc::now();
+    let mut per_user: BTreeMap<String, i64> = BTreeMap::new();
+    for entry in &entries {
+        *per_user.entry(entry.user_id.clone()).or_default() += duration_seconds(entry, now);
+    }
+    let total_seconds = per_user.values().sum();
+
+    Ok(Json(TaskTimeEntriesResponse {
+        task_id,
+        total_seconds,
+        total_hours: hours(total_seconds),
+        users: user_totals(&db, per_user).await?,
+        entries: entries.into_iter().map(Into::into).collect(),
+    }))
+}
+
+// 修改工时记录，只能修改自己的记录
+pub async fn update_time_entry(
+    State(db): State<DatabaseConnection>,
+    Path(entry_id): Path<String>,
+    Json(payload): Json<UpdateTimeEntryRequest>,
+) -> Result<Json<TimeEntryResponse>, StatusCode> {
+    let entry = find_user_entry(&db, &entry_id, &payload.user_id).await?;
+    ensure_task_board_writable(&db, &entry.task_id).await?;
+
+    // 客户端的时间统一转换为UTC保存
+    let started_at = payload.started_at.map(|dt| dt.with_timezone(&Utc).into()).unwrap_or(entry.started_at);
+    let ended_at = payload.ended_at.map(|dt| dt.with_timezone(&Utc).into()).or(entry.ended_at);
+    if ended_at.is_some_and(|ended_at| ended_at < started_at) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let mut entry_active: time_entry::ActiveModel = entry.into();
+    entry_active.started_at = Set(started_at);
+    entry_active.ended_at = Set(ended_at);
+    if let Some(note) = payload.note {
+        entry_active.note = Set(Some(note));
+    }
+    entry_active.updated_at = Set(Utc::now().into());
+
+    let updated = entry_active
+        .update(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(updated.into()))
+}
+
+// 删除工时记录，只能删除自己的记录
+pub async fn delete_time_entry(
+    State(db): State<DatabaseConnection>,
+    Path(entry_id): Path<String>,
+    Query(query): Query<UserQuery>,
+) -> Result<StatusCode, StatusCode> {
+    let entry = find_user_entry(&db, &entry_id, &query.user_id).await?;
+    ensure_task_board_writable(&db, &entry.task_id).await?;
+
+    entry.delete(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// 看板工时汇总：按任务和按用户统计
+pub async fn get_board_time_totals(
+    State(db): State<DatabaseConnection>,
+    Path(invite_id): Path<String>,
+) -> Result<Json<BoardTimeTotalsResponse>, StatusCode> {
+    Invite::find_by_id(&invite_id)
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let tasks = Task::find()
+        .filter(task::Column::DeletedAt.is_null())
+        .filter(task::Column::InviteId.eq(&invite_id))
+        .order_by_asc(task::Column::CreatedAt)
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let entries = TimeEntry::find()
+        .filter(time_entry::Column::TaskId.is_in(tasks.iter().map(|task| task.id.clone())))
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let now = Utc::now();
+    let mut per_task: HashMap<String, i64> = HashMap::new();
+    let mut per_user: BTreeMap<String, i64> = BTreeMap::new();
+    for entry in &entries {
+        let seconds = duration_seconds(entry, now);
+        *per_task.entry(entry.task_id.clone()).or_default() += seconds;
+        *per_user.entry(entry.user_id.clone()).or_default() += seconds;
+    }
+    let total_seconds = per_task.values().sum();
+
+    // 只列出有工时记录的任务
+    let task_totals = tasks
+        .into_iter()
+        .filter_map(|task| {
+            let total_seconds = *per_task.get(&task.id)?;
+            Some(TaskTotal {
+                task_id: task.id,
+                title: task.title,
+                total_seconds,
+                total_hours: hours(total_seconds),
+            })
+        })
+        .collect();
+
+    Ok(Json(BoardTimeTotalsResponse {
+        invite_id,
+        total_seconds,
+        total_hours: hours(total_seconds),
+        tasks: task_totals,
+        users: user_totals(&db, per_user).await?,
+    }))
+}
+
+// 只有看板创建者、看板执行者和任务执行者可以记录工时
+async fn find_member_task(db: &DatabaseConnection, task_id: &str, user_id: &str) -> Result<task::Model, StatusCode> {
+    let task = Task::find_by_id(task_id)
+        .filter(task::Column::DeletedAt.is_null())
+        .one(db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let invite = Invite::find_by_id(&task.invite_id)
+        .one(db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    if invite.creator_id != user_id
+        && invite.executor_id.as_deref() != Some(user_id)
+        && task.executor_id.as_deref() != Some(user_id)
+    {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    if invite.archived_at.is_some() {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    Ok(task)
+}
+
+async fn find_entry(db: &DatabaseConnection, entry_id: &str) -> Result<time_entry::Model, StatusCode> {
+    TimeEntry::find_by_id(entry_id)
+        .one(db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+// 查找用户自己的工时记录
+async fn find_user_entry(db: &DatabaseConnection, entry_id: &str, user_id: &str) -> Result<time_entry::Model, StatusCode> {
+    TimeEntry::find_by_id(entry_id)
+        .filter(time_entry::Column::UserId.eq(user_id))
+        .one(db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+async fn user_totals(db: &DatabaseConnection, per_user: BTreeMap<String, i64>) -> Result<Vec<UserTotal>, StatusCode> {
+    let usernames: HashMap<String, String> = User::find()
+        .filter(user::Column::Id.is_in(per_user.keys().cloned()))
+        .all(db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .into_iter()
+        .map(|user| (user.id, user.username))
+        .collect();
+
+    Ok(per_user
+        .into_iter()
+        .map(|(user_id, total_seconds)| UserTotal {
+            username: usernames.get(&user_id).cloned(),
+            user_id,
+            total_seconds,
+            total_hours: hours(total_seconds),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+    use serde_json::json;
+
+    use crate::test_util::{create_board, create_task, create_user, test_db};
+
+    // 开始时间带+08:00时区，按字符串比较会早于UTC书写的结束时间
+    #[tokio::test]
+    async fn stores_entries_in_utc() {
+        let db = test_db().await;
+        let creator_id = create_user(&db, "creator", "creator").await;
+        let executor_id = create_user(&db, "executor", "executor").await;
+        let invite_id = create_board(&db, &creator_id, Some(&executor_id)).await;
+        let task = create_task(&db, &invite_id, &creator_id, Some(&executor_id)).await;
+
+        let ended_at = Utc::now();
+        let started_at = (ended_at - chrono::Duration::minutes(30)).with_timezone(&FixedOffset::east_opt(8 * 3600).unwrap());
+        let (status, Json(entry)) = create_time_entry(
+            State(db.clone()),
+            Path(task.id.clone()),
+            Json(serde_json::from_value(json!({
+                "user_id": executor_id,
+                "started_at": started_at.to_rfc3339(),
+                "ended_at": ended_at.to_rfc3339(),
+            })).unwrap()),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(entry.duration_seconds, 30 * 60);
+        assert!(entry.started_at.ends_with("+00:00"));
+    }
+}
@@ -0,0 +1,339 @@
+use axum::{extract::{Path, Query, State}, http::StatusCode, Json};
+use sea_orm::{ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, ModelTrait, QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait};
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv4Addr};
+use chrono;
+
+use crate::events::EVENT_TYPES;
+use crate::models::{invite, invite::Entity as Invite, webhook, webhook::Entity as Webhook, webhook_delivery, webhook_delivery::Entity as WebhookDelivery};
+
+#[derive(Deserialize)]
+pub struct CreateWebhookRequest {
+    pub creator_id: String,
+    pub url: String,
+    pub events: Option<Vec<String>>, // 为空表示订阅全部事件
+}
+
+#[derive(Deserialize)]
+pub struct CreatorQuery {
+    pub creator_id: String,
+}
+
+#[derive(Deserialize)]
+pub struct DeliveryListQuery {
+    pub creator_id: String,
+    pub status: Option<String>,
+    pub limit: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub struct RedeliverRequest {
+    pub creator_id: String,
+}
+
+#[derive(Serialize)]
+pub struct WebhookResponse {
+    pub id: String,
+    pub invite_id: String,
+    pub creator_id: String,
+    pub url: String,
+    pub secret: String,
+    pub events: Vec<String>,
+    pub active: bool,
+    pub created_at: String,
+}
+
+#[derive(Serialize)]
+pub struct WebhookListResponse {
+    pub webhooks: Vec<WebhookResponse>,
+}
+
+#[derive(Serialize)]
+pub struct WebhookDeliveryResponse {
+    pub id: String,
+    pub webhook_id: String,
+    pub event: String,
+    pub payload: serde_json::Value,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: Option<String>,
+    pub last_status_code: Option<i32>,
+    pub last_error: Option<String>,
+    pub created_at: String,
+    pub delivered_at: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct WebhookDeliveryListResponse {
+    pub deliveries: Vec<WebhookDeliveryResponse>,
+}
+
+impl From<webhook::Model> for WebhookResponse {
+    fn from(hook: webhook::Model) -> Self {
+        WebhookResponse {
+            events: hook
+                .events
+                .split(',')
+                .filter(|e| !e.is_empty())
+                .map(str::to_string)
+                .collect(),
+            id: hook.id,
+            invite_id: hook.invite_id,
+            creator_id: hook.creator_id,
+            url: hook.url,
+            secret: hook.secret,
+            active: hook.active,
+            created_at: hook.created_at.to_rfc3339(),
+        }
+    }
+}
+
+impl From<webhook_delivery::Model> for WebhookDeliveryResponse {
+    fn from(delivery: webhook_delivery::Model) -> Self {
+        WebhookDeliveryResponse {
+            payload: serde_json::from_str(&delivery.payload).unwrap_or(serde_json::Value::Null),
+            id: delivery.id,
+            webhook_id: delivery.webhook_id,
+            event: delivery.event,
+            status: delivery.status,
+            attempts: delivery.attempts,
+            next_attempt_at: delivery.next_attempt_at.map(|dt| dt.to_rfc3339()),
+            last_status_code: delivery.last_status_code,
+            last_error: delivery.last_error,
+            created_at: delivery.created_at.to_rfc3339(),
+            delivered_at: delivery.delivered_at.map(|dt| dt.to_rfc3339()),
+        }
+    }
+}
+
+// 注册Webhook接口
+pub async fn create_webhook(
+    State(db): State<DatabaseConnection>,
+    Path(invite_id): Path<String>,
+    Json(payload): Json<CreateWebhookRequest>,
+) -> Result<Json<WebhookResponse>, StatusCode> {
+    // 只有看板创建者可以注册Webhook
+    Invite::find()
+        .filter(invite::Column::Id.eq(&invite_id))
+        .filter(invite::Column::CreatorId.eq(&payload.creator_id))
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    if !is_allowed_url(&payload.url) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let events = payload.events.unwrap_or_default();
+    if events.iter().any(|e| !EVENT_TYPES.contains(&e.as_str())) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let txn = db.begin().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let webhook_id = uuid::Uuid::new_v4().to_string();
+    let secret = uuid::Uuid::new_v4().simple().to_string();
+    let now = chrono::Utc::now();
+
+    txn.execute(
+        sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            r#"
+            INSERT INTO webhooks (id, invite_id, creator_id, url, secret, events, active, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            [
+                webhook_id.clone().into(),
+                invite_id.into(),
+                payload.creator_id.into(),
+                payload.url.into(),
+                secret.into(),
+                events.join(",").into(),
+                true.into(),
+                now.into(),
+            ]
+        )
+    ).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    txn.commit().await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let hook = Webhook::find_by_id(&webhook_id)
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(hook.into()))
+}
+
+// 只允许推送到公网的http(s)地址，拒绝本机、内网和链路本地地址
+fn is_allowed_url(url: &str) -> bool {
+    let Ok(url) = reqwest::Url::parse(url) else {
+        return false;
+    };
+    if !matches!(url.scheme(), "http" | "https") {
+        return false;
+    }
+    let Some(host) = url.host_str() else {
+        return false;
+    };
+
+    match host.trim_start_matches('[').trim_end_matches(']').parse::<IpAddr>() {
+        Ok(IpAddr::V4(ip)) => is_public_ipv4(ip),
+        Ok(IpAddr::V6(ip)) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public_ipv4(ip),
+            None => {
+                let first = ip.segments()[0];
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || (first & 0xfe00) == 0xfc00 // 唯一本地地址 fc00::/7
+                    || (first & 0xffc0) == 0xfe80) // 链路本地地址 fe80::/10
+            }
+        },
+        Err(_) => {
+            let host = host.trim_end_matches('.').to_ascii_lowercase();
+            host != "localhost" && !host.ends_with(".localhost")
+        }
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    !(ip.is_loopback() || ip.is_private() || ip.is_link_local() || ip.is_unspecified() || ip.is_broadcast())
+}
+
+// 获取看板的Webhook列表
+pub async fn get_webhooks(
+    State(db): State<DatabaseConnection>,
+    Path(invite_id): Path<String>,
+    Query(query): Query<CreatorQuery>,
+) -> Result<Json<WebhookListResponse>, StatusCode> {
+    let webhooks = Webhook::find()
+        .filter(webhook::Column::InviteId.eq(&invite_id))
+        .filter(webhook::Column::CreatorId.eq(&query.creator_id))
+        .order_by_asc(webhook::Column::CreatedAt)
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(WebhookListResponse {
+        webhooks: webhooks.into_iter().map(Into::into).collect(),
+    }))
+}
+
+// 删除Webhook接口，投递记录随之删除
+pub async fn delete_webhook(
+    State(db): State<DatabaseConnection>,
+    Path(webhook_id): Path<String>,
+    Query(query): Query<CreatorQuery>,
+) -> Result<StatusCode, StatusCode> {
+    let hook = find_creator_webhook(&db, &webhook_id, &query.creator_id).await?;
+
+    hook.delete(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// 获取Webhook的投递日志
+pub async fn get_webhook_deliveries(
+    State(db): State<DatabaseConnection>,
+    Path(webhook_id): Path<String>,
+    Query(query): Query<DeliveryListQuery>,
+) -> Result<Json<WebhookDeliveryListResponse>, StatusCode> {
+    find_creator_webhook(&db, &webhook_id, &query.creator_id).await?;
+
+    let mut select = WebhookDelivery::find()
+        .filter(webhook_delivery::Column::WebhookId.eq(&webhook_id));
+    if let Some(status) = &query.status {
+        select = select.filter(webhook_delivery::Column::Status.eq(status));
+    }
+
+    let deliveries = select
+        .order_by_desc(webhook_delivery::Column::CreatedAt)
+        .limit(query.limit.unwrap_or(50).min(200))
+        .all(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(WebhookDeliveryListResponse {
+        deliveries: deliveries.into_iter().map(Into::into).collect(),
+    }))
+}
+
+// 重新投递接口，重置重试次数后交给后台任务立即投递
+pub async fn redeliver_webhook_delivery(
+    State(db): State<DatabaseConnection>,
+    Path(delivery_id): Path<String>,
+    Json(payload): Json<RedeliverRequest>,
+) -> Result<(StatusCode, Json<WebhookDeliveryResponse>), StatusCode> {
+    let delivery = WebhookDelivery::find_by_id(&delivery_id)
+        .one(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    find_creator_webhook(&db, &delivery.webhook_id, &payload.creator_id).await?;
+
+    let mut delivery_active: webhook_delivery::ActiveModel = delivery.into();
+    delivery_active.status = Set("pending".to_string());
+    delivery_active.attempts = Set(0);
+    delivery_active.next_attempt_at = Set(Some(chrono::Utc::now().into()));
+
+    let updated = delivery_active
+        .update(&db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok((StatusCode::ACCEPTED, Json(updated.into())))
+}
+
+// 查找创建者名下的Webhook
+async fn find_creator_webhook(
+    db: &DatabaseConnection,
+    webhook_id: &str,
+    creator_id: &str,
+) -> Result<webhook::Model, StatusCode> {
+    Webhook::find_by_id(webhook_id)
+        .filter(webhook::Column::CreatorId.eq(creator_id))
+        .one(db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_public_http_urls() {
+        assert!(is_allowed_url("https://example.com/hooks/taskview"));
+        assert!(is_allowed_url("http://93.184.216.34:8080/hook"));
+        assert!(is_allowed_url("https://[2606:2800:220:1::]/hook"));
+    }
+
+    #[test]
+    fn rejects_local_and_private_hosts() {
+        for url in [
+            "ftp://example.com/hook",
+            "not a url",
+            "http://localhost:8080/hook",
+            "http://api.localhost/hook",
+            "http://127.0.0.1/hook",
+            "http://2130706433/hook", // 127.0.0.1的十进制写法
+            "http://0.0.0.0/hook",
+            "http://10.1.2.3/hook",
+            "http://172.16.0.1/hook",
+            "http://192.168.1.10/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://[::1]/hook",
+            "http://[fd00::1]/hook",
+            "http://[fe80::1]/hook",
+            "http://[::ffff:127.0.0.1]/hook",
+        ] {
+            assert!(!is_allowed_url(url), "{}", url);
+        }
+    }
+}
@@ -0,0 +1,155 @@
+// RFC 5545 iCalendar 输出
+use crate::models::task;
+
+// 日历条目类型：VTODO支持完成状态，VEVENT兼容只显示事件的日历应用
+#[derive(Clone, Copy, PartialEq)]
+pub enum Component {
+    Todo,
+    Event,
+}
+
+// 生成日历，每个有截止时间的任务一个条目
+pub fn render(calendar_name: &str, uid_domain: &str, component: Component, tasks: &[task::Model]) -> String {
+    let now = format_utc(&chrono::Utc::now().into());
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//task-view//Task Calendar//ZH".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{}", escape_text(calendar_name)),
+    ];
+
+    for task in tasks {
+        let Some(due_date) = task.due_date else { continue };
+        let done = task.status_category == "finished";
+
+        match component {
+            Component::Todo => {
+                lines.push("BEGIN:VTODO".to_string());
+                lines.push(format!("UID:{}@{}", task.id, uid_domain));
+                lines.push(format!("DTSTAMP:{}", now));
+                lines.push(format!("SUMMARY:{}", escape_text(&task.title)));
+                lines.push(format!("DUE:{}", format_utc(&due_date)));
+                let status = match task.status_category.as_str() {
+                    "finished" => "COMPLETED",
+                    "active" => "IN-PROCESS",
+                    _ => "NEEDS-ACTION",
+                };
+                lines.push(format!("STATUS:{}", status));
+                if done {
+                    if let Some(completed_at) = task.completed_at {
+                        lines.push(format!("COMPLETED:{}", format_utc(&completed_at)));
+                    }
+                    lines.push("PERCENT-COMPLETE:100".to_string());
+                }
+            }
+            Component::Event => {
+                // VEVENT没有完成状态，已完成的任务在标题前加标记
+                let summary = if done { format!("✓ {}", task.title) } else { task.title.clone() };
+                lines.push("BEGIN:VEVENT".to_string());
+                lines.push(format!("UID:{}@{}", task.id, uid_domain));
+                lines.push(format!("DTSTAMP:{}", now));
+                lines.push(format!("SUMMARY:{}", escape_text(&summary)));
+                lines.push(format!("DTSTART:{}", format_utc(&due_date)));
+                lines.push("STATUS:CONFIRMED".to_string());
+                lines.push("TRANSP:TRANSPARENT".to_string());
+            }
+        }
+
+        if let Some(description) = &task.description {
+            lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        lines.push(format!("CREATED:{}", format_utc(&task.created_at)));
+        lines.push(format!("LAST-MODIFIED:{}", format_utc(&task.updated_at)));
+        lines.push(match component {
+            Component::Todo => "END:VTODO".to_string(),
+            Component::Event => "END:VEVENT".to_string(),
+        });
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut output = String::new();
+    for line in lines {
+        output.push_str(&fold_line(&line));
+        output.push_str("\r\n");
+    }
+    output
+}
+
+fn format_utc(dt: &chrono::DateTime<chrono::FixedOffset>) -> String {
+    dt.with_timezone(&chrono::Utc).format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+// 文本值中的反斜杠、分号、逗号和换行需要转义
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// 每行不超过75字节，超出部分折行并以空格开头，不拆开多字节字符
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut line_len = 0;
+    for c in line.chars() {
+        if line_len + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            line_len = 1;
+        }
+        folded.push(c);
+        line_len += c.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_text_values() {
+        assert_eq!(escape_text(r"a\b;c,d"), r"a\\b\;c\,d");
+        assert_eq!(escape_text("第一行\r\n第二行\n"), r"第一行\n第二行\n");
+        assert_eq!(escape_text("普通文本"), "普通文本");
+    }
+
+    #[test]
+    fn folds_lines_at_75_octets() {
+        let short = "S".repeat(75);
+        assert_eq!(fold_line(&short), short);
+
+        let long = "S".repeat(160);
+        let folded = fold_line(&long);
+        let lines: Vec<&str> = folded.split("\r\n").collect();
+        assert_eq!(lines.iter().map(|line| line.len()).collect::<Vec<_>>(), [75, 75, 12]);
+        assert!(lines[1..].iter().all(|line| line.starts_with(' ')));
+        assert_eq!(folded.replace("\r\n ", ""), long);
+    }
+
+    // 多字节字符不会被拆到两行
+    #[test]
+    fn folds_multibyte_text_on_character_boundaries() {
+        let text = format!("SUMMARY:{}", "任务标题".repeat(20));
+        let folded = fold_line(&text);
+
+        for line in folded.split("\r\n") {
+            assert!(line.len() <= 75);
+        }
+        // 首行前缀占8字节，再放22个汉字共74字节；续行以空格开头，放24个汉字共73字节
+        let lines: Vec<&str> = folded.split("\r\n").collect();
+        assert_eq!(lines[0].len(), 8 + 22 * 3);
+        assert_eq!(lines[1].len(), 1 + 24 * 3);
+        assert_eq!(folded.replace("\r\n ", ""), text);
+    }
+}
@@ -0,0 +1,206 @@
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+use super::{parse_timestamp, string_field, ExternalBoard, ExternalComment, ExternalLabel, ExternalTask, SkippedItem};
+
+// Jira状态分类与本系统状态的对应关系
+fn status_category(key: &str) -> Option<&'static str> {
+    match key {
+        "new" => Some("todo"),
+        "indeterminate" => Some("in_progress"),
+        "done" => Some("done"),
+        _ => None,
+    }
+}
+
+// 描述和评论在API v2中是纯文本，在v3中是ADF文档，统一提取为文本
+fn rich_text(value: &Value) -> Option<String> {
+    fn collect(node: &Value, out: &mut String) {
+        if let Some(text) = node.get("text").and_then(Value::as_str) {
+            out.push_str(text);
+        }
+        if node.get("type").and_then(Value::as_str) == Some("hardBreak") {
+            out.push('\n');
+        }
+        if let Some(children) = node.get("content").and_then(Value::as_array) {
+            for child in children {
+                collect(child, out);
+            }
+            if matches!(node.get("type").and_then(Value::as_str), Some("paragraph" | "heading" | "listItem")) {
+                out.push('\n');
+            }
+        }
+    }
+
+    let text = match value {
+        Value::String(s) => s.clone(),
+        Value::Object(_) => {
+            let mut out = String::new();
+            collect(value, &mut out);
+            out
+        }
+        _ => return None,
+    };
+    let text = text.trim().to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+// 解析Jira问题的JSON导出（REST API search接口的返回，或其中的issues数组）
+pub fn parse(data: &Value) -> Result<ExternalBoard, String> {
+    let issues = match data {
+        Value::Array(issues) => issues,
+        _ => data
+            .get("issues")
+            .and_then(Value::as_array)
+            .ok_or("不是Jira导出文件：缺少issues")?,
+    };
+
+    let mut tasks = Vec::new();
+    let mut skipped = Vec::new();
+    let mut label_names = BTreeSet::new();
+    let mut project_name = None;
+
+    for issue in issues {
+        let key = string_field(issue, "key")
+            .or_else(|| string_field(issue, "id"))
+            .unwrap_or_default();
+        let Some(fields) = issue.get("fields") else {
+            skipped.push(SkippedItem { external_id: key, title: None, reason: "缺少fields".to_string() });
+            continue;
+        };
+        if project_name.is_none() {
+            project_name = fields.get("project").and_then(|project| string_field(project, "name"));
+        }
+
+        let status = fields.get("status");
+        let column = status
+            .and_then(|status| string_field(status, "name"))
+            .unwrap_or_else(|| "未知状态".to_string());
+        let category = status
+            .and_then(|status| status.pointer("/statusCategory/key"))
+            .and_then(Value::as_str)
+            .and_then(status_category);
+
+        let labels: Vec<String> = fields
+            .get("labels")
+            .and_then(Value::as_array)
+            .map(|labels| labels.iter().filter_map(Value::as_str).map(str::to_string).collect())
+            .unwrap_or_default();
+        label_names.extend(labels.iter().cloned());
+
+        let mut comments: Vec<ExternalComment> = fields
+            .pointer("/comment/comments")
+            .and_then(Value::as_array)
+            .map(|comments| {
+                comments
+                    .iter()
+                    .filter_map(|comment| {
+                        Some(ExternalComment {
+                            author: comment.get("author").and_then(|author| string_field(author, "displayName")),
+                            body: rich_text(comment.get("body")?)?,
+                            created_at: comment.get("created").and_then(parse_timestamp),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        comments.sort_by_key(|comment| comment.created_at);
+
+        tasks.push(ExternalTask {
+            title: string_field(fields, "summary").unwrap_or_default(),
+            description: fields.get("description").and_then(rich_text),
+            column,
+            category,
+            labels,
+            created_at: fields.get("created").and_then(parse_timestamp),
+            completed_at: fields.get("resolutiondate").and_then(parse_timestamp),
+            due_date: fields.get("duedate").and_then(parse_timestamp),
+            comments,
+            external_id: key,
+        });
+    }
+
+    Ok(ExternalBoard {
+        name: project_name.unwrap_or_else(|| "Jira项目".to_string()),
+        tasks,
+        labels: label_names.into_iter().map(|name| ExternalLabel { name, color: None }).collect(),
+        skipped,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    use crate::importers::map_status;
+
+    #[test]
+    fn parses_issues_labels_and_comments() {
+        let data = json!({
+            "issues": [
+                {
+                    "key": "APP-1",
+                    "fields": {
+                        "summary": "登录页面",
+                        "project": { "name": "应用" },
+                        "status": { "name": "Code Review", "statusCategory": { "key": "indeterminate" } },
+                        "labels": ["frontend", "urgent"],
+                        "description": {
+                            "type": "doc",
+                            "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "实现登录" }] }],
+                        },
+                        "created": "2025-09-01T10:00:00.000+0800",
+                        "comment": { "comments": [
+                            { "author": { "displayName": "张三" }, "body": "第二条", "created": "2025-09-03T10:00:00.000+0000" },
+                            { "author": { "displayName": "李四" }, "body": "第一条", "created": "2025-09-02T10:00:00.000+0000" },
+                        ] },
+                    },
+                },
+                {
+                    "key": "APP-2",
+                    "fields": {
+                        "summary": "发布",
+                        "status": { "name": "Shipped", "statusCategory": { "key": "done" } },
+                        "labels": ["frontend"],
+                        "resolutiondate": "2025-09-05T12:00:00.000+0000",
+                    },
+                },
+                { "key": "APP-3" },
+            ],
+        });
+
+        let board = parse(&data).unwrap();
+        assert_eq!(board.name, "应用");
+        let labels: Vec<&str> = board.labels.iter().map(|label| label.name.as_str()).collect();
+        assert_eq!(labels, ["frontend", "urgent"]);
+
+        assert_eq!(board.tasks.len(), 2);
+        let login = &board.tasks[0];
+        assert_eq!(login.external_id, "APP-1");
+        assert_eq!(login.labels, ["frontend", "urgent"]);
+        assert_eq!(login.description.as_deref(), Some("实现登录"));
+        assert_eq!(login.created_at.unwrap().to_rfc3339(), "2025-09-01T02:00:00+00:00");
+        let comments: Vec<(&str, Option<&str>)> =
+            login.comments.iter().map(|comment| (comment.body.as_str(), comment.author.as_deref())).collect();
+        assert_eq!(comments, [("第一条", Some("李四")), ("第二条", Some("张三"))]);
+
+        // 状态分类优先于列名，显式映射优先于状态分类
+        let overrides = HashMap::new();
+        assert_eq!(map_status(&login.column, login.category, &overrides), "in_progress");
+        let release = &board.tasks[1];
+        assert_eq!(map_status(&release.column, release.category, &overrides), "done");
+        let overrides = HashMap::from([("Shipped".to_string(), "in_review".to_string())]);
+        assert_eq!(map_status(&release.column, release.category, &overrides), "in_review");
+
+        assert_eq!(board.skipped.len(), 1);
+        assert_eq!(board.skipped[0].external_id, "APP-3");
+    }
+
+    #[test]
+    fn rejects_files_without_issues() {
+        assert!(parse(&json!({ "projects": [] })).is_err());
+        assert!(parse(&json!("issues")).is_err());
+    }
+}
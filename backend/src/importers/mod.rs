@@ -0,0 +1,91 @@
+// 外部看板导出文件的解析，统一转换为ExternalBoard后由导入接口写入
+pub mod jira;
+pub mod trello;
+
+use serde_json::Value;
+use std::collections::HashMap;
+
+pub type Timestamp = chrono::DateTime<chrono::FixedOffset>;
+
+pub struct ExternalBoard {
+    pub name: String,
+    pub tasks: Vec<ExternalTask>,
+    pub labels: Vec<ExternalLabel>,
+    pub skipped: Vec<SkippedItem>,
+}
+
+pub struct ExternalTask {
+    pub external_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub column: String,                       // 外部列名或状态名
+    pub category: Option<&'static str>,       // 外部数据自带的状态分类，已映射为本系统状态
+    pub labels: Vec<String>,
+    pub created_at: Option<Timestamp>,
+    pub completed_at: Option<Timestamp>,
+    pub due_date: Option<Timestamp>,
+    pub comments: Vec<ExternalComment>,
+}
+
+pub struct ExternalLabel {
+    pub name: String,
+    pub color: Option<String>,
+}
+
+pub struct ExternalComment {
+    pub author: Option<String>,
+    pub body: String,
+    pub created_at: Option<Timestamp>,
+}
+
+pub struct SkippedItem {
+    pub external_id: String,
+    pub title: Option<String>,
+    pub reason: String,
+}
+
+// 按导入请求指定的映射或列名推断本系统状态，优先级：显式映射 > 外部分类 > 列名关键字
+pub fn map_status(column: &str, category: Option<&'static str>, overrides: &HashMap<String, String>) -> String {
+    if let Some(status) = overrides.get(column) {
+        return status.clone();
+    }
+    if let Some(status) = category {
+        return status.to_string();
+    }
+
+    let name = column.to_lowercase();
+    const DONE: &[&str] = &["done", "complete", "closed", "resolved", "finished", "完成", "已完成", "关闭"];
+    const IN_PROGRESS: &[&str] = &["progress", "doing", "review", "testing", "active", "进行", "处理中", "开发中", "测试"];
+    if DONE.iter().any(|keyword| name.contains(keyword)) {
+        "done".to_string()
+    } else if IN_PROGRESS.iter().any(|keyword| name.contains(keyword)) {
+        "in_progress".to_string()
+    } else {
+        "todo".to_string()
+    }
+}
+
+// 解析外部时间，支持RFC3339、Jira的 +0000 时区格式和纯日期
+pub fn parse_timestamp(value: &Value) -> Option<Timestamp> {
+    let value = value.as_str()?.trim();
+    // 统一转换为UTC保存，与系统生成的时间可以直接比较
+    chrono::DateTime::parse_from_rfc3339(value)
+        .or_else(|_| chrono::DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f%z"))
+        .ok()
+        .map(|dt| dt.with_timezone(&chrono::Utc).into())
+        .or_else(|| {
+            chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+                .map(|dt| dt.and_utc().into())
+        })
+}
+
+pub fn string_field(value: &Value, key: &str) -> Option<String> {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
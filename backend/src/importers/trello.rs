@@ -0,0 +1,228 @@
+use serde_json::Value;
+use std::collections::HashMap;
+
+use super::{parse_timestamp, string_field, ExternalBoard, ExternalComment, ExternalLabel, ExternalTask, SkippedItem, Timestamp};
+
+// Trello卡片ID的前8位是创建时间的Unix时间戳（十六进制）
+fn created_at_from_id(id: &str) -> Option<Timestamp> {
+    let secs = i64::from_str_radix(id.get(0..8)?, 16).ok()?;
+    chrono::DateTime::from_timestamp(secs, 0).map(Into::into)
+}
+
+// Trello标签可以没有名称，此时用颜色作为名称
+fn label_name(label: &Value) -> Option<String> {
+    string_field(label, "name").or_else(|| string_field(label, "color"))
+}
+
+// 解析Trello看板导出的JSON（看板菜单 → 打印和导出 → 导出为JSON）
+pub fn parse(data: &Value) -> Result<ExternalBoard, String> {
+    let cards = data
+        .get("cards")
+        .and_then(Value::as_array)
+        .ok_or("不是Trello导出文件：缺少cards")?;
+    let empty = Vec::new();
+
+    let lists: HashMap<&str, (&str, bool)> = data
+        .get("lists")
+        .and_then(Value::as_array)
+        .unwrap_or(&empty)
+        .iter()
+        .filter_map(|list| {
+            let id = list.get("id")?.as_str()?;
+            let name = list.get("name").and_then(Value::as_str).unwrap_or("");
+            let closed = list.get("closed").and_then(Value::as_bool).unwrap_or(false);
+            Some((id, (name, closed)))
+        })
+        .collect();
+
+    let labels: Vec<ExternalLabel> = data
+        .get("labels")
+        .and_then(Value::as_array)
+        .unwrap_or(&empty)
+        .iter()
+        .filter_map(|label| {
+            Some(ExternalLabel {
+                name: label_name(label)?,
+                color: string_field(label, "color"),
+            })
+        })
+        .collect();
+
+    // 按卡片归集动作：创建时间、移动到当前列的时间和评论
+    let mut created: HashMap<&str, Timestamp> = HashMap::new();
+    let mut moved: HashMap<(&str, &str), Timestamp> = HashMap::new();
+    let mut comments: HashMap<&str, Vec<ExternalComment>> = HashMap::new();
+    for action in data.get("actions").and_then(Value::as_array).unwrap_or(&empty) {
+        let Some(card_id) = action.pointer("/data/card/id").and_then(Value::as_str) else { continue };
+        let date = action.get("date").and_then(parse_timestamp);
+        match action.get("type").and_then(Value::as_str) {
+            Some("createCard") | Some("copyCard") | Some("convertToCardFromCheckItem") => {
+                if let Some(date) = date {
+                    created.insert(card_id, date);
+                }
+            }
+            Some("updateCard") => {
+                let Some(list_id) = action.pointer("/data/listAfter/id").and_then(Value::as_str) else { continue };
+                if let Some(date) = date {
+                    let entry = moved.entry((card_id, list_id)).or_insert(date);
+                    *entry = (*entry).max(date);
+                }
+            }
+            Some("commentCard") => {
+                let Some(body) = action.pointer("/data/text").and_then(Value::as_str) else { continue };
+                let author = action
+                    .get("memberCreator")
+                    .and_then(|member| string_field(member, "fullName").or_else(|| string_field(member, "username")));
+                comments.entry(card_id).or_default().push(ExternalComment {
+                    author,
+                    body: body.to_string(),
+                    created_at: date,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    let mut tasks = Vec::new();
+    let mut skipped = Vec::new();
+    for card in cards {
+        let Some(id) = card.get("id").and_then(Value::as_str) else { continue };
+        let title = string_field(card, "name");
+
+        if card.get("closed").and_then(Value::as_bool).unwrap_or(false) {
+            skipped.push(SkippedItem { external_id: id.to_string(), title, reason: "卡片已归档".to_string() });
+            continue;
+        }
+        let list_id = card.get("idList").and_then(Value::as_str).unwrap_or("");
+        let Some(&(column, list_closed)) = lists.get(list_id) else {
+            skipped.push(SkippedItem { external_id: id.to_string(), title, reason: "找不到卡片所在的列表".to_string() });
+            continue;
+        };
+        if list_closed {
+            skipped.push(SkippedItem { external_id: id.to_string(), title, reason: format!("列表「{}」已归档", column) });
+            continue;
+        }
+
+        let card_labels = card
+            .get("labels")
+            .and_then(Value::as_array)
+            .unwrap_or(&empty)
+            .iter()
+            .filter_map(label_name)
+            .collect();
+
+        let mut card_comments = comments.remove(id).unwrap_or_default();
+        card_comments.sort_by_key(|comment| comment.created_at);
+
+        tasks.push(ExternalTask {
+            external_id: id.to_string(),
+            title: title.unwrap_or_default(),
+            description: string_field(card, "desc"),
+            column: column.to_string(),
+            category: None,
+            labels: card_labels,
+            created_at: created.get(id).copied().or_else(|| created_at_from_id(id)),
+            // 完成时间取最后一次移入当前列的时间，没有记录时取最后活动时间
+            completed_at: moved
+                .get(&(id, list_id))
+                .copied()
+                .or_else(|| card.get("dateLastActivity").and_then(parse_timestamp)),
+            due_date: card.get("due").and_then(parse_timestamp),
+            comments: card_comments,
+        });
+    }
+
+    Ok(ExternalBoard {
+        name: string_field(data, "name").unwrap_or_else(|| "Trello看板".to_string()),
+        tasks,
+        labels,
+        skipped,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    use crate::importers::map_status;
+
+    #[test]
+    fn parses_cards_labels_and_comments() {
+        let data = json!({
+            "name": "产品迭代",
+            "lists": [
+                { "id": "list-todo", "name": "To Do", "closed": false },
+                { "id": "list-doing", "name": "Doing", "closed": false },
+                { "id": "list-done", "name": "Done", "closed": false },
+                { "id": "list-old", "name": "Old", "closed": true },
+            ],
+            "labels": [
+                { "name": "前端", "color": "green" },
+                { "name": "", "color": "red" },
+            ],
+            "cards": [
+                {
+                    "id": "5f5e10000000000000000001",
+                    "name": "登录页面",
+                    "desc": "实现登录",
+                    "idList": "list-doing",
+                    "labels": [{ "name": "前端", "color": "green" }, { "name": "", "color": "red" }],
+                },
+                { "id": "5f5e10000000000000000002", "name": "发布", "idList": "list-done", "labels": [] },
+                { "id": "5f5e10000000000000000003", "name": "已归档", "idList": "list-todo", "closed": true },
+                { "id": "5f5e10000000000000000004", "name": "旧列表", "idList": "list-old" },
+            ],
+            "actions": [
+                {
+                    "type": "commentCard",
+                    "date": "2025-09-02T10:00:00.000Z",
+                    "data": { "card": { "id": "5f5e10000000000000000001" }, "text": "第二条" },
+                    "memberCreator": { "fullName": "张三" },
+                },
+                {
+                    "type": "commentCard",
+                    "date": "2025-09-01T10:00:00.000Z",
+                    "data": { "card": { "id": "5f5e10000000000000000001" }, "text": "第一条" },
+                    "memberCreator": { "username": "lisi" },
+                },
+                {
+                    "type": "updateCard",
+                    "date": "2025-09-03T08:00:00.000Z",
+                    "data": { "card": { "id": "5f5e10000000000000000002" }, "listAfter": { "id": "list-done" } },
+                },
+            ],
+        });
+
+        let board = parse(&data).unwrap();
+        assert_eq!(board.name, "产品迭代");
+        // 没有名称的标签用颜色作为名称
+        let labels: Vec<&str> = board.labels.iter().map(|label| label.name.as_str()).collect();
+        assert_eq!(labels, ["前端", "red"]);
+
+        assert_eq!(board.tasks.len(), 2);
+        let login = &board.tasks[0];
+        assert_eq!(login.labels, ["前端", "red"]);
+        assert_eq!(login.description.as_deref(), Some("实现登录"));
+        let comments: Vec<(&str, Option<&str>)> =
+            login.comments.iter().map(|comment| (comment.body.as_str(), comment.author.as_deref())).collect();
+        assert_eq!(comments, [("第一条", Some("lisi")), ("第二条", Some("张三"))]);
+        // 没有创建动作时从卡片ID中取创建时间
+        assert_eq!(login.created_at.unwrap().timestamp(), 0x5f5e1000);
+
+        let overrides = HashMap::new();
+        assert_eq!(map_status(&login.column, login.category, &overrides), "in_progress");
+        let release = &board.tasks[1];
+        assert_eq!(map_status(&release.column, release.category, &overrides), "done");
+        assert_eq!(release.completed_at.unwrap().to_rfc3339(), "2025-09-03T08:00:00+00:00");
+
+        let skipped: Vec<&str> = board.skipped.iter().map(|item| item.reason.as_str()).collect();
+        assert_eq!(skipped, ["卡片已归档", "列表「Old」已归档"]);
+    }
+
+    #[test]
+    fn rejects_files_without_cards() {
+        assert!(parse(&json!({ "name": "看板" })).is_err());
+        assert!(parse(&json!([])).is_err());
+    }
+}
@@ -0,0 +1,610 @@
+use lettre::{
+    message::{header::ContentType, Mailbox},
+    transport::smtp::authentication::Credentials,
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+use sea_orm::{ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set};
+
+use crate::config::{Config, SmtpConfig};
+use crate::events::Event;
+use crate::handlers::column::REVIEW_STATUS;
+use crate::models::{
+    email_outbox, email_outbox::Entity as EmailOutbox,
+    invite, invite::Entity as Invite,
+    notification_preference, notification_preference::Entity as NotificationPreference,
+    task, task::Entity as Task,
+    user, user::Entity as User,
+};
+use crate::retry;
+
+type SmtpTransport = AsyncSmtpTransport<Tokio1Executor>;
+
+// 单次轮询最多发送的邮件数
+const BATCH_SIZE: u64 = 20;
+
+// 渲染后的邮件内容
+pub struct EmailTemplate {
+    pub subject: String,
+    pub body: String,
+}
+
+// 邀请码被使用，通知创建者
+fn invite_redeemed_email(creator: &user::Model, executor: &user::Model, invite: &invite::Model) -> EmailTemplate {
+    EmailTemplate {
+        subject: format!("[TaskView] {} 已加入看板 {}", executor.username, invite.code),
+        body: format!(
+            "{}，你好：\n\n执行者 {}（{}）已使用邀请码 {} 加入你的看板，现在可以为其分配任务了。\n\n—— TaskView",
+            creator.username, executor.username, executor.email, invite.code
+        ),
+    }
+}
+
+// 任务完成，通知创建者
+fn task_completed_email(creator: &user::Model, executor_name: &str, task: &task::Model) -> EmailTemplate {
+    let details = task
+        .completion_details
+        .as_deref()
+        .map(|details| format!("\n完成详情：\n{}\n", details))
+        .unwrap_or_default();
+
+    // 待验收的任务提醒创建者验收
+    let (subject, action) = if task.status == REVIEW_STATUS {
+        ("已提交验收", "已提交任务")
+    } else {
+        ("已完成", "已完成任务")
+    };
+
+    EmailTemplate {
+        subject: format!("[TaskView] 任务「{}」{}", task.title, subject),
+        body: format!(
+            "{}，你好：\n\n{} {}「{}」。\n{}\n—— TaskView",
+            creator.username, executor_name, action, task.title, details
+        ),
+    }
+}
+
+// 任务逾期提醒
+fn task_overdue_email(recipient: &user::Model, task: &task::Model) -> EmailTemplate {
+    let due_date = task
+        .due_date
+        .map(|dt| dt.format("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_default();
+
+    EmailTemplate {
+        subject: format!("[TaskView] 任务「{}」已逾期", task.title),
+        body: format!(
+            "{}，你好：\n\n任务「{}」的截止时间为 {}，目前状态为 {}，已经逾期。\n\n—— TaskView",
+            recipient.username, task.title, due_date, task.status
+        ),
+    }
+}
+
+// 每日摘要
+fn daily_digest_email(recipient: &user::Model, sections: &[String]) -> EmailTemplate {
+    EmailTemplate {
+        subject: format!("[TaskView] 每日摘要 {}", chrono::Utc::now().format("%Y-%m-%d")),
+        body: format!(
+            "{}，你好：\n\n以下是你所在看板的任务概况：\n\n{}\n—— TaskView",
+            recipient.username,
+            sections.join("\n")
+        ),
+    }
+}
+
+// 根据事件写入发件箱，未配置SMTP时忽略
+pub async fn enqueue_for_event(db: &DatabaseConnection, config: &Config, event: &Event) -> Result<(), DbErr> {
+    if config.smtp.is_none() {
+        return Ok(());
+    }
+
+    match event {
+        Event::InviteUsed { invite } => {
+            let Some(executor_id) = &invite.executor_id else {
+                return Ok(());
+            };
+            let (Some(creator), Some(executor)) = (
+                User::find_by_id(&invite.creator_id).one(db).await?,
+                User::find_by_id(executor_id).one(db).await?,
+            ) else {
+                return Ok(());
+            };
+
+            if preferences(db, &creator.id).await?.email_invite_redeemed {
+                let email = invite_redeemed_email(&creator, &executor, invite);
+                enqueue(db, &creator, "invite_redeemed", email).await?;
+            }
+        }
+        // 提交验收时通知创建者，验收通过由创建者本人操作，不再重复通知
+        Event::TaskStatusChanged { task, old_status, .. }
+            if task.status == REVIEW_STATUS || (task.status_category == "finished" && old_status != REVIEW_STATUS) =>
+        {
+            let Some(creator) = User::find_by_id(&task.creator_id).one(db).await? else {
+                return Ok(());
+            };
+            if !preferences(db, &creator.id).await?.email_task_completed {
+                return Ok(());
+            }
+
+            let executor_name = match task_executor(db, task).await? {
+                Some(executor) => executor.username,
+                None => "执行者".to_string(),
+            };
+            let email = task_completed_email(&creator, &executor_name, task);
+            enqueue(db, &creator, "task_completed", email).await?;
+        }
+        Event::TaskOverdue { task } => {
+            let mut recipients = Vec::new();
+            recipients.extend(User::find_by_id(&task.creator_id).one(db).await?);
+            recipients.extend(task_executor(db, task).await?);
+
+            for recipient in &recipients {
+                if preferences(db, &recipient.id).await?.email_task_overdue {
+                    enqueue(db, recipient, "task_overdue", task_overdue_email(recipient, task)).await?;
+                }
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+// 用户的通知偏好，未设置时返回默认值
+pub async fn preferences(db: &DatabaseConnection, user_id: &str) -> Result<notification_preference::Model, DbErr> {
+    Ok(NotificationPreference::find_by_id(user_id)
+        .one(db)
+        .await?
+        .unwrap_or_else(|| notification_preference::Model::default_for(user_id)))
+}
+
+// 写入发件箱
+async fn enqueue(db: &DatabaseConnection, recipient: &user::Model, template: &str, email: EmailTemplate) -> Result<(), DbErr> {
+    let now = chrono::Utc::now();
+    db.execute(sea_orm::Statement::from_sql_and_values(
+        sea_orm::DatabaseBackend::Sqlite,
+        r#"
+        INSERT INTO email_outbox (id, user_id, to_address, template, subject, body, status, attempts, next_attempt_at, created_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+        [
+            uuid::Uuid::new_v4().to_string().into(),
+            recipient.id.clone().into(),
+            recipient.email.clone().into(),
+            template.into(),
+            email.subject.into(),
+            email.body.into(),
+            "pending".into(),
+            0.into(),
+            now.into(),
+            now.into(),
+        ],
+    ))
+    .await?;
+
+    Ok(())
+}
+
+// 任务的执行者，任务未指定时取看板的执行者
+async fn task_executor(db: &DatabaseConnection, task: &task::Model) -> Result<Option<user::Model>, DbErr> {
+    let executor_id = match &task.executor_id {
+        Some(executor_id) => Some(executor_id.clone()),
+        None => Invite::find_by_id(&task.invite_id)
+            .one(db)
+            .await?
+            .and_then(|invite| invite.executor_id),
+    };
+
+    match executor_id {
+        Some(executor_id) => User::find_by_id(executor_id).one(db).await,
+        None => Ok(None),
+    }
+}
+
+// SMTP发送器
+pub struct Mailer {
+    transport: SmtpTransport,
+    from: Mailbox,
+}
+
+impl Mailer {
+    // 根据配置创建发送器，未配置SMTP或配置无效时返回None
+    pub fn from_config(config: &Config) -> Option<Self> {
+        let Some(smtp) = &config.smtp else {
+            tracing::info!("未配置SMTP_HOST，邮件通知已关闭");
+            return None;
+        };
+
+        match build_transport(smtp) {
+            Ok((transport, from)) => Some(Mailer { transport, from }),
+            Err(e) => {
+                tracing::error!("SMTP配置无效，邮件通知已关闭: {}", e);
+                None
+            }
+        }
+    }
+}
+
+fn build_transport(smtp: &SmtpConfig) -> Result<(SmtpTransport, Mailbox), String> {
+    let builder = match smtp.tls.as_str() {
+        "tls" => SmtpTransport::relay(&smtp.host).map_err(|e| e.to_string())?,
+        "starttls" => SmtpTransport::starttls_relay(&smtp.host).map_err(|e| e.to_string())?,
+        "none" => SmtpTransport::builder_dangerous(&smtp.host),
+        other => return Err(format!("未知的SMTP_TLS取值: {}", other)),
+    };
+
+    let builder = match (&smtp.username, &smtp.password) {
+        (Some(username), Some(password)) => {
+            builder.credentials(Credentials::new(username.clone(), password.clone()))
+        }
+        _ => builder,
+    };
+
+    let from = smtp.from.parse::<Mailbox>().map_err(|e| e.to_string())?;
+    Ok((builder.port(smtp.port).build(), from))
+}
+
+// 发送到期的邮件
+pub async fn send_due(db: &DatabaseConnection, mailer: &Mailer, max_attempts: i32) -> Result<(), DbErr> {
+    let now: chrono::DateTime<chrono::FixedOffset> = chrono::Utc::now().into();
+    let emails = EmailOutbox::find()
+        .filter(email_outbox::Column::Status.eq("pending"))
+        .filter(email_outbox::Column::NextAttemptAt.lte(now))
+        .order_by_asc(email_outbox::Column::NextAttemptAt)
+        .limit(BATCH_SIZE)
+        .all(db)
+        .await?;
+
+    for email in emails {
+        let result = match build_message(&mailer.from, &email) {
+            Ok(message) => mailer.transport.send(message).await.map(|_| ()).map_err(|e| (e.is_permanent(), e.to_string())),
+            Err(e) => Err((true, e)),
+        };
+
+        let attempts = email.attempts + 1;
+        let now = chrono::Utc::now();
+        let mut email_active: email_outbox::ActiveModel = email.into();
+        email_active.attempts = Set(attempts);
+
+        match result {
+            Ok(()) => {
+                email_active.status = Set("sent".to_string());
+                email_active.last_error = Set(None);
+                email_active.next_attempt_at = Set(None);
+                email_active.sent_at = Set(Some(now.into()));
+            }
+            Err((permanent, error)) => {
+                tracing::warn!("邮件发送失败（第{}次）: {}", attempts, error);
+                email_active.last_error = Set(Some(error));
+                if permanent || attempts >= max_attempts {
+                    email_active.status = Set("failed".to_string());
+                    email_active.next_attempt_at = Set(None);
+                } else {
+                    let next_attempt_at = now + chrono::Duration::seconds(retry::backoff_secs(attempts));
+                    email_active.next_attempt_at = Set(Some(next_attempt_at.into()));
+                }
+            }
+        }
+
+        email_active.update(db).await?;
+    }
+
+    Ok(())
+}
+
+fn build_message(from: &Mailbox, email: &email_outbox::Model) -> Result<Message, String> {
+    let to = email.to_address.parse::<Mailbox>().map_err(|e| e.to_string())?;
+    Message::builder()
+        .from(from.clone())
+        .to(to)
+        .subject(email.subject.clone())
+        .header(ContentType::TEXT_PLAIN)
+        .body(email.body.clone())
+        .map_err(|e| e.to_string())
+}
+
+// 为开启每日摘要且到达发送时间的用户生成摘要，每天只发送一次
+pub async fn send_daily_digests(db: &DatabaseConnection) -> Result<(), DbErr> {
+    let now = chrono::Utc::now();
+    let today = now.date_naive();
+    let hour = now.format("%H").to_string().parse::<i32>().unwrap_or(0);
+
+    let subscribers = NotificationPreference::find()
+        .filter(notification_preference::Column::DailyDigest.eq(true))
+        .filter(notification_preference::Column::DigestHour.lte(hour))
+        .all(db)
+        .await?;
+
+    for preference in subscribers {
+        if preference.last_digest_at.is_some_and(|at| at.date_naive() >= today) {
+            continue;
+        }
+        let Some(recipient) = User::find_by_id(&preference.user_id).one(db).await? else {
+            continue;
+        };
+
+        let sections = digest_sections(db, &recipient.id, now).await?;
+        if !sections.is_empty() {
+            enqueue(db, &recipient, "daily_digest", daily_digest_email(&recipient, &sections)).await?;
+        }
+
+        let mut preference_active: notification_preference::ActiveModel = preference.into();
+        preference_active.last_digest_at = Set(Some(now.into()));
+        preference_active.update(db).await?;
+    }
+
+    Ok(())
+}
+
+// 按看板汇总未完成、逾期和最近24小时完成的任务
+async fn digest_sections(db: &DatabaseConnection, user_id: &str, now: chrono::DateTime<chrono::Utc>) -> Result<Vec<String>, DbErr> {
+    let invites = Invite::find()
+        .filter(
+            Condition::any()
+                .add(invite::Column::CreatorId.eq(user_id))
+                .add(invite::Column::ExecutorId.eq(user_id)),
+        )
+        .filter(invite::Column::ArchivedAt.is_null())
+        .all(db)
+        .await?;
+
+    let since = now - chrono::Duration::hours(24);
+    let mut sections = Vec::new();
+    for invite in invites {
+        let tasks = Task::find()
+            .filter(task::Column::DeletedAt.is_null())
+            .filter(task::Column::ArchivedAt.is_null())
+            .filter(task::Column::InviteId.eq(&invite.id))
+            .all(db)
+            .await?;
+
+        let open: Vec<&task::Model> = tasks.iter().filter(|t| t.status_category != "finished").collect();
+        let overdue = open
+            .iter()
+            .filter(|t| t.due_date.is_some_and(|due| due < now))
+            .count();
+        let completed: Vec<&task::Model> = tasks
+            .iter()
+            .filter(|t| t.status_category == "finished" && t.completed_at.is_some_and(|at| at >= since))
+            .collect();
+
+        if open.is_empty() && completed.is_empty() {
+            continue;
+        }
+
+        let mut section = format!(
+            "看板 {}：未完成 {} 个（逾期 {} 个），最近24小时完成 {} 个\n",
+            invite.code,
+            open.len(),
+            overdue,
+            completed.len()
+        );
+        for task in &open {
+            section.push_str(&format!("  - [{}] {}\n", task.status, task.title));
+        }
+        for task in &completed {
+            section.push_str(&format!("  - [已完成] {}\n", task.title));
+        }
+        sections.push(section);
+    }
+
+    Ok(sections)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::Engine;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    use crate::test_util::{create_board, create_task, create_user, test_config, test_db};
+
+    // SMTP替身收到的邮件
+    #[derive(Clone, Default)]
+    struct Captured {
+        mail_from: String,
+        rcpt_to: Vec<String>,
+        data: String,
+    }
+
+    type Inbox = Arc<Mutex<Vec<Captured>>>;
+
+    // 本地SMTP替身：接收所有邮件，指定拒绝回复时拒绝收件人
+    async fn smtp_catcher(rcpt_reply: Option<&'static str>) -> (u16, Inbox) {
+        let inbox: Inbox = Arc::default();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let received = inbox.clone();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let (reader, mut writer) = stream.into_split();
+                let mut lines = BufReader::new(reader).lines();
+                let mut mail = Captured::default();
+                writer.write_all(b"220 catcher ESMTP\r\n").await.unwrap();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    let command = line.to_ascii_uppercase();
+                    let reply = if command.starts_with("EHLO") || command.starts_with("HELO") {
+                        "250 catcher"
+                    } else if command.starts_with("MAIL FROM:") {
+                        mail.mail_from = line[10..].to_string();
+                        "250 OK"
+                    } else if command.starts_with("RCPT TO:") {
+                        match rcpt_reply {
+                            Some(reply) => reply,
+                            None => {
+                                mail.rcpt_to.push(line[8..].to_string());
+                                "250 OK"
+                            }
+                        }
+                    } else if command == "DATA" {
+                        writer.write_all(b"354 End data with <CR><LF>.<CR><LF>\r\n").await.unwrap();
+                        while let Ok(Some(line)) = lines.next_line().await {
+                            if line == "." {
+                                break;
+                            }
+                            mail.data.push_str(line.strip_prefix('.').unwrap_or(&line));
+                            mail.data.push_str("\r\n");
+                        }
+                        received.lock().unwrap().push(std::mem::take(&mut mail));
+                        "250 OK queued"
+                    } else if command == "QUIT" {
+                        writer.write_all(b"221 Bye\r\n").await.unwrap();
+                        break;
+                    } else {
+                        "250 OK"
+                    };
+                    writer.write_all(format!("{}\r\n", reply).as_bytes()).await.unwrap();
+                }
+            }
+        });
+        (port, inbox)
+    }
+
+    fn smtp_config(port: u16) -> Config {
+        let mut config = test_config();
+        config.smtp = Some(SmtpConfig {
+            host: "127.0.0.1".to_string(),
+            port,
+            username: None,
+            password: None,
+            from: "TaskView <noreply@taskview.local>".to_string(),
+            tls: "none".to_string(),
+        });
+        config
+    }
+
+    // 解码邮件正文
+    fn decoded_body(data: &str) -> String {
+        let (headers, body) = data.split_once("\r\n\r\n").unwrap();
+        assert!(headers.contains("Content-Transfer-Encoding: base64"), "{}", headers);
+        let encoded: String = body.split_whitespace().collect();
+        String::from_utf8(base64::engine::general_purpose::STANDARD.decode(encoded).unwrap()).unwrap()
+    }
+
+    async fn outbox(db: &DatabaseConnection) -> Vec<email_outbox::Model> {
+        EmailOutbox::find().all(db).await.unwrap()
+    }
+
+    // 执行者加入看板，返回创建者ID和邀请码
+    async fn redeem(db: &DatabaseConnection, config: &Config) -> String {
+        let creator_id = create_user(db, "creator", "creator").await;
+        let executor_id = create_user(db, "executor", "executor").await;
+        let invite_id = create_board(db, &creator_id, Some(&executor_id)).await;
+        let invite = Invite::find_by_id(&invite_id).one(db).await.unwrap().unwrap();
+        enqueue_for_event(db, config, &Event::InviteUsed { invite }).await.unwrap();
+        creator_id
+    }
+
+    #[tokio::test]
+    async fn sends_outbox_email_through_smtp() {
+        let db = test_db().await;
+        let (port, inbox) = smtp_catcher(None).await;
+        let config = smtp_config(port);
+        redeem(&db, &config).await;
+
+        let pending = outbox(&db).await;
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].template, "invite_redeemed");
+        assert_eq!(pending[0].to_address, "creator@example.com");
+        assert!(pending[0].subject.contains("executor 已加入看板"));
+
+        let mailer = Mailer::from_config(&config).unwrap();
+        send_due(&db, &mailer, 5).await.unwrap();
+
+        let mail = inbox.lock().unwrap()[0].clone();
+        assert_eq!(mail.mail_from, "<noreply@taskview.local>");
+        assert_eq!(mail.rcpt_to, ["<creator@example.com>"]);
+        assert!(mail.data.contains("To: creator@example.com"));
+        assert!(mail.data.contains("Subject: "));
+        assert!(decoded_body(&mail.data).contains("执行者 executor（executor@example.com）已使用邀请码"));
+
+        let sent = outbox(&db).await.remove(0);
+        assert_eq!(sent.status, "sent");
+        assert_eq!(sent.attempts, 1);
+        assert!(sent.sent_at.is_some());
+        assert!(sent.next_attempt_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn rejected_recipients_retry_or_fail() {
+        let db = test_db().await;
+        let (port, inbox) = smtp_catcher(Some("451 try again later")).await;
+        let config = smtp_config(port);
+        redeem(&db, &config).await;
+        let mailer = Mailer::from_config(&config).unwrap();
+
+        // 临时错误按退避时间重试
+        send_due(&db, &mailer, 5).await.unwrap();
+        let email = outbox(&db).await.remove(0);
+        assert!(inbox.lock().unwrap().is_empty());
+        assert_eq!(email.status, "pending");
+        assert_eq!(email.attempts, 1);
+        assert!(email.last_error.is_some());
+        assert!(email.next_attempt_at.unwrap() > chrono::Utc::now() + chrono::Duration::seconds(25));
+
+        // 永久错误直接标记失败
+        let (port, _) = smtp_catcher(Some("550 no such user")).await;
+        let mailer = Mailer::from_config(&smtp_config(port)).unwrap();
+        let mut email_active: email_outbox::ActiveModel = email.into();
+        email_active.next_attempt_at = Set(Some(chrono::Utc::now().into()));
+        email_active.update(&db).await.unwrap();
+        send_due(&db, &mailer, 5).await.unwrap();
+
+        let email = outbox(&db).await.remove(0);
+        assert_eq!(email.status, "failed");
+        assert_eq!(email.attempts, 2);
+        assert!(email.next_attempt_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn skips_emails_turned_off_in_preferences() {
+        let db = test_db().await;
+        let config = smtp_config(25);
+        let creator_id = create_user(&db, "creator", "creator").await;
+        db.execute(sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            "INSERT INTO notification_preferences (user_id, email_invite_redeemed) VALUES (?, ?)",
+            [creator_id.clone().into(), false.into()],
+        ))
+        .await
+        .unwrap();
+        let executor_id = create_user(&db, "executor", "executor").await;
+        let invite_id = create_board(&db, &creator_id, Some(&executor_id)).await;
+        let invite = Invite::find_by_id(&invite_id).one(&db).await.unwrap().unwrap();
+
+        enqueue_for_event(&db, &config, &Event::InviteUsed { invite: invite.clone() }).await.unwrap();
+        assert!(outbox(&db).await.is_empty());
+
+        // 未配置SMTP时不写入发件箱
+        enqueue_for_event(&db, &test_config(), &Event::InviteUsed { invite }).await.unwrap();
+        assert!(outbox(&db).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn daily_digest_summarizes_boards_once_a_day() {
+        let db = test_db().await;
+        let creator_id = create_user(&db, "creator", "creator").await;
+        let invite_id = create_board(&db, &creator_id, None).await;
+        let task = create_task(&db, &invite_id, &creator_id, None).await;
+        let mut task_active: task::ActiveModel = task.into();
+        task_active.due_date = Set(Some((chrono::Utc::now() - chrono::Duration::hours(1)).into()));
+        task_active.update(&db).await.unwrap();
+        db.execute(sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            "INSERT INTO notification_preferences (user_id, daily_digest, digest_hour) VALUES (?, ?, ?)",
+            [creator_id.into(), true.into(), 0.into()],
+        ))
+        .await
+        .unwrap();
+
+        send_daily_digests(&db).await.unwrap();
+        send_daily_digests(&db).await.unwrap();
+
+        let emails = outbox(&db).await;
+        assert_eq!(emails.len(), 1);
+        assert_eq!(emails[0].template, "daily_digest");
+        assert!(emails[0].body.contains("未完成 1 个（逾期 1 个），最近24小时完成 0 个"));
+        assert!(emails[0].body.contains("- [todo] 测试任务"));
+    }
+}
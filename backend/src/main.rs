@@ -1,12 +1,25 @@
 use anyhow::Result;
 use axum::{http::{header, Method}, routing::{get, post, put, delete}, Router};
-use std::net::SocketAddr;
+use std::{net::SocketAddr, sync::Arc};
 use tower_http::cors::{Any, CorsLayer};
 
 mod config;
 mod database;
+mod diff;
+mod events;
 mod handlers;
+mod ical;
+mod importers;
+mod mailer;
 mod models;
+mod recurrence;
+mod retry;
+mod scheduler;
+mod signing;
+mod state;
+#[cfg(test)]
+mod test_util;
+mod webhooks;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -20,6 +33,11 @@ async fn main() -> Result<()> {
     let db = database::Database::new(&config.database_url).await
         .map_err(|e| anyhow::anyhow!("Failed to connect to database: {}", e))?;
 
+    let config = Arc::new(config);
+
+    // 启动定时任务调度器（Webhook投递、邮件发送、到期提醒、周期任务、过期邀请码清理）
+    scheduler::spawn(db.connection.clone(), config.clone());
+
     let cors = CorsLayer::new()
         .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
         .allow_headers([header::CONTENT_TYPE, header::AUTHORIZATION])
@@ -34,17 +52,110 @@ async fn main() -> Result<()> {
         // 邀请码相关路由
         .route("/api/invites", post(handlers::invite::create_invite))
         .route("/api/invites/use", post(handlers::invite::use_invite))
+        .route("/api/invites/link/use", post(handlers::invite::use_invite_link))
         .route("/api/invites/:user_id", get(handlers::invite::get_invites))
         .route("/api/invites/executor/:executor_id", get(handlers::invite::get_executor_invites))
         .route("/api/invite/:invite_id", get(handlers::invite::get_invite_details))
+        .route("/api/invite/:invite_id/link", post(handlers::invite::create_invite_link))
+        .route("/api/invite/:invite_id/qrcode", get(handlers::invite::get_invite_qrcode))
         // 任务相关路由
         .route("/api/tasks", post(handlers::task::create_task))
+        .route("/api/tasks/bulk", post(handlers::bulk::bulk_update_tasks))
         .route("/api/tasks/:invite_id", get(handlers::task::get_tasks))
         .route("/api/task/:task_id", put(handlers::task::update_task))
         .route("/api/task/:task_id", delete(handlers::task::delete_task))
         .route("/api/task/:task_id/status", put(handlers::task::update_task_status))
+        .route("/api/task/:task_id/restore", post(handlers::task::restore_task))
+        .route("/api/boards/:invite_id/trash", get(handlers::task::get_trash))
+        .route("/api/task/:task_id/review", post(handlers::review::review_task))
+        .route("/api/task/:task_id/reviews", get(handlers::review::get_task_reviews))
+        .route("/api/task/:task_id/completion-submissions", get(handlers::completion::get_completion_submissions))
+        .route("/api/task/:task_id/completion-submissions/:version/diff", get(handlers::completion::get_completion_diff))
+        .route("/api/task/:task_id/labels", get(handlers::label::get_task_labels))
+        .route("/api/task/:task_id/comments", get(handlers::comment::get_comments))
+        .route("/api/task/:task_id/comments", post(handlers::comment::create_comment))
+        // 任务模板和看板模板相关路由
+        .route("/api/task-templates", post(handlers::template::create_task_template))
+        .route("/api/task-templates", get(handlers::template::get_task_templates))
+        .route("/api/task-template/:template_id", put(handlers::template::update_task_template))
+        .route("/api/task-template/:template_id", delete(handlers::template::delete_task_template))
+        .route("/api/task-template/:template_id/tasks", post(handlers::template::create_task_from_template))
+        .route("/api/board-templates", post(handlers::template::create_board_template))
+        .route("/api/board-templates", get(handlers::template::get_board_templates))
+        .route("/api/board-template/:template_id", put(handlers::template::update_board_template))
+        .route("/api/board-template/:template_id", delete(handlers::template::delete_board_template))
+        .route("/api/board-template/:template_id/boards", post(handlers::template::create_board_from_template))
+        // 站内通知相关路由
+        .route("/api/notifications/:user_id", get(handlers::notification::get_notifications))
+        .route("/api/notifications/:user_id/unread-count", get(handlers::notification::get_unread_count))
+        .route("/api/notifications/:user_id/read-all", put(handlers::notification::mark_all_read))
+        .route("/api/notification/:notification_id/read", put(handlers::notification::mark_notification))
+        .route("/api/users/:user_id/mentions", get(handlers::mention::get_user_mentions))
+        .route("/api/users/:user_id/notification-preferences", get(handlers::notification::get_preferences))
+        .route("/api/users/:user_id/notification-preferences", put(handlers::notification::update_preferences))
+        // 日历订阅相关路由
+        .route("/api/users/:user_id/calendar-feed", get(handlers::calendar::get_calendar_feed))
+        .route("/api/users/:user_id/calendar-feed", post(handlers::calendar::reset_calendar_feed))
+        .route("/api/users/:user_id/calendar-feed", delete(handlers::calendar::delete_calendar_feed))
+        .route("/api/calendar/:file", get(handlers::calendar::get_calendar))
+        // 看板导入导出、标签和统计相关路由
+        .route("/api/boards/import", post(handlers::import::import_board))
+        .route("/api/boards/:invite_id/export", get(handlers::export::export_tasks))
+        .route("/api/boards/:invite_id/import", post(handlers::import::import_tasks))
+        .route("/api/boards/:invite_id/labels", get(handlers::label::get_board_labels))
+        .route("/api/boards/:invite_id/stats", get(handlers::stats::get_board_stats))
+        .route("/api/reports/workload", get(handlers::report::get_workload_report))
+        .route("/api/reports/estimates", get(handlers::report::get_estimate_report))
+        // 归档相关路由
+        .route("/api/task/:task_id/archive", post(handlers::archive::archive_task))
+        .route("/api/task/:task_id/unarchive", post(handlers::archive::unarchive_task))
+        .route("/api/boards/:invite_id/archive", post(handlers::archive::archive_board))
+        .route("/api/boards/:invite_id/unarchive", post(handlers::archive::unarchive_board))
+        .route("/api/boards/:invite_id/auto-archive", put(handlers::archive::update_auto_archive))
+        // 工作流列相关路由
+        .route("/api/boards/:invite_id/columns", get(handlers::column::get_board_columns))
+        .route("/api/boards/:invite_id/columns", put(handlers::column::update_board_columns))
+        // 自定义字段相关路由
+        .route("/api/boards/:invite_id/custom-fields", post(handlers::custom_field::create_custom_field))
+        .route("/api/boards/:invite_id/custom-fields", get(handlers::custom_field::get_custom_fields))
+        .route("/api/custom-field/:field_id", put(handlers::custom_field::update_custom_field))
+        .route("/api/custom-field/:field_id", delete(handlers::custom_field::delete_custom_field))
+        // 里程碑相关路由
+        .route("/api/boards/:invite_id/milestones", post(handlers::milestone::create_milestone))
+        .route("/api/boards/:invite_id/milestones", get(handlers::milestone::get_milestones))
+        .route("/api/milestone/:milestone_id", put(handlers::milestone::update_milestone))
+        .route("/api/milestone/:milestone_id", delete(handlers::milestone::delete_milestone))
+        .route("/api/milestone/:milestone_id/tasks", get(handlers::milestone::get_milestone_tasks))
+        .route("/api/milestone/:milestone_id/tasks", post(handlers::milestone::add_milestone_tasks))
+        .route("/api/milestone/:milestone_id/tasks/:task_id", delete(handlers::milestone::remove_milestone_task))
+        .route("/api/milestone/:milestone_id/burndown", get(handlers::milestone::get_milestone_burndown))
+        // 周期任务相关路由
+        .route("/api/boards/:invite_id/recurring-tasks", post(handlers::recurring_task::create_recurring_task))
+        .route("/api/boards/:invite_id/recurring-tasks", get(handlers::recurring_task::get_recurring_tasks))
+        .route("/api/recurring-task/:template_id", put(handlers::recurring_task::update_recurring_task))
+        .route("/api/recurring-task/:template_id", delete(handlers::recurring_task::delete_recurring_task))
+        // 工时相关路由
+        .route("/api/task/:task_id/timer/start", post(handlers::time_entry::start_timer))
+        .route("/api/task/:task_id/timer/stop", post(handlers::time_entry::stop_timer))
+        .route("/api/task/:task_id/time-entries", get(handlers::time_entry::get_task_time_entries))
+        .route("/api/task/:task_id/time-entries", post(handlers::time_entry::create_time_entry))
+        .route("/api/time-entry/:entry_id", put(handlers::time_entry::update_time_entry))
+        .route("/api/time-entry/:entry_id", delete(handlers::time_entry::delete_time_entry))
+        .route("/api/users/:user_id/timer", get(handlers::time_entry::get_running_timer))
+        .route("/api/boards/:invite_id/time-totals", get(handlers::time_entry::get_board_time_totals))
+        // Webhook相关路由
+        .route("/api/boards/:invite_id/webhooks", post(handlers::webhook::create_webhook))
+        .route("/api/boards/:invite_id/webhooks", get(handlers::webhook::get_webhooks))
+        .route("/api/webhook/:webhook_id", delete(handlers::webhook::delete_webhook))
+        .route("/api/webhook/:webhook_id/deliveries", get(handlers::webhook::get_webhook_deliveries))
+        .route("/api/webhook-deliveries/:delivery_id/redeliver", post(handlers::webhook::redeliver_webhook_delivery))
+        // 管理接口
+        .route("/api/admin/jobs", get(handlers::admin::get_jobs))
         .layer(cors)
-        .with_state(db.connection);
+        .with_state(state::AppState {
+            db: db.connection,
+            config: config.clone(),
+        });
 
     let addr = SocketAddr::from(([0, 0, 0, 0], config.port));
     println!("Server running on http://{}", addr);
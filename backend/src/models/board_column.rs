@@ -0,0 +1,21 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "board_columns")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: String,
+    pub invite_id: String,
+    pub key: String,
+    pub name: String,
+    pub category: String, // "not_started", "active", "finished"
+    pub position: i32,
+    pub wip_limit: Option<i32>, // 为空表示不限制
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,19 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "board_templates")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: String,
+    pub creator_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub tasks: String, // JSON数组
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,18 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "completion_submissions")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: String,
+    pub task_id: String,
+    pub version: i32,
+    pub author_id: Option<String>,
+    pub body: String,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
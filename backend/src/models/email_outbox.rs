@@ -0,0 +1,37 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "email_outbox")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: String,
+    pub user_id: Option<String>,
+    pub to_address: String,
+    pub template: String, // "invite_redeemed", "task_completed", "task_overdue", "daily_digest"
+    pub subject: String,
+    pub body: String,
+    pub status: String, // "pending", "sent", "failed"
+    pub attempts: i32,
+    pub next_attempt_at: Option<DateTimeWithTimeZone>,
+    pub last_error: Option<String>,
+    pub created_at: DateTimeWithTimeZone,
+    pub sent_at: Option<DateTimeWithTimeZone>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {
+    fn new() -> Self {
+        let now = chrono::Utc::now().into();
+        Self {
+            id: Set(uuid::Uuid::new_v4().to_string()),
+            status: Set("pending".to_string()),
+            attempts: Set(0),
+            next_attempt_at: Set(Some(now)),
+            created_at: Set(now),
+            ..Default::default()
+        }
+    }
+}
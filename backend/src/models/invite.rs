@@ -1,6 +1,5 @@
 use sea_orm::entity::prelude::*;
 use sea_orm::Set;
-use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
 #[sea_orm(table_name = "invites")]
@@ -13,6 +12,8 @@ pub struct Model {
     pub status: String, // "pending" or "used"
     pub created_at: DateTimeWithTimeZone,
     pub used_at: Option<DateTimeWithTimeZone>,
+    pub archived_at: Option<DateTimeWithTimeZone>, // 归档后看板只读
+    pub auto_archive_days: Option<i32>, // 任务完成多少天后自动归档
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -0,0 +1,23 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "jobs")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: String,
+    pub name: String,
+    pub interval_secs: i64,
+    pub status: String, // "scheduled", "running", "failed"
+    pub run_at: DateTimeWithTimeZone,
+    pub attempts: i32, // 连续失败次数
+    pub last_run_at: Option<DateTimeWithTimeZone>,
+    pub last_success_at: Option<DateTimeWithTimeZone>,
+    pub last_error: Option<String>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,20 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "mentions")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: String,
+    pub user_id: String, // 被提到的用户
+    pub actor_id: Option<String>,
+    pub invite_id: String,
+    pub task_id: String,
+    pub comment_id: Option<String>,
+    pub source: String, // "description", "completion_details", "comment"
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
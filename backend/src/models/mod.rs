@@ -1,7 +1,25 @@
 pub mod user;
 pub mod invite;
 pub mod task;
-
-pub use user::*;
-pub use invite::*;
-pub use task::*;
\ No newline at end of file
+pub mod notification;
+pub mod webhook;
+pub mod webhook_delivery;
+pub mod notification_preference;
+pub mod email_outbox;
+pub mod job;
+pub mod label;
+pub mod task_label;
+pub mod comment;
+pub mod calendar_feed;
+pub mod task_status_history;
+pub mod milestone;
+pub mod milestone_task;
+pub mod recurring_task;
+pub mod task_template;
+pub mod board_template;
+pub mod time_entry;
+pub mod task_review;
+pub mod completion_submission;
+pub mod custom_field;
+pub mod board_column;
+pub mod mention;
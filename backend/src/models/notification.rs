@@ -0,0 +1,30 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "notifications")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: String,
+    pub user_id: String,
+    pub actor_id: Option<String>,
+    pub kind: String, // "task_assigned", "status_changed", "task_reviewed", "commented", "mentioned", "due_soon", "overdue"
+    pub invite_id: Option<String>,
+    pub task_id: Option<String>,
+    pub message: String,
+    pub read_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {
+    fn new() -> Self {
+        Self {
+            id: Set(uuid::Uuid::new_v4().to_string()),
+            created_at: Set(chrono::Utc::now().into()),
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "notification_preferences")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub user_id: String,
+    pub email_invite_redeemed: bool,
+    pub email_task_completed: bool,
+    pub email_task_overdue: bool,
+    pub daily_digest: bool,
+    pub digest_hour: i32, // UTC小时，0-23
+    pub last_digest_at: Option<DateTimeWithTimeZone>,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    // 用户未设置偏好时的默认值：事件邮件开启，每日摘要关闭
+    pub fn default_for(user_id: &str) -> Self {
+        Model {
+            user_id: user_id.to_string(),
+            email_invite_redeemed: true,
+            email_task_completed: true,
+            email_task_overdue: true,
+            daily_digest: false,
+            digest_hour: 8,
+            last_digest_at: None,
+            updated_at: chrono::Utc::now().into(),
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "recurring_tasks")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: String,
+    pub invite_id: String,
+    pub creator_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub rrule: String,
+    pub start_at: DateTimeWithTimeZone,
+    pub due_in_hours: Option<i64>,
+    pub paused: bool,
+    pub next_run_at: Option<DateTimeWithTimeZone>, // 为空表示已超过UNTIL
+    pub last_run_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,6 +1,5 @@
 use sea_orm::entity::prelude::*;
 use sea_orm::Set;
-use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
 #[sea_orm(table_name = "tasks")]
@@ -9,12 +8,22 @@ pub struct Model {
     pub id: String,
     pub title: String,
     pub description: Option<String>,
-    pub status: String, // "todo", "in_progress", "done"
+    pub status: String, // 看板列的键，默认为 "todo", "in_progress", "in_review", "done"
+    pub status_category: String, // "not_started", "active", "finished"
     pub creator_id: String,
     pub executor_id: Option<String>,
     pub invite_id: String,
     pub completion_details: Option<String>,
     pub completed_at: Option<DateTimeWithTimeZone>,
+    pub due_date: Option<DateTimeWithTimeZone>,
+    pub overdue_notified_at: Option<DateTimeWithTimeZone>,
+    pub due_soon_notified_at: Option<DateTimeWithTimeZone>,
+    pub priority: Option<String>, // "low", "medium", "high"
+    pub estimate: Option<f64>,
+    pub estimate_unit: Option<String>, // "points", "hours"
+    pub custom_fields: Option<String>, // 以字段ID为键的JSON对象
+    pub deleted_at: Option<DateTimeWithTimeZone>, // 不为空时在回收站中
+    pub archived_at: Option<DateTimeWithTimeZone>,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
@@ -28,6 +37,7 @@ impl ActiveModelBehavior for ActiveModel {
         Self {
             id: Set(uuid::Uuid::new_v4().to_string()),
             status: Set("todo".to_string()),
+            status_category: Set("not_started".to_string()),
             created_at: Set(now),
             updated_at: Set(now),
             ..Default::default()
@@ -0,0 +1,19 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "task_reviews")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: String,
+    pub task_id: String,
+    pub reviewer_id: String,
+    pub decision: String, // "accepted", "changes_requested"
+    pub comment: Option<String>,
+    pub completion_details: Option<String>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
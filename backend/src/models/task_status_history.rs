@@ -0,0 +1,19 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "task_status_history")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: String,
+    pub task_id: String,
+    pub from_status: Option<String>, // 任务创建时为空
+    pub to_status: String,
+    pub to_category: String, // 目标列的分类
+    pub actor_id: Option<String>,
+    pub changed_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
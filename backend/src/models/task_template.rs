@@ -0,0 +1,22 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "task_templates")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: String,
+    pub creator_id: String,
+    pub name: String,
+    pub title_pattern: String,
+    pub description: Option<String>,
+    pub labels: String,    // JSON数组
+    pub checklist: String, // JSON数组
+    pub priority: Option<String>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
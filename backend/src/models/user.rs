@@ -1,6 +1,5 @@
 use sea_orm::entity::prelude::*;
 use sea_orm::Set;
-use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
 #[sea_orm(table_name = "users")]
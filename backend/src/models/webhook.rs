@@ -0,0 +1,37 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "webhooks")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: String,
+    pub invite_id: String,
+    pub creator_id: String,
+    pub url: String,
+    pub secret: String,
+    pub events: String, // 逗号分隔的事件类型，空字符串表示订阅全部事件
+    pub active: bool,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {
+    fn new() -> Self {
+        Self {
+            id: Set(uuid::Uuid::new_v4().to_string()),
+            active: Set(true),
+            created_at: Set(chrono::Utc::now().into()),
+            ..Default::default()
+        }
+    }
+}
+
+impl Model {
+    // 是否订阅了指定事件
+    pub fn subscribes_to(&self, event: &str) -> bool {
+        self.events.is_empty() || self.events.split(',').any(|e| e == event)
+    }
+}
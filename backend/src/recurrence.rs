@@ -0,0 +1,239 @@
+// RFC 5545 RRULE 子集：FREQ=DAILY|WEEKLY|MONTHLY，INTERVAL，UNTIL，时间均按UTC计算
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDate, TimeZone, Utc};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rule {
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub until: Option<DateTime<Utc>>,
+}
+
+// 按月重复时连续跳过的月份上限，如每年2月29日最多跳过3次
+const MAX_SKIPPED: u32 = 8;
+
+impl Rule {
+    // 解析规则字符串，可带 "RRULE:" 前缀，不支持的属性返回错误
+    pub fn parse(value: &str) -> Result<Rule, String> {
+        let value = value.trim();
+        let value = value.strip_prefix("RRULE:").unwrap_or(value);
+
+        let mut frequency = None;
+        let mut interval = 1;
+        let mut until = None;
+        for part in value.split(';').filter(|part| !part.is_empty()) {
+            let (key, val) = part.split_once('=').ok_or_else(|| format!("无效的规则片段: {}", part))?;
+            match key.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match val.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        _ => return Err(format!("不支持的重复频率: {}", val)),
+                    })
+                }
+                "INTERVAL" => {
+                    interval = val
+                        .parse()
+                        .ok()
+                        .filter(|interval| (1..=1000).contains(interval))
+                        .ok_or_else(|| format!("无效的间隔: {}", val))?
+                }
+                "UNTIL" => until = Some(parse_until(val)?),
+                _ => return Err(format!("不支持的规则属性: {}", key)),
+            }
+        }
+
+        Ok(Rule {
+            frequency: frequency.ok_or("缺少FREQ")?,
+            interval,
+            until,
+        })
+    }
+
+    // 第k次（从0开始）重复的时间，按月重复时当月没有该日期返回None
+    fn nth(&self, start: DateTime<Utc>, k: u32) -> Option<DateTime<Utc>> {
+        let steps = k.checked_mul(self.interval)?;
+        match self.frequency {
+            Frequency::Daily => Some(start + Duration::days(steps as i64)),
+            Frequency::Weekly => Some(start + Duration::weeks(steps as i64)),
+            // checked_add_months会把1月31日变成2月28日，RFC 5545要求跳过这样的月份
+            Frequency::Monthly => start
+                .checked_add_months(Months::new(steps))
+                .filter(|dt| dt.day() == start.day()),
+        }
+    }
+
+    // 大致对应at的重复序号，可能偏小
+    fn index_near(&self, start: DateTime<Utc>, at: DateTime<Utc>) -> u32 {
+        let elapsed = match self.frequency {
+            Frequency::Daily => (at - start).num_days(),
+            Frequency::Weekly => (at - start).num_weeks(),
+            Frequency::Monthly => {
+                ((at.year() - start.year()) * 12 + at.month() as i32 - start.month() as i32) as i64 - 1
+            }
+        };
+        (elapsed.max(0) as u64 / self.interval as u64).min(u32::MAX as u64) as u32
+    }
+
+    fn within_until(&self, dt: DateTime<Utc>) -> bool {
+        self.until.map_or(true, |until| dt <= until)
+    }
+
+    // 第一个不早于at的重复时间，超过UNTIL时返回None
+    pub fn next_on_or_after(&self, start: DateTime<Utc>, at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let first = if at <= start { 0 } else { self.index_near(start, at) };
+        let mut skipped = 0;
+        for k in first.. {
+            match self.nth(start, k) {
+                Some(dt) if dt >= at => return Some(dt).filter(|dt| self.within_until(*dt)),
+                Some(_) => {}
+                None if skipped >= MAX_SKIPPED => return None,
+                None => skipped += 1,
+            }
+        }
+        None
+    }
+
+    // 最后一个不晚于at的重复时间，at早于开始时间时返回None
+    pub fn latest_on_or_before(&self, start: DateTime<Utc>, at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if at < start {
+            return None;
+        }
+        let mut latest = None;
+        let mut k = self.index_near(start, at);
+        // 从偏小的序号向后找到超过at为止
+        let mut skipped = 0;
+        loop {
+            match self.nth(start, k) {
+                Some(dt) if dt > at => break,
+                Some(dt) => latest = Some(dt),
+                None if skipped >= MAX_SKIPPED => break,
+                None => skipped += 1,
+            }
+            k += 1;
+        }
+        // 按月重复时偏小的序号可能正好落在被跳过的月份
+        while latest.is_none() && k > 0 {
+            k -= 1;
+            latest = self.nth(start, k).filter(|dt| *dt <= at);
+        }
+        latest.filter(|dt| self.within_until(*dt))
+    }
+}
+
+// 输出规范化的规则字符串
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let frequency = match self.frequency {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+        };
+        write!(f, "FREQ={};INTERVAL={}", frequency, self.interval)?;
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until.format("%Y%m%dT%H%M%SZ"))?;
+        }
+        Ok(())
+    }
+}
+
+// UNTIL支持 YYYYMMDD（当天结束前有效）和 YYYYMMDDTHHMMSSZ 两种格式
+fn parse_until(value: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(dt) = chrono::NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%SZ") {
+        return Ok(Utc.from_utc_datetime(&dt));
+    }
+    NaiveDate::parse_from_str(value, "%Y%m%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(23, 59, 59))
+        .map(|dt| Utc.from_utc_datetime(&dt))
+        .ok_or_else(|| format!("无效的UNTIL: {}", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn parses_and_normalizes_rules() {
+        let rule = Rule::parse("RRULE:freq=weekly;interval=2;UNTIL=20261231").unwrap();
+        assert_eq!(rule.frequency, Frequency::Weekly);
+        assert_eq!(rule.interval, 2);
+        assert_eq!(rule.until, Some(at("2026-12-31T23:59:59Z")));
+        assert_eq!(rule.to_string(), "FREQ=WEEKLY;INTERVAL=2;UNTIL=20261231T235959Z");
+        assert_eq!(Rule::parse(&rule.to_string()).unwrap(), rule);
+
+        for invalid in ["INTERVAL=2", "FREQ=YEARLY", "FREQ=DAILY;INTERVAL=0", "FREQ=DAILY;COUNT=5", "FREQ=DAILY;UNTIL=tomorrow"] {
+            assert!(Rule::parse(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    // 每月31日：没有31日的月份跳过，而不是改到月末
+    #[test]
+    fn monthly_on_31st_skips_short_months() {
+        let rule = Rule::parse("FREQ=MONTHLY").unwrap();
+        let start = at("2025-01-31T09:00:00Z");
+
+        assert_eq!(rule.next_on_or_after(start, at("2025-01-01T00:00:00Z")), Some(start));
+        assert_eq!(rule.next_on_or_after(start, at("2025-02-01T00:00:00Z")), Some(at("2025-03-31T09:00:00Z")));
+        assert_eq!(rule.next_on_or_after(start, at("2025-04-01T00:00:00Z")), Some(at("2025-05-31T09:00:00Z")));
+        assert_eq!(rule.latest_on_or_before(start, at("2025-03-15T00:00:00Z")), Some(start));
+        assert_eq!(rule.latest_on_or_before(start, at("2025-07-30T00:00:00Z")), Some(at("2025-05-31T09:00:00Z")));
+        assert_eq!(rule.latest_on_or_before(start, at("2025-01-30T00:00:00Z")), None);
+    }
+
+    // 每12个月的2月29日只在闰年重复，2100年不是闰年
+    #[test]
+    fn yearly_on_feb_29_waits_for_leap_years() {
+        let rule = Rule::parse("FREQ=MONTHLY;INTERVAL=12").unwrap();
+        let start = at("2024-02-29T00:00:00Z");
+
+        assert_eq!(rule.next_on_or_after(start, at("2024-03-01T00:00:00Z")), Some(at("2028-02-29T00:00:00Z")));
+        assert_eq!(rule.latest_on_or_before(start, at("2027-12-31T00:00:00Z")), Some(start));
+        assert_eq!(rule.next_on_or_after(start, at("2096-03-01T00:00:00Z")), Some(at("2104-02-29T00:00:00Z")));
+        assert_eq!(rule.latest_on_or_before(start, at("2103-12-31T00:00:00Z")), Some(at("2096-02-29T00:00:00Z")));
+    }
+
+    // UNTIL当时的重复仍然有效，之后没有下一次
+    #[test]
+    fn until_is_inclusive() {
+        let rule = Rule::parse("FREQ=DAILY;UNTIL=20250110T090000Z").unwrap();
+        let start = at("2025-01-01T09:00:00Z");
+        let until = at("2025-01-10T09:00:00Z");
+
+        assert_eq!(rule.next_on_or_after(start, at("2025-01-10T00:00:00Z")), Some(until));
+        assert_eq!(rule.next_on_or_after(start, until), Some(until));
+        assert_eq!(rule.next_on_or_after(start, at("2025-01-10T09:00:01Z")), None);
+        assert_eq!(rule.latest_on_or_before(start, until), Some(until));
+
+        // 只写日期时当天全天有效
+        let rule = Rule::parse("FREQ=DAILY;UNTIL=20250110").unwrap();
+        assert_eq!(rule.next_on_or_after(start, at("2025-01-10T09:00:00Z")), Some(until));
+        assert_eq!(rule.next_on_or_after(start, at("2025-01-10T09:00:01Z")), None);
+    }
+
+    // 停机后只补最近一次重复，下次从之后的重复继续
+    #[test]
+    fn catches_up_to_latest_occurrence() {
+        let rule = Rule::parse("FREQ=WEEKLY;INTERVAL=2").unwrap();
+        let start = at("2025-01-06T08:00:00Z");
+        let now = at("2025-03-20T12:00:00Z");
+
+        let latest = rule.latest_on_or_before(start, now).unwrap();
+        assert_eq!(latest, at("2025-03-17T08:00:00Z"));
+        assert_eq!(rule.next_on_or_after(start, latest + Duration::seconds(1)), Some(at("2025-03-31T08:00:00Z")));
+        assert_eq!(rule.latest_on_or_before(start, at("2025-03-17T08:00:00Z")), Some(latest));
+        assert_eq!(rule.latest_on_or_before(start, at("2025-03-17T07:59:59Z")), Some(at("2025-03-03T08:00:00Z")));
+    }
+}
@@ -0,0 +1,22 @@
+// 重试退避的基础间隔和上限（秒）
+const BACKOFF_BASE_SECS: i64 = 30;
+const BACKOFF_MAX_SECS: i64 = 3600;
+
+// 指数退避：30秒、60秒、120秒……最长1小时
+pub fn backoff_secs(attempts: i32) -> i64 {
+    let exponent = (attempts - 1).clamp(0, 16) as u32;
+    (BACKOFF_BASE_SECS * 2_i64.pow(exponent)).min(BACKOFF_MAX_SECS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_one_hour() {
+        let schedule: Vec<i64> = (1..=9).map(backoff_secs).collect();
+        assert_eq!(schedule, [30, 60, 120, 240, 480, 960, 1920, 3600, 3600]);
+        assert_eq!(backoff_secs(0), 30);
+        assert_eq!(backoff_secs(100), 3600);
+    }
+}
@@ -0,0 +1,582 @@
+use sea_orm::{ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder, Set, TransactionTrait};
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+use crate::config::Config;
+use crate::events::{self, Event};
+use crate::mailer::{self, Mailer};
+use crate::handlers::archive;
+use crate::handlers::mention::{sync_mentions, MentionSource};
+use crate::handlers::task::{insert_task, NewTask};
+use crate::models::{invite, invite::Entity as Invite, job, job::Entity as Job, recurring_task, recurring_task::Entity as RecurringTask, task, task::Entity as Task};
+use crate::recurrence::Rule;
+use crate::{retry, webhooks};
+
+// 调度器轮询间隔
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+// 内置定时任务及其执行间隔（秒）
+const JOBS: &[(&str, i64)] = &[
+    ("webhooks.deliver", 5),
+    ("emails.send", 10),
+    ("reminders.due_soon", 300),
+    ("reminders.overdue", 300),
+    ("recurring_tasks.create", 60),
+    ("emails.daily_digest", 600),
+    ("invites.purge_expired", 3600),
+    ("tasks.purge_deleted", 3600),
+    ("tasks.auto_archive", 3600),
+];
+
+// 定时任务执行所需的上下文
+struct JobContext {
+    db: DatabaseConnection,
+    config: Arc<Config>,
+    http: reqwest::Client,
+    mailer: Option<Mailer>,
+}
+
+// 启动调度器，任务的下次执行时间和失败状态保存在jobs表中，重启后继续
+pub fn spawn(db: DatabaseConnection, config: Arc<Config>) {
+    let http = reqwest::Client::builder()
+        .timeout(Duration::from_secs(config.webhook_timeout_secs))
+        .build()
+        .expect("创建HTTP客户端失败");
+    let mailer = Mailer::from_config(&config);
+    let ctx = JobContext { db, config, http, mailer };
+
+    tokio::spawn(async move {
+        if let Err(e) = register_jobs(&ctx.db).await {
+            tracing::error!("注册定时任务失败: {:?}", e);
+            return;
+        }
+
+        loop {
+            if let Err(e) = run_due_jobs(&ctx).await {
+                tracing::error!("执行定时任务失败: {:?}", e);
+            }
+            tokio::time::sleep(TICK_INTERVAL).await;
+        }
+    });
+}
+
+// 写入内置任务，已存在的保留原有执行时间；上次异常退出时处于running的任务恢复为待执行
+async fn register_jobs(db: &DatabaseConnection) -> Result<(), DbErr> {
+    let now = chrono::Utc::now();
+    for (name, interval_secs) in JOBS {
+        db.execute(sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            r#"
+            INSERT INTO jobs (id, name, interval_secs, status, run_at, attempts, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(name) DO UPDATE SET interval_secs = excluded.interval_secs
+            "#,
+            [
+                uuid::Uuid::new_v4().to_string().into(),
+                (*name).into(),
+                (*interval_secs).into(),
+                "scheduled".into(),
+                now.into(),
+                0.into(),
+                now.into(),
+                now.into(),
+            ],
+        ))
+        .await?;
+    }
+
+    db.execute(sea_orm::Statement::from_sql_and_values(
+        sea_orm::DatabaseBackend::Sqlite,
+        "UPDATE jobs SET status = 'scheduled' WHERE status = 'running'",
+        [],
+    ))
+    .await?;
+
+    Ok(())
+}
+
+// 执行所有到期的任务
+async fn run_due_jobs(ctx: &JobContext) -> Result<(), DbErr> {
+    let now: chrono::DateTime<chrono::FixedOffset> = chrono::Utc::now().into();
+    let jobs = Job::find()
+        .filter(job::Column::Status.ne("running"))
+        .filter(job::Column::RunAt.lte(now))
+        .order_by_asc(job::Column::RunAt)
+        .all(&ctx.db)
+        .await?;
+
+    for job in jobs {
+        let name = job.name.clone();
+        let interval_secs = job.interval_secs;
+        let attempts = job.attempts;
+
+        let mut job_active: job::ActiveModel = job.into();
+        job_active.status = Set("running".to_string());
+        job_active.last_run_at = Set(Some(chrono::Utc::now().into()));
+        job_active.updated_at = Set(chrono::Utc::now().into());
+        let job = job_active.update(&ctx.db).await?;
+
+        let result = run_job(ctx, &name).await;
+
+        let now = chrono::Utc::now();
+        let mut job_active: job::ActiveModel = job.into();
+        job_active.updated_at = Set(now.into());
+        match result {
+            Ok(()) => {
+                job_active.status = Set("scheduled".to_string());
+                job_active.attempts = Set(0);
+                job_active.last_error = Set(None);
+                job_active.last_success_at = Set(Some(now.into()));
+                job_active.run_at = Set((now + chrono::Duration::seconds(interval_secs)).into());
+            }
+            Err(error) => {
+                // 连续失败时按指数退避推迟，但不超过正常间隔太久
+                tracing::warn!("定时任务 {} 执行失败（连续第{}次）: {}", name, attempts + 1, error);
+                let delay = retry::backoff_secs(attempts + 1).max(interval_secs);
+                job_active.status = Set("failed".to_string());
+                job_active.attempts = Set(attempts + 1);
+                job_active.last_error = Set(Some(error));
+                job_active.run_at = Set((now + chrono::Duration::seconds(delay)).into());
+            }
+        }
+        job_active.update(&ctx.db).await?;
+    }
+
+    Ok(())
+}
+
+async fn run_job(ctx: &JobContext, name: &str) -> Result<(), String> {
+    let db = &ctx.db;
+    let result = match name {
+        "webhooks.deliver" => webhooks::deliver_due(db, &ctx.http, ctx.config.webhook_max_attempts).await,
+        "emails.send" => match &ctx.mailer {
+            Some(mailer) => mailer::send_due(db, mailer, ctx.config.email_max_attempts).await,
+            None => Ok(()),
+        },
+        "emails.daily_digest" if ctx.mailer.is_some() => mailer::send_daily_digests(db).await,
+        "emails.daily_digest" => Ok(()),
+        "reminders.due_soon" => remind_due_soon(db, &ctx.config).await,
+        "reminders.overdue" => remind_overdue(db, &ctx.config).await,
+        "recurring_tasks.create" => create_recurring_tasks(db, &ctx.config).await,
+        "invites.purge_expired" => purge_expired_invites(db, &ctx.config).await,
+        "tasks.purge_deleted" => purge_deleted_tasks(db, &ctx.config).await,
+        "tasks.auto_archive" => archive::auto_archive_tasks(db).await,
+        _ => return Err(format!("未知的定时任务: {}", name)),
+    };
+
+    result.map_err(|e| e.to_string())
+}
+
+// 已归档的看板，看板上的任务不再提醒也不再自动创建
+async fn archived_boards(db: &DatabaseConnection) -> Result<HashSet<String>, DbErr> {
+    Ok(Invite::find()
+        .filter(invite::Column::ArchivedAt.is_not_null())
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|invite| invite.id)
+        .collect())
+}
+
+// 对即将到期的未完成任务发布临期事件，每个截止时间只提醒一次
+async fn remind_due_soon(db: &DatabaseConnection, config: &Config) -> Result<(), DbErr> {
+    let now = chrono::Utc::now();
+    let window_end: chrono::DateTime<chrono::FixedOffset> =
+        (now + chrono::Duration::hours(config.due_soon_hours)).into();
+    let now: chrono::DateTime<chrono::FixedOffset> = now.into();
+
+    let tasks = Task::find()
+        .filter(task::Column::DeletedAt.is_null())
+        .filter(task::Column::ArchivedAt.is_null())
+        .filter(task::Column::DueDate.gt(now))
+        .filter(task::Column::DueDate.lte(window_end))
+        .filter(task::Column::StatusCategory.ne("finished"))
+        .filter(task::Column::DueSoonNotifiedAt.is_null())
+        .all(db)
+        .await?;
+
+    let archived = archived_boards(db).await?;
+    for task in tasks {
+        if archived.contains(&task.invite_id) {
+            continue;
+        }
+        let mut task_active: task::ActiveModel = task.clone().into();
+        task_active.due_soon_notified_at = Set(Some(now));
+        task_active.update(db).await?;
+
+        events::publish(db, config, Event::TaskDueSoon { task }).await;
+    }
+
+    Ok(())
+}
+
+// 对新逾期的未完成任务发布逾期事件，每个截止时间只提醒一次
+async fn remind_overdue(db: &DatabaseConnection, config: &Config) -> Result<(), DbErr> {
+    let now: chrono::DateTime<chrono::FixedOffset> = chrono::Utc::now().into();
+    let tasks = Task::find()
+        .filter(task::Column::DeletedAt.is_null())
+        .filter(task::Column::ArchivedAt.is_null())
+        .filter(task::Column::DueDate.lte(now))
+        .filter(task::Column::StatusCategory.ne("finished"))
+        .filter(task::Column::OverdueNotifiedAt.is_null())
+        .all(db)
+        .await?;
+
+    let archived = archived_boards(db).await?;
+    for task in tasks {
+        if archived.contains(&task.invite_id) {
+            continue;
+        }
+        let mut task_active: task::ActiveModel = task.clone().into();
+        task_active.overdue_notified_at = Set(Some(now));
+        task_active.update(db).await?;
+
+        events::publish(db, config, Event::TaskOverdue { task }).await;
+    }
+
+    Ok(())
+}
+
+// 为到期的周期任务模板创建任务；停机期间错过多次重复时只补建最近一次
+async fn create_recurring_tasks(db: &DatabaseConnection, config: &Config) -> Result<(), DbErr> {
+    let now = chrono::Utc::now();
+    let templates = RecurringTask::find()
+        .filter(recurring_task::Column::Paused.eq(false))
+        .filter(recurring_task::Column::NextRunAt.lte(chrono::DateTime::<chrono::FixedOffset>::from(now)))
+        .all(db)
+        .await?;
+
+    // 归档看板上的周期任务暂停，恢复看板后从最近一次重复继续
+    let archived = archived_boards(db).await?;
+    for template in templates {
+        if archived.contains(&template.invite_id) {
+            continue;
+        }
+        let Some(next_run_at) = template.next_run_at.map(|dt| dt.with_timezone(&chrono::Utc)) else { continue };
+        let rule = match Rule::parse(&template.rrule) {
+            Ok(rule) => rule,
+            Err(error) => {
+                tracing::warn!("周期任务 {} 的规则无效: {}", template.id, error);
+                continue;
+            }
+        };
+        let start = template.start_at.with_timezone(&chrono::Utc);
+        let cutoff = rule.until.map_or(now, |until| until.min(now));
+        let occurrence = rule
+            .latest_on_or_before(start, cutoff)
+            .filter(|occurrence| *occurrence >= next_run_at)
+            .unwrap_or(next_run_at);
+
+        let txn = db.begin().await?;
+        // 同一次重复已经创建过任务时（如上次执行中途退出）只推进下次执行时间
+        let inserted = txn
+            .execute(sea_orm::Statement::from_sql_and_values(
+                sea_orm::DatabaseBackend::Sqlite,
+                r#"
+                INSERT OR IGNORE INTO recurring_task_occurrences (recurring_task_id, occurrence_at, created_at)
+                VALUES (?, ?, ?)
+                "#,
+                [template.id.clone().into(), occurrence.into(), now.into()],
+            ))
+            .await?
+            .rows_affected()
+            > 0;
+
+        let mut task_id = None;
+        if inserted {
+            let id = insert_task(&txn, NewTask {
+                title: template.title.clone(),
+                description: template.description.clone(),
+                creator_id: template.creator_id.clone(),
+                invite_id: template.invite_id.clone(),
+                due_date: template
+                    .due_in_hours
+                    .map(|hours| (occurrence + chrono::Duration::hours(hours)).into()),
+                priority: None,
+                custom_fields: None,
+            })
+            .await?;
+            txn.execute(sea_orm::Statement::from_sql_and_values(
+                sea_orm::DatabaseBackend::Sqlite,
+                "UPDATE recurring_task_occurrences SET task_id = ? WHERE recurring_task_id = ? AND occurrence_at = ?",
+                [id.clone().into(), template.id.clone().into(), occurrence.into()],
+            ))
+            .await?;
+            task_id = Some(id);
+        }
+
+        let next_run_at = rule.next_on_or_after(start, occurrence + chrono::Duration::seconds(1));
+        let mut template_active: recurring_task::ActiveModel = template.into();
+        template_active.next_run_at = Set(next_run_at.map(Into::into));
+        template_active.last_run_at = Set(Some(now.into()));
+        template_active.updated_at = Set(now.into());
+        template_active.update(&txn).await?;
+        txn.commit().await?;
+
+        let Some(task_id) = task_id else { continue };
+        if let Some(task) = Task::find_by_id(&task_id).one(db).await? {
+            let actor_id = Some(task.creator_id.clone());
+            events::publish(db, config, Event::TaskCreated { task: task.clone(), actor_id }).await;
+            sync_mentions(db, config, &task, MentionSource::Description, task.description.as_deref(), Some(&task.creator_id)).await;
+        }
+    }
+
+    Ok(())
+}
+
+// 删除超过有效期且没有任务的未使用邀请码
+async fn purge_expired_invites(db: &DatabaseConnection, config: &Config) -> Result<(), DbErr> {
+    if config.invite_expiry_days <= 0 {
+        return Ok(());
+    }
+
+    let cutoff = chrono::Utc::now() - chrono::Duration::days(config.invite_expiry_days);
+    let expired = r#"
+        SELECT id FROM invites
+        WHERE status = 'pending' AND created_at < ?
+          AND NOT EXISTS (SELECT 1 FROM tasks WHERE tasks.invite_id = invites.id)
+    "#;
+
+    let txn = db.begin().await?;
+    txn.execute(sea_orm::Statement::from_sql_and_values(
+        sea_orm::DatabaseBackend::Sqlite,
+        format!("DELETE FROM webhooks WHERE invite_id IN ({})", expired),
+        [cutoff.into()],
+    ))
+    .await?;
+    let result = txn
+        .execute(sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            format!("DELETE FROM invites WHERE id IN ({})", expired),
+            [cutoff.into()],
+        ))
+        .await?;
+    txn.commit().await?;
+
+    if result.rows_affected() > 0 {
+        tracing::info!("已清理过期邀请码 {} 个", result.rows_affected());
+    }
+
+    Ok(())
+}
+
+// 彻底删除回收站中超过保留期的任务，关联的评论、工时等记录随任务级联删除
+async fn purge_deleted_tasks(db: &DatabaseConnection, config: &Config) -> Result<(), DbErr> {
+    if config.trash_retention_days <= 0 {
+        return Ok(());
+    }
+
+    let cutoff = chrono::Utc::now() - chrono::Duration::days(config.trash_retention_days);
+    let result = db
+        .execute(sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            "DELETE FROM tasks WHERE deleted_at IS NOT NULL AND deleted_at < ?",
+            [cutoff.into()],
+        ))
+        .await?;
+
+    if result.rows_affected() > 0 {
+        tracing::info!("已清理回收站任务 {} 个", result.rows_affected());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{extract::{Path, State}, Json};
+    use chrono::{FixedOffset, Utc};
+    use serde_json::json;
+
+    use crate::handlers::task::{create_task, update_task};
+    use crate::models::{mention, mention::Entity as Mention};
+    use crate::test_util::{create_board, create_user, test_config, test_db};
+
+    // 截止时间带非UTC时区时，提醒按实际时间判断
+    #[tokio::test]
+    async fn reminders_compare_due_dates_across_offsets() {
+        let db = test_db().await;
+        let config = Arc::new(test_config());
+        let creator_id = create_user(&db, "creator", "creator").await;
+        let invite_id = create_board(&db, &creator_id, None).await;
+
+        // 一小时前到期，按+08:00书写时字符串比当前UTC时间大
+        let overdue_at = (Utc::now() - chrono::Duration::hours(1)).with_timezone(&FixedOffset::east_opt(8 * 3600).unwrap());
+        let Json(overdue) = create_task(
+            State(db.clone()),
+            State(config.clone()),
+            Json(serde_json::from_value(json!({
+                "title": "已逾期",
+                "creator_id": creator_id,
+                "invite_id": invite_id,
+                "due_date": overdue_at.to_rfc3339(),
+            })).unwrap()),
+        )
+        .await
+        .unwrap();
+
+        // 两小时后到期，按-05:00书写时字符串比当前UTC时间小
+        let Json(due_soon) = create_task(
+            State(db.clone()),
+            State(config.clone()),
+            Json(serde_json::from_value(json!({
+                "title": "即将到期",
+                "creator_id": creator_id,
+                "invite_id": invite_id,
+            })).unwrap()),
+        )
+        .await
+        .unwrap();
+        let due_soon_at = (Utc::now() + chrono::Duration::hours(2)).with_timezone(&FixedOffset::west_opt(5 * 3600).unwrap());
+        let Json(_) = update_task(
+            State(db.clone()),
+            State(config.clone()),
+            Path(due_soon.id.clone()),
+            Json(serde_json::from_value(json!({
+                "due_date": due_soon_at.to_rfc3339(),
+                "user_id": creator_id,
+            })).unwrap()),
+        )
+        .await
+        .unwrap();
+
+        remind_overdue(&db, &config).await.unwrap();
+        remind_due_soon(&db, &config).await.unwrap();
+
+        let overdue = Task::find_by_id(&overdue.id).one(&db).await.unwrap().unwrap();
+        let due_soon = Task::find_by_id(&due_soon.id).one(&db).await.unwrap().unwrap();
+        assert_eq!(overdue.due_date.unwrap().offset().local_minus_utc(), 0);
+        assert_eq!(overdue.due_date.unwrap(), overdue_at);
+        assert_eq!(due_soon.due_date.unwrap().offset().local_minus_utc(), 0);
+        assert_eq!(due_soon.due_date.unwrap(), due_soon_at);
+        assert!(overdue.overdue_notified_at.is_some());
+        assert!(overdue.due_soon_notified_at.is_none());
+        assert!(due_soon.overdue_notified_at.is_none());
+        assert!(due_soon.due_soon_notified_at.is_some());
+    }
+
+    // 停机期间错过多次重复时只补建最近一次，再次执行不重复创建
+    #[tokio::test]
+    async fn recurring_tasks_catch_up_once_after_downtime() {
+        let db = test_db().await;
+        let config = test_config();
+        let creator_id = create_user(&db, "creator", "creator").await;
+        let invite_id = create_board(&db, &creator_id, None).await;
+        let now = Utc::now();
+        let start = now - chrono::Duration::days(10) - chrono::Duration::hours(1);
+        db.execute(sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            r#"
+            INSERT INTO recurring_tasks (id, invite_id, creator_id, title, rrule, start_at, due_in_hours, paused, next_run_at, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            [
+                "daily".into(),
+                invite_id.clone().into(),
+                creator_id.into(),
+                "每日站会".into(),
+                "FREQ=DAILY;INTERVAL=1".into(),
+                start.into(),
+                2i64.into(),
+                false.into(),
+                (start + chrono::Duration::days(1)).into(),
+                start.into(),
+                start.into(),
+            ],
+        ))
+        .await
+        .unwrap();
+
+        create_recurring_tasks(&db, &config).await.unwrap();
+        create_recurring_tasks(&db, &config).await.unwrap();
+
+        let tasks = Task::find().filter(task::Column::InviteId.eq(&invite_id)).all(&db).await.unwrap();
+        assert_eq!(tasks.len(), 1);
+        let latest = start + chrono::Duration::days(10);
+        assert_eq!(tasks[0].due_date.unwrap(), latest + chrono::Duration::hours(2));
+
+        let template = RecurringTask::find_by_id("daily").one(&db).await.unwrap().unwrap();
+        assert_eq!(template.next_run_at.unwrap(), latest + chrono::Duration::days(1));
+    }
+
+    // 周期任务创建时同步描述中提到的看板成员
+    #[tokio::test]
+    async fn recurring_tasks_sync_description_mentions() {
+        let db = test_db().await;
+        let config = test_config();
+        let creator_id = create_user(&db, "creator", "creator").await;
+        let executor_id = create_user(&db, "executor", "executor").await;
+        let invite_id = create_board(&db, &creator_id, Some(&executor_id)).await;
+        let start = Utc::now() - chrono::Duration::hours(1);
+        db.execute(sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            r#"
+            INSERT INTO recurring_tasks (id, invite_id, creator_id, title, description, rrule, start_at, paused, next_run_at, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            [
+                "weekly".into(),
+                invite_id.into(),
+                creator_id.into(),
+                "周报".into(),
+                "@executor 请整理本周进展".into(),
+                "FREQ=WEEKLY;INTERVAL=1".into(),
+                start.into(),
+                false.into(),
+                start.into(),
+                start.into(),
+                start.into(),
+            ],
+        ))
+        .await
+        .unwrap();
+
+        create_recurring_tasks(&db, &config).await.unwrap();
+
+        let mentions = Mention::find()
+            .filter(mention::Column::UserId.eq(&executor_id))
+            .all(&db)
+            .await
+            .unwrap();
+        assert_eq!(mentions.len(), 1);
+        assert_eq!(mentions[0].source, "description");
+    }
+
+    // 只清理超过保留期的回收站任务
+    #[tokio::test]
+    async fn purges_tasks_past_the_retention_period() {
+        let db = test_db().await;
+        let config = test_config();
+        let creator_id = create_user(&db, "creator", "creator").await;
+        let invite_id = create_board(&db, &creator_id, None).await;
+        let now = Utc::now();
+        let retention = chrono::Duration::days(config.trash_retention_days);
+        let mut task_ids = Vec::new();
+        for deleted_at in [Some(now - retention - chrono::Duration::hours(1)), Some(now - retention + chrono::Duration::hours(1)), None] {
+            let task = crate::test_util::create_task(&db, &invite_id, &creator_id, None).await;
+            let mut task_active: task::ActiveModel = task.into();
+            task_active.deleted_at = Set(deleted_at.map(Into::into));
+            task_ids.push(task_active.update(&db).await.unwrap().id);
+        }
+
+        purge_deleted_tasks(&db, &config).await.unwrap();
+
+        let remaining: HashSet<String> = Task::find()
+            .filter(task::Column::InviteId.eq(&invite_id))
+            .all(&db)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|task| task.id)
+            .collect();
+        assert_eq!(remaining, task_ids[1..].iter().cloned().collect());
+
+        // 保留天数为0时不自动清理
+        let keep_forever = Config { trash_retention_days: 0, ..test_config() };
+        let mut task_active: task::ActiveModel = Task::find_by_id(&task_ids[1]).one(&db).await.unwrap().unwrap().into();
+        task_active.deleted_at = Set(Some((now - chrono::Duration::days(3650)).into()));
+        task_active.update(&db).await.unwrap();
+        purge_deleted_tasks(&db, &keep_forever).await.unwrap();
+        assert!(Task::find_by_id(&task_ids[1]).one(&db).await.unwrap().is_some());
+    }
+}
@@ -0,0 +1,44 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+// 比较令牌时使用的固定密钥
+const TOKEN_COMPARE_KEY: &str = "token-compare";
+
+// 计算HMAC-SHA256签名，返回十六进制字符串
+pub fn sign(secret: &str, payload: &[u8]) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
+        .expect("HMAC可以接受任意长度的密钥");
+    mac.update(payload);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+// 常量时间比较两个令牌：比较两者的签名，耗时与令牌内容无关
+pub fn secure_eq(expected: &str, provided: &str) -> bool {
+    verify(TOKEN_COMPARE_KEY, expected.as_bytes(), &sign(TOKEN_COMPARE_KEY, provided.as_bytes()))
+}
+
+// 常量时间校验签名
+pub fn verify(secret: &str, payload: &[u8], signature: &str) -> bool {
+    let Ok(expected) = hex::decode(signature) else {
+        return false;
+    };
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
+        .expect("HMAC可以接受任意长度的密钥");
+    mac.update(payload);
+    mac.verify_slice(&expected).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secure_eq_matches_only_identical_tokens() {
+        assert!(secure_eq("admin-token", "admin-token"));
+        assert!(!secure_eq("admin-token", "admin-tokeN"));
+        assert!(!secure_eq("admin-token", "admin"));
+        assert!(!secure_eq("admin-token", ""));
+    }
+}
@@ -0,0 +1,96 @@
+// 测试使用的数据库、配置和基础数据
+use sea_orm::{ConnectionTrait, DatabaseConnection, EntityTrait};
+
+use crate::config::Config;
+use crate::database::Database;
+use crate::handlers::task::{insert_task, NewTask};
+use crate::models::{task, task::Entity as Task};
+
+pub async fn test_db() -> DatabaseConnection {
+    Database::in_memory().await.expect("创建测试数据库失败")
+}
+
+pub fn test_config() -> Config {
+    Config {
+        database_url: "sqlite::memory:".to_string(),
+        port: 0,
+        public_base_url: "http://127.0.0.1:20001".to_string(),
+        invite_link_secret: Some("test-secret".to_string()),
+        invite_link_ttl_hours: 72,
+        webhook_max_attempts: 6,
+        webhook_timeout_secs: 5,
+        smtp: None,
+        email_max_attempts: 5,
+        due_soon_hours: 24,
+        invite_expiry_days: 0,
+        trash_retention_days: 30,
+        admin_token: None,
+    }
+}
+
+// 创建用户，邮箱由用户名生成
+pub async fn create_user(db: &DatabaseConnection, username: &str, role: &str) -> String {
+    let user_id = uuid::Uuid::new_v4().to_string();
+    db.execute(sea_orm::Statement::from_sql_and_values(
+        sea_orm::DatabaseBackend::Sqlite,
+        "INSERT INTO users (id, username, email, password_hash, role, created_at) VALUES (?, ?, ?, ?, ?, ?)",
+        [
+            user_id.clone().into(),
+            username.into(),
+            format!("{}@example.com", username).into(),
+            "hash".into(),
+            role.into(),
+            chrono::Utc::now().into(),
+        ],
+    ))
+    .await
+    .expect("创建用户失败");
+    user_id
+}
+
+// 创建看板（邀请码），有执行者时邀请码为已使用
+pub async fn create_board(db: &DatabaseConnection, creator_id: &str, executor_id: Option<&str>) -> String {
+    let invite_id = uuid::Uuid::new_v4().to_string();
+    let now = chrono::Utc::now();
+    db.execute(sea_orm::Statement::from_sql_and_values(
+        sea_orm::DatabaseBackend::Sqlite,
+        "INSERT INTO invites (id, code, creator_id, executor_id, status, created_at, used_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
+        [
+            invite_id.clone().into(),
+            uuid::Uuid::new_v4().simple().to_string().into(),
+            creator_id.into(),
+            executor_id.map(str::to_string).into(),
+            if executor_id.is_some() { "used" } else { "pending" }.into(),
+            now.into(),
+            executor_id.map(|_| now).into(),
+        ],
+    ))
+    .await
+    .expect("创建看板失败");
+    invite_id
+}
+
+// 在看板上创建任务并分配给执行者
+pub async fn create_task(db: &DatabaseConnection, invite_id: &str, creator_id: &str, executor_id: Option<&str>) -> task::Model {
+    let task_id = insert_task(db, NewTask {
+        title: "测试任务".to_string(),
+        description: None,
+        creator_id: creator_id.to_string(),
+        invite_id: invite_id.to_string(),
+        due_date: None,
+        priority: None,
+        custom_fields: None,
+    })
+    .await
+    .expect("创建任务失败");
+
+    db.execute(sea_orm::Statement::from_sql_and_values(
+        sea_orm::DatabaseBackend::Sqlite,
+        "UPDATE tasks SET executor_id = ? WHERE id = ?",
+        [executor_id.map(str::to_string).into(), task_id.clone().into()],
+    ))
+    .await
+    .expect("分配任务失败");
+
+    Task::find_by_id(task_id).one(db).await.unwrap().unwrap()
+}
@@ -0,0 +1,299 @@
+use sea_orm::{ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set};
+
+use crate::events::Event;
+use crate::models::{webhook, webhook::Entity as Webhook, webhook_delivery, webhook_delivery::Entity as WebhookDelivery};
+use crate::{retry, signing};
+
+// 单次轮询最多处理的投递数
+const BATCH_SIZE: u64 = 20;
+
+// 为订阅了该事件的Webhook写入待投递记录
+pub async fn enqueue(db: &DatabaseConnection, event: &Event) -> Result<(), DbErr> {
+    let webhooks = Webhook::find()
+        .filter(webhook::Column::InviteId.eq(event.invite_id()))
+        .filter(webhook::Column::Active.eq(true))
+        .all(db)
+        .await?;
+
+    let event_type = event.event_type();
+    let now = chrono::Utc::now();
+    for hook in webhooks.into_iter().filter(|hook| hook.subscribes_to(event_type)) {
+        let delivery_id = uuid::Uuid::new_v4().to_string();
+        let mut payload = event.payload();
+        payload["delivery_id"] = delivery_id.clone().into();
+
+        db.execute(sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            r#"
+            INSERT INTO webhook_deliveries (id, webhook_id, event, payload, status, attempts, next_attempt_at, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            [
+                delivery_id.into(),
+                hook.id.into(),
+                event_type.into(),
+                payload.to_string().into(),
+                "pending".into(),
+                0.into(),
+                now.into(),
+                now.into(),
+            ],
+        ))
+        .await?;
+    }
+
+    Ok(())
+}
+
+// 投递所有到期的记录
+pub async fn deliver_due(db: &DatabaseConnection, client: &reqwest::Client, max_attempts: i32) -> Result<(), DbErr> {
+    let now: chrono::DateTime<chrono::FixedOffset> = chrono::Utc::now().into();
+    let deliveries = WebhookDelivery::find()
+        .filter(webhook_delivery::Column::Status.eq("pending"))
+        .filter(webhook_delivery::Column::NextAttemptAt.lte(now))
+        .order_by_asc(webhook_delivery::Column::NextAttemptAt)
+        .limit(BATCH_SIZE)
+        .all(db)
+        .await?;
+
+    for delivery in deliveries {
+        let Some(hook) = Webhook::find_by_id(&delivery.webhook_id).one(db).await? else {
+            continue;
+        };
+        deliver(db, client, &hook, delivery, max_attempts).await?;
+    }
+
+    Ok(())
+}
+
+// 投递单条记录并更新投递日志
+async fn deliver(
+    db: &DatabaseConnection,
+    client: &reqwest::Client,
+    hook: &webhook::Model,
+    delivery: webhook_delivery::Model,
+    max_attempts: i32,
+) -> Result<webhook_delivery::Model, DbErr> {
+    let signature = signing::sign(&hook.secret, delivery.payload.as_bytes());
+    let result = client
+        .post(&hook.url)
+        .header("Content-Type", "application/json")
+        .header("X-Signature", format!("sha256={}", signature))
+        .header("X-Webhook-Event", &delivery.event)
+        .header("X-Webhook-Delivery", &delivery.id)
+        .body(delivery.payload.clone())
+        .send()
+        .await;
+
+    let attempts = delivery.attempts + 1;
+    let now = chrono::Utc::now();
+    let mut delivery_active: webhook_delivery::ActiveModel = delivery.into();
+    delivery_active.attempts = Set(attempts);
+
+    let error = match result {
+        Ok(response) => {
+            delivery_active.last_status_code = Set(Some(response.status().as_u16() as i32));
+            if response.status().is_success() {
+                None
+            } else {
+                Some(format!("HTTP {}", response.status()))
+            }
+        }
+        Err(e) => {
+            delivery_active.last_status_code = Set(None);
+            Some(e.to_string())
+        }
+    };
+
+    match error {
+        None => {
+            delivery_active.status = Set("success".to_string());
+            delivery_active.last_error = Set(None);
+            delivery_active.next_attempt_at = Set(None);
+            delivery_active.delivered_at = Set(Some(now.into()));
+        }
+        Some(error) => {
+            tracing::warn!("Webhook投递失败（第{}次）: {}", attempts, error);
+            delivery_active.last_error = Set(Some(error));
+            if attempts >= max_attempts {
+                delivery_active.status = Set("failed".to_string());
+                delivery_active.next_attempt_at = Set(None);
+            } else {
+                let next_attempt_at = now + chrono::Duration::seconds(retry::backoff_secs(attempts));
+                delivery_active.next_attempt_at = Set(Some(next_attempt_at.into()));
+            }
+        }
+    }
+
+    delivery_active.update(db).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{extract::{Path, State}, http::{HeaderMap, StatusCode}, routing::post, Json, Router};
+    use std::sync::{Arc, Mutex};
+
+    use crate::handlers::webhook::{redeliver_webhook_delivery, RedeliverRequest};
+    use crate::models::invite::Entity as Invite;
+    use crate::test_util::{create_board, create_user, test_db};
+
+    type Received = Arc<Mutex<Vec<(HeaderMap, String)>>>;
+
+    // 本地HTTP替身：记录收到的请求，按给定状态码响应
+    async fn stand_in(status: StatusCode) -> (String, Received) {
+        let received: Received = Arc::default();
+        let app = Router::new()
+            .route("/hook", post(move |State(received): State<Received>, headers: HeaderMap, body: String| async move {
+                received.lock().unwrap().push((headers, body));
+                status
+            }))
+            .with_state(received.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (url, received)
+    }
+
+    fn client() -> reqwest::Client {
+        reqwest::Client::builder().no_proxy().build().unwrap()
+    }
+
+    // 注册订阅全部事件的Webhook并发布一个事件，返回Webhook和待投递记录
+    async fn setup(db: &DatabaseConnection, url: &str) -> (webhook::Model, webhook_delivery::Model) {
+        let creator_id = create_user(db, "creator", "creator").await;
+        let invite_id = create_board(db, &creator_id, None).await;
+        db.execute(sea_orm::Statement::from_sql_and_values(
+            sea_orm::DatabaseBackend::Sqlite,
+            "INSERT INTO webhooks (id, invite_id, creator_id, url, secret, events, active, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            [
+                "hook-1".into(),
+                invite_id.clone().into(),
+                creator_id.into(),
+                url.into(),
+                "hook-secret".into(),
+                "".into(),
+                true.into(),
+                chrono::Utc::now().into(),
+            ],
+        ))
+        .await
+        .unwrap();
+
+        let invite = Invite::find_by_id(&invite_id).one(db).await.unwrap().unwrap();
+        enqueue(db, &Event::InviteUsed { invite }).await.unwrap();
+
+        let hook = Webhook::find_by_id("hook-1").one(db).await.unwrap().unwrap();
+        let delivery = WebhookDelivery::find().one(db).await.unwrap().unwrap();
+        (hook, delivery)
+    }
+
+    async fn reload(db: &DatabaseConnection, delivery_id: &str) -> webhook_delivery::Model {
+        WebhookDelivery::find_by_id(delivery_id).one(db).await.unwrap().unwrap()
+    }
+
+    #[tokio::test]
+    async fn delivers_signed_payload_and_logs_success() {
+        let db = test_db().await;
+        let (url, received) = stand_in(StatusCode::OK).await;
+        let (hook, delivery) = setup(&db, &url).await;
+        assert_eq!(delivery.status, "pending");
+
+        deliver_due(&db, &client(), 6).await.unwrap();
+
+        let (headers, body) = {
+            let received = received.lock().unwrap();
+            assert_eq!(received.len(), 1);
+            received[0].clone()
+        };
+        assert_eq!(body, delivery.payload);
+        let signature = headers["x-signature"].to_str().unwrap().strip_prefix("sha256=").unwrap();
+        assert!(signing::verify(&hook.secret, body.as_bytes(), signature));
+        assert!(!signing::verify("other-secret", body.as_bytes(), signature));
+        assert_eq!(headers["x-webhook-event"], "invite.used");
+        assert_eq!(headers["x-webhook-delivery"], delivery.id.as_str());
+
+        let delivery = reload(&db, &delivery.id).await;
+        assert_eq!(delivery.status, "success");
+        assert_eq!(delivery.attempts, 1);
+        assert_eq!(delivery.last_status_code, Some(200));
+        assert!(delivery.last_error.is_none());
+        assert!(delivery.next_attempt_at.is_none());
+        assert!(delivery.delivered_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn failed_delivery_backs_off_until_max_attempts() {
+        let db = test_db().await;
+        let (url, received) = stand_in(StatusCode::INTERNAL_SERVER_ERROR).await;
+        let (_, delivery) = setup(&db, &url).await;
+
+        let before = chrono::Utc::now();
+        deliver_due(&db, &client(), 2).await.unwrap();
+        let after = chrono::Utc::now();
+
+        let delivery = reload(&db, &delivery.id).await;
+        assert_eq!(delivery.status, "pending");
+        assert_eq!(delivery.attempts, 1);
+        assert_eq!(delivery.last_status_code, Some(500));
+        assert!(delivery.last_error.as_deref().unwrap().contains("500"));
+        let next_attempt_at = delivery.next_attempt_at.unwrap();
+        assert!(next_attempt_at >= before + chrono::Duration::seconds(30));
+        assert!(next_attempt_at <= after + chrono::Duration::seconds(30));
+
+        // 还没到重试时间时不投递
+        deliver_due(&db, &client(), 2).await.unwrap();
+        assert_eq!(received.lock().unwrap().len(), 1);
+
+        let mut delivery_active: webhook_delivery::ActiveModel = delivery.into();
+        delivery_active.next_attempt_at = Set(Some(chrono::Utc::now().into()));
+        let delivery = delivery_active.update(&db).await.unwrap();
+        deliver_due(&db, &client(), 2).await.unwrap();
+
+        let delivery = reload(&db, &delivery.id).await;
+        assert_eq!(received.lock().unwrap().len(), 2);
+        assert_eq!(delivery.status, "failed");
+        assert_eq!(delivery.attempts, 2);
+        assert!(delivery.next_attempt_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn redeliver_resets_failed_delivery() {
+        let db = test_db().await;
+        let (url, _) = stand_in(StatusCode::INTERNAL_SERVER_ERROR).await;
+        let (hook, delivery) = setup(&db, &url).await;
+        deliver_due(&db, &client(), 1).await.unwrap();
+        assert_eq!(reload(&db, &delivery.id).await.status, "failed");
+
+        let other = redeliver_webhook_delivery(
+            State(db.clone()),
+            Path(delivery.id.clone()),
+            Json(RedeliverRequest { creator_id: "someone-else".to_string() }),
+        )
+        .await;
+        assert_eq!(other.err(), Some(StatusCode::NOT_FOUND));
+
+        let (status, Json(response)) = redeliver_webhook_delivery(
+            State(db.clone()),
+            Path(delivery.id.clone()),
+            Json(RedeliverRequest { creator_id: hook.creator_id.clone() }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, StatusCode::ACCEPTED);
+        assert_eq!(response.status, "pending");
+        assert_eq!(response.attempts, 0);
+
+        // 接收端恢复后重新投递成功
+        let (url, received) = stand_in(StatusCode::OK).await;
+        let mut hook_active: webhook::ActiveModel = hook.into();
+        hook_active.url = Set(url);
+        hook_active.update(&db).await.unwrap();
+        deliver_due(&db, &client(), 1).await.unwrap();
+
+        assert_eq!(received.lock().unwrap().len(), 1);
+        let delivery = reload(&db, &delivery.id).await;
+        assert_eq!(delivery.status, "success");
+        assert_eq!(delivery.attempts, 1);
+    }
+}
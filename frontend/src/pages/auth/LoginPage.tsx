@@ -1,5 +1,5 @@
 import React, { useState } from 'react';
-import { Link, useNavigate } from 'react-router-dom';
+import { Link, useLocation, useNavigate, type Location } from 'react-router-dom';
 import { useForm } from 'react-hook-form';
 import { yupResolver } from '@hookform/resolvers/yup';
 import * as yup from 'yup';
@@ -22,6 +22,8 @@ type FormData = yup.InferType<typeof schema>;
 
 export const LoginPage: React.FC = () => {
   const navigate = useNavigate();
+  const location = useLocation();
+  const from = (location.state as { from?: Location } | null)?.from;
   const { login, isLoading } = useAuth();
   const [error, setError] = useState<string>('');
 
@@ -37,7 +39,7 @@ export const LoginPage: React.FC = () => {
     try {
       setError('');
       await login(data.email, data.password);
-      navigate('/dashboard');
+      navigate(from ? `${from.pathname}${from.search}` : '/dashboard', { replace: true });
     } catch (err) {
       setError(err instanceof Error ? err.message : '登录失败');
     }
@@ -1,6 +1,7 @@
-import React, { useState } from 'react';
+import React, { useEffect, useRef, useState } from 'react';
+import { useSearchParams } from 'react-router-dom';
 import { useAuth } from '../../context/AuthContext';
-import { useInvite } from '../../services/invite';
+import { useInvite, redeemInviteLink } from '../../services/invite';
 import { Button } from '../../components/ui/Button';
 import { Input } from '../../components/ui/Input';
 
@@ -10,6 +11,37 @@ export const UseInvitePage: React.FC = () => {
   const [isLoading, setIsLoading] = useState<boolean>(false);
   const [error, setError] = useState<string>('');
   const [success, setSuccess] = useState<string>('');
+  const [searchParams, setSearchParams] = useSearchParams();
+  const linkToken = searchParams.get('token');
+  const linkRedeemed = useRef<boolean>(false);
+
+  // 通过邀请链接或二维码打开时自动绑定，令牌只使用一次
+  useEffect(() => {
+    if (!user || user.role !== 'executor' || !linkToken || linkRedeemed.current) return;
+    linkRedeemed.current = true;
+
+    const redeemLink = async () => {
+      setIsLoading(true);
+      setError('');
+      setSuccess('');
+
+      try {
+        const response = await redeemInviteLink({
+          token: linkToken,
+          executor_id: user.id
+        });
+
+        setSuccess(response.message);
+      } catch (err) {
+        setError(err instanceof Error ? err.message : '邀请链接无效或已过期');
+      } finally {
+        setIsLoading(false);
+        setSearchParams({}, { replace: true });
+      }
+    };
+
+    redeemLink();
+  }, [user, linkToken, setSearchParams]);
 
   const handleUseInvite = async (e: React.FormEvent) => {
     e.preventDefault();
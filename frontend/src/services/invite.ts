@@ -19,6 +19,11 @@ export interface UseInviteRequest {
   executor_id: string;
 }
 
+export interface UseInviteLinkRequest {
+  token: string;
+  executor_id: string;
+}
+
 export interface UseInviteResponse {
   message: string;
   invite: Invite;
@@ -38,6 +43,11 @@ export const useInvite = async (data: UseInviteRequest): Promise<UseInviteRespon
   return await api.post('/invites/use', data);
 };
 
+// 通过邀请链接使用邀请码
+export const redeemInviteLink = async (data: UseInviteLinkRequest): Promise<UseInviteResponse> => {
+  return await api.post('/invites/link/use', data);
+};
+
 // 获取邀请码列表（创建者视角）
 export const getInvites = async (creatorId: string): Promise<InviteListResponse> => {
   return await api.get(`/invites/${creatorId}`);